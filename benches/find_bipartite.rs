@@ -0,0 +1,39 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use labisu::bipartite::find_bipartite;
+use labisu::graphs::Graph;
+
+// Both graphs satisfy |E| ^ 2 > 64 * |V| ^ 3, so the Gray code search is actually run.
+
+fn complete(c: &mut Criterion) {
+    let graph = Graph::complete(300);
+    c.bench_function("find_bipartite complete(300) pool 10 size 3", |b| {
+        b.iter(|| find_bipartite(&graph, 10, 3))
+    });
+}
+
+// Common neighbourhoods of 8 vertices are small compared to the pool,
+// hence a number of subsets has to be examined before the search succeeds.
+fn long_search(c: &mut Criterion) {
+    let graph = Graph::random(1100, 0.55);
+    let mut group = c.benchmark_group("long_search");
+    group.sample_size(10);
+    group.bench_function("find_bipartite random(1100, 0.55) pool 20 size 8", |b| {
+        b.iter(|| find_bipartite(&graph, 20, 8))
+    });
+    group.finish();
+}
+
+// No subset of 10 vertices of the pool has 20 common neighbours, so all C(20, 10) subsets are examined.
+// The neighbourhoods are large compared to |V| / 64, hence they are intersected as packed bitmaps with popcount.
+fn exhaustive(c: &mut Criterion) {
+    let graph = Graph::random(2000, 0.5);
+    let mut group = c.benchmark_group("exhaustive");
+    group.sample_size(10);
+    group.bench_function("find_bipartite random(2000, 0.5) pool 20 size 10", |b| {
+        b.iter(|| find_bipartite(&graph, 20, 10))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, complete, long_search, exhaustive);
+criterion_main!(benches);
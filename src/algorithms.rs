@@ -0,0 +1,116 @@
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::sync::{Mutex, OnceLock};
+
+use super::bipartite::{find_bipartite, qr_parameters_checked};
+use super::graphs::stats::{assortativity, rich_club_coefficients};
+use super::graphs::GraphOps;
+use super::traversal::connected_components;
+
+/// An algorithm which can be run by name on any graph. GraphOps is object safe, so the graph is passed as a trait object.
+/// The parameters and the result are json values, so that they can be given and printed by a command line tool.
+pub type Algorithm = fn(&dyn GraphOps, &Value) -> Result<Value, String>;
+
+/// The registered algorithms by name.
+fn registry() -> &'static Mutex<BTreeMap<String, Algorithm>> {
+    static REGISTRY: OnceLock<Mutex<BTreeMap<String, Algorithm>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        let mut algorithms: BTreeMap<String, Algorithm> = BTreeMap::new();
+        algorithms.insert("assortativity".to_owned(), run_assortativity);
+        algorithms.insert("connected_components".to_owned(), run_connected_components);
+        algorithms.insert("find_bipartite".to_owned(), run_find_bipartite);
+        algorithms.insert("qr_parameters".to_owned(), run_qr_parameters);
+        algorithms.insert("rich_club".to_owned(), run_rich_club);
+        Mutex::new(algorithms)
+    })
+}
+
+/// Registers an algorithm under given name, e.g. one defined in a downstream crate, replacing an algorithm with the same name.
+/// Returns true if there was no algorithm with the name before.
+/// # Examples
+/// ```
+/// use labisu::algorithms;
+/// use labisu::graphs::{Graph, GraphOps};
+/// use serde_json::{json, Value};
+///
+/// fn max_degree(graph: &dyn GraphOps, _: &Value) -> Result<Value, String> {
+///     Ok(json!(graph.vertices().map(|v| graph.degree(v)).max()))
+/// }
+/// assert!(algorithms::register("max_degree", max_degree));
+/// assert!(algorithms::names().contains(&"max_degree".to_string()));
+/// assert_eq!(Ok(json!(3)), algorithms::run("max_degree", &Graph::petersen(), &json!({})));
+/// ```
+pub fn register(name: &str, algorithm: Algorithm) -> bool {
+    registry().lock().unwrap().insert(name.to_owned(), algorithm).is_none()
+}
+
+/// Returns the names of all registered algorithms, sorted.
+/// # Examples
+/// ```
+/// use labisu::algorithms;
+/// assert!(algorithms::names().contains(&"find_bipartite".to_string()));
+/// ```
+pub fn names() -> Vec<String> {
+    registry().lock().unwrap().keys().cloned().collect()
+}
+
+/// Runs the algorithm with given name on the graph with given parameters.
+/// Returns an Err value if there is no such algorithm or the algorithm fails, e.g. because of missing parameters.
+/// # Examples
+/// ```
+/// use labisu::algorithms;
+/// use labisu::graphs::Graph;
+/// use serde_json::json;
+/// let k300 = Graph::complete(300);
+/// let result = algorithms::run("find_bipartite", &k300, &json!({"highest_degree_size": 10, "bipartite_size": 3})).unwrap();
+/// assert_eq!(json!([0, 1, 2]), result["left"]);
+/// assert!(algorithms::run("find_bipartite", &k300, &json!({})).is_err());
+/// assert!(algorithms::run("unknown", &k300, &json!({})).is_err());
+/// ```
+pub fn run(name: &str, graph: &dyn GraphOps, parameters: &Value) -> Result<Value, String> {
+    let algorithm = *registry().lock().unwrap().get(name).ok_or_else(|| format!("unknown algorithm {}", name))?;
+    algorithm(graph, parameters)
+}
+
+/// Reads a non-negative integer parameter with given name.
+pub fn usize_parameter(parameters: &Value, name: &str) -> Result<usize, String> {
+    parameters
+    .get(name)
+    .and_then(Value::as_u64)
+    .map(|value| value as usize)
+    .ok_or_else(|| format!("missing non-negative integer parameter {}", name))
+}
+
+/// Returns the elements of a set sorted, so that the results are deterministic.
+fn sorted<I: IntoIterator<Item = usize>>(set: I) -> Vec<usize> {
+    let mut sorted = set.into_iter().collect::<Vec<usize>>();
+    sorted.sort_unstable();
+    sorted
+}
+
+fn run_find_bipartite(graph: &dyn GraphOps, parameters: &Value) -> Result<Value, String> {
+    let highest_degree_size = usize_parameter(parameters, "highest_degree_size")?;
+    let bipartite_size = usize_parameter(parameters, "bipartite_size")?;
+    if highest_degree_size > graph.get_num_of_vertices() || bipartite_size > highest_degree_size {
+        return Err("the parameters have to satisfy bipartite_size <= highest_degree_size <= number of vertices".to_owned())
+    }
+    let (left, right) = find_bipartite(&graph, highest_degree_size, bipartite_size);
+    Ok(json!({ "left": sorted(left), "right": sorted(right) }))
+}
+
+fn run_qr_parameters(graph: &dyn GraphOps, _: &Value) -> Result<Value, String> {
+    let parameters = qr_parameters_checked(&graph)?;
+    serde_json::to_value(parameters).map_err(|e| e.to_string())
+}
+
+fn run_assortativity(graph: &dyn GraphOps, _: &Value) -> Result<Value, String> {
+    Ok(json!(assortativity(&graph)))
+}
+
+fn run_rich_club(graph: &dyn GraphOps, _: &Value) -> Result<Value, String> {
+    Ok(json!(rich_club_coefficients(&graph)))
+}
+
+fn run_connected_components(graph: &dyn GraphOps, _: &Value) -> Result<Value, String> {
+    Ok(json!(connected_components(&graph)))
+}
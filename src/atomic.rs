@@ -0,0 +1,113 @@
+use std::fs::File;
+use std::io::{BufWriter, Result, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// Whether committed files are synced to the disk, see set_sync.
+static SYNC: AtomicBool = AtomicBool::new(false);
+/// The counter making the names of temporary files written at the same time by one process distinct.
+static TEMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Sets whether the files written by the crate are synced to the disk (with fsync) before they replace the target files.
+/// Syncing makes the writes durable also after a power failure, at the cost of speed. It is disabled by default.
+/// # Examples
+/// ```
+/// use labisu::atomic;
+/// atomic::set_sync(true);
+/// assert!(atomic::is_sync());
+/// atomic::set_sync(false);
+/// ```
+pub fn set_sync(enabled: bool) {
+    SYNC.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns whether the files written by the crate are synced to the disk, see set_sync.
+pub fn is_sync() -> bool {
+    SYNC.load(Ordering::Relaxed)
+}
+
+/// A file written to a temporary file in the same directory as the target, which replaces the target only when commit is called.
+/// If the writing is interrupted or fails, the target keeps its previous contents and the temporary file is removed when dropped,
+/// so a checkpoint never corrupts the only copy of the data. All writers of the crate which rewrite whole files use it.
+/// # Examples
+/// ```
+/// use labisu::atomic::AtomicFile;
+/// use std::io::Write;
+/// std::fs::write("atomic.txt", "old").unwrap();
+/// let mut file = AtomicFile::create("atomic.txt").unwrap();
+/// file.write_all(b"new").unwrap();
+/// assert_eq!("old", std::fs::read_to_string("atomic.txt").unwrap());
+/// file.commit().unwrap();
+/// assert_eq!("new", std::fs::read_to_string("atomic.txt").unwrap());
+///
+/// let mut file = AtomicFile::create("atomic.txt").unwrap();
+/// file.write_all(b"interrupted").unwrap();
+/// drop(file);
+/// assert_eq!("new", std::fs::read_to_string("atomic.txt").unwrap());
+/// ```
+pub struct AtomicFile {
+    writer: Option<BufWriter<File>>,
+    temp_path: PathBuf,
+    path: PathBuf,
+}
+
+impl AtomicFile {
+    /// Creates the temporary file for the target with given path.
+    pub fn create<P: AsRef<Path>>(path: P) -> Result<AtomicFile> {
+        let path = path.as_ref().to_path_buf();
+        let name = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+        let temp_name = format!(".{}.{}-{}.tmp", name, std::process::id(), TEMP_COUNTER.fetch_add(1, Ordering::Relaxed));
+        let temp_path = path.with_file_name(temp_name);
+        let file = File::create(&temp_path)?;
+        Ok(AtomicFile { writer: Some(BufWriter::new(file)), temp_path, path })
+    }
+
+    /// Flushes the written data and atomically replaces the target with it, syncing it first if set_sync was enabled.
+    pub fn commit(mut self) -> Result<()> {
+        let mut writer = self.writer.take().expect("the writer is present until commit");
+        let flushed = writer.flush().and_then(|()| if is_sync() { writer.get_ref().sync_all() } else { Ok(()) });
+        drop(writer); // the file is closed before it is renamed
+        if let Err(e) = flushed.and_then(|()| std::fs::rename(&self.temp_path, &self.path)) {
+            let _ = std::fs::remove_file(&self.temp_path);
+            return Err(e)
+        }
+        #[cfg(unix)]
+        if is_sync() {
+            // the rename itself is durable only after the directory is synced
+            let dir = self.path.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(Path::new("."));
+            File::open(dir)?.sync_all()?;
+        }
+        Ok(())
+    }
+}
+
+impl Write for AtomicFile {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.writer.as_mut().expect("the writer is present until commit").write(buf)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.writer.as_mut().expect("the writer is present until commit").flush()
+    }
+}
+
+impl Drop for AtomicFile {
+    fn drop(&mut self) {
+        if self.writer.take().is_some() {
+            let _ = std::fs::remove_file(&self.temp_path);
+        }
+    }
+}
+
+/// Writes the contents to the file with given path atomically, like std::fs::write but with AtomicFile.
+/// # Examples
+/// ```
+/// use labisu::atomic;
+/// atomic::write("contents.txt", "contents").unwrap();
+/// assert_eq!("contents", std::fs::read_to_string("contents.txt").unwrap());
+/// ```
+pub fn write<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) -> Result<()> {
+    let mut file = AtomicFile::create(path)?;
+    file.write_all(contents.as_ref())?;
+    file.commit()
+}
@@ -0,0 +1,188 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufReader, Result};
+use std::path::Path;
+
+use super::atomic::AtomicFile;
+use super::bipartite::find_bipartite_certified;
+use super::graphs::{densest_subgraph, Graph, GraphOps};
+use super::traversal::connected_components;
+
+/// The name of the file of a bundle storing the graph, written with Graph::write_to_json_edge_list.
+pub const GRAPH_FILE: &str = "graph.json";
+/// The name of the file of a bundle storing the metadata.
+pub const METADATA_FILE: &str = "metadata.json";
+
+/// A complete bipartite subgraph found in the instance, published as a reference value for other searches.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KnownBiclique {
+    /// The number of vertices of the highest degrees searched by find_bipartite_certified.
+    pub highest_degree_size: usize,
+    /// The sorted indices of the vertices of one side.
+    pub left: Vec<usize>,
+    /// The sorted indices of the vertices of the other side.
+    pub right: Vec<usize>,
+}
+
+/// The description of a benchmark instance, stored next to its graph.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BenchmarkMetadata {
+    /// The name of the instance.
+    pub name: String,
+    /// The number of vertices of the instance.
+    pub num_of_vertices: usize,
+    /// The number of edges of the instance.
+    pub num_of_edges: usize,
+    /// The number of vertices of the graph the instance was extracted from.
+    pub source_num_of_vertices: usize,
+    /// The number of edges of the graph the instance was extracted from.
+    pub source_num_of_edges: usize,
+    /// The origin of the graph, e.g. the root url of the crawl.
+    pub source: Option<String>,
+    /// The date when the graph was obtained, e.g. the day of the crawl.
+    pub source_date: Option<String>,
+    /// The parameters with which the graph was obtained, e.g. the depth of the crawl.
+    pub parameters: BTreeMap<String, String>,
+    /// The largest complete bipartite subgraph found in the instance, if it was searched for.
+    pub known_biclique: Option<KnownBiclique>,
+}
+
+/// An anonymized instance extracted from a graph, e.g. a crawl, that can be published as a benchmark
+/// and read back by anyone using the crate.
+/// # Examples
+/// ```
+/// use labisu::benchmark::BenchmarkBundle;
+/// use labisu::graphs::Graph;
+/// let mut crawl = Graph::complete(6);
+/// crawl.add_vertex("https://example.com/");
+/// crawl.add_edge_idx(0, 6);
+/// let bundle =
+///     BenchmarkBundle::from_graph(&crawl, "example")
+///     .with_source("https://example.com/")
+///     .with_source_date("2024-05-01")
+///     .with_parameter("depth", "3")
+///     .with_known_biclique(3, 3);
+/// assert_eq!(6, bundle.metadata.num_of_vertices);
+/// assert_eq!(7, bundle.metadata.source_num_of_vertices);
+/// assert!(!bundle.graph.contains_vertex("https://example.com/"));
+/// assert_eq!(3, bundle.metadata.known_biclique.as_ref().unwrap().left.len());
+///
+/// bundle.write_to_dir("example_bundle").unwrap();
+/// assert!(bundle == BenchmarkBundle::read_from_dir("example_bundle").unwrap());
+/// ```
+#[derive(Clone)]
+pub struct BenchmarkBundle {
+    /// The instance, with vertices named vertex_0, vertex_1, ... in non-increasing order of degrees.
+    pub graph: Graph,
+    /// The description of the instance.
+    pub metadata: BenchmarkMetadata,
+}
+
+impl PartialEq for BenchmarkBundle {
+    fn eq(&self, other: &BenchmarkBundle) -> bool {
+        self.metadata == other.metadata
+            && self.graph.get_num_of_vertices() == other.graph.get_num_of_vertices()
+            && self.graph.vertices().all(|v| self.graph.neighbours_idx(v) == other.graph.neighbours_idx(v))
+    }
+}
+
+/// Returns the vertices of the largest connected component of the densest subgraph found by densest_subgraph.
+fn densest_component(graph: &Graph) -> Vec<usize> {
+    let densest = densest_subgraph(graph).vertices;
+    let subgraph = graph.induced_subgraph(&densest).unwrap();
+    let labels = connected_components(&subgraph);
+    let mut sizes = vec![0; labels.len()];
+    labels.iter().for_each(|label| sizes[*label] += 1);
+    let largest = (0..sizes.len()).max_by_key(|label| (sizes[*label], std::cmp::Reverse(*label)));
+    labels
+    .iter()
+    .enumerate()
+    .filter(|(_, label)| Some(**label) == largest)
+    .map(|(idx, _)| densest[idx])
+    .collect()
+}
+
+impl BenchmarkBundle {
+    /// Creates a bundle from the largest connected component of the densest subgraph of a graph (see densest_subgraph).
+    /// The names of the vertices, e.g. urls, are stripped: the vertices are renumbered by non-increasing degree and named vertex_0, vertex_1, ...
+    pub fn from_graph(graph: &Graph, name: &str) -> BenchmarkBundle {
+        let component = graph.induced_subgraph(&densest_component(graph)).unwrap();
+        let (ordered, _) = component.reorder_by_degree();
+        let mut anonymized = Graph::from_names(ordered.vertices().map(|v| format!("vertex_{}", v)).collect());
+        for v in ordered.vertices() {
+            ordered.for_each_neighbour(v, &mut |u| {
+                if v < u {
+                    anonymized.add_edge_idx(v, u);
+                }
+            });
+        }
+
+        let metadata = BenchmarkMetadata {
+            name: name.to_owned(),
+            num_of_vertices: anonymized.get_num_of_vertices(),
+            num_of_edges: anonymized.get_num_of_edges(),
+            source_num_of_vertices: graph.get_num_of_vertices(),
+            source_num_of_edges: graph.get_num_of_edges(),
+            source: None,
+            source_date: None,
+            parameters: BTreeMap::new(),
+            known_biclique: None,
+        };
+        BenchmarkBundle { graph: anonymized, metadata }
+    }
+
+    /// Sets the origin of the graph, e.g. the root url of the crawl.
+    pub fn with_source(mut self, source: &str) -> BenchmarkBundle {
+        self.metadata.source = Some(source.to_owned());
+        self
+    }
+
+    /// Sets the date when the graph was obtained.
+    pub fn with_source_date(mut self, date: &str) -> BenchmarkBundle {
+        self.metadata.source_date = Some(date.to_owned());
+        self
+    }
+
+    /// Records a parameter with which the graph was obtained, replacing the previous value of the key.
+    pub fn with_parameter(mut self, key: &str, value: &str) -> BenchmarkBundle {
+        self.metadata.parameters.insert(key.to_owned(), value.to_owned());
+        self
+    }
+
+    /// Searches the instance for K_{t,t} with find_bipartite_certified for t = 1, 2, ..., max_bipartite_size and records the largest one found,
+    /// so that the bundle carries a verifiable lower bound for other biclique searches.
+    pub fn with_known_biclique(mut self, highest_degree_size: usize, max_bipartite_size: usize) -> BenchmarkBundle {
+        let mut best = None;
+        for bipartite_size in 1..=max_bipartite_size {
+            let Ok((left, right)) = find_bipartite_certified(&self.graph, highest_degree_size, bipartite_size) else {
+                break
+            };
+            let (mut left, mut right) = (left.into_iter().collect::<Vec<usize>>(), right.into_iter().collect::<Vec<usize>>());
+            left.sort_unstable();
+            right.sort_unstable();
+            best = Some(KnownBiclique { highest_degree_size, left, right });
+        }
+        self.metadata.known_biclique = best;
+        self
+    }
+
+    /// Writes the bundle to the directory with given name, creating it if needed,
+    /// with the graph in the file GRAPH_FILE and the metadata in the file METADATA_FILE.
+    pub fn write_to_dir(&self, dir: &str) -> Result<()> {
+        std::fs::create_dir_all(dir)?;
+        let dir = Path::new(dir);
+        self.graph.write_to_json_edge_list(dir.join(GRAPH_FILE).to_str().unwrap())?;
+        let mut file = AtomicFile::create(dir.join(METADATA_FILE))?;
+        serde_json::to_writer_pretty(&mut file, &self.metadata)?;
+        file.commit()
+    }
+
+    /// Reads a bundle from the directory with given name written by write_to_dir.
+    pub fn read_from_dir(dir: &str) -> Result<BenchmarkBundle> {
+        let dir = Path::new(dir);
+        let metadata: BenchmarkMetadata = serde_json::from_reader(BufReader::new(File::open(dir.join(METADATA_FILE))?))?;
+        let graph = Graph::try_read_from_json(&dir.join(GRAPH_FILE).to_string_lossy())?;
+        Ok(BenchmarkBundle { graph, metadata })
+    }
+}
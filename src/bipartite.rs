@@ -1,69 +1,373 @@
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
+use std::fs::File;
 
-use super::graphs::Graph;
-use super::combinatorics::GraySubsets;
+use super::atomic::AtomicFile;
+use super::graphs::GraphOps;
+use super::combinatorics::{binomial, next_combination, unrank_combination, GraySubsets};
 
+mod cache;
+mod counting;
+mod ilp;
+mod neighbourhood;
+mod pool_cache;
+mod report;
+mod restarts;
+mod sat;
+mod weighted;
+pub use cache::{CachedResult, ResultCache};
+pub use counting::{count_bicliques, MAX_COUNTED_SIDE};
+pub use ilp::{read_biclique_solution, write_biclique_lp};
+pub use neighbourhood::BicliqueNeighbourhood;
+pub use pool_cache::PoolCache;
+pub use report::{BipartiteReport, ReportParameters, ReportSet, ReportStats};
+pub use restarts::{find_bipartite_restarts, PoolStrategy, RestartStats, RestartsReport};
+pub use sat::SatEncoding;
+pub use weighted::{find_bipartite_weighted, max_weight_bipartite, WeightedBiclique};
+
+/// The largest bipartite_size the search supports, as the common neighbour counters are kept as u16.
+pub const MAX_BIPARTITE_SIZE: usize = u16::MAX as usize;
+
+/// The number of bits in a word of a packed neighbourhood.
+const WORD_BITS: usize = 64;
+
+/// The number of changes of the subset after which the sums of edge weights are recomputed from scratch.
+const WEIGHT_RESYNC_INTERVAL: usize = 1024;
+
+/// The way the common neighbours of the current subset of the pool are counted.
+enum Counters {
+    /// A u16 counter for every vertex, updated with the neighbourhood of every vertex entering or leaving the subset.
+    /// Neighbourhoods of the pool vertices are copied once into one contiguous buffer (offsets as in the CSR format),
+    /// so that the updates walk over flat memory only.
+    Scalar {
+        pool_offsets: Vec<usize>,
+        pool_neighbours: Vec<usize>,
+        threshold: u16,
+        count_array: Vec<u16>,
+    },
+    /// The neighbourhoods outside the pool packed into bitmaps, intersected word by word and counted with popcount
+    /// when the subset changes, which is cheaper than the counter updates if the neighbourhoods are large compared to n / 64.
+    Packed {
+        neighbourhoods: Vec<Vec<u64>>,
+        common: Vec<u64>,
+        stale: bool, // common does not match the subset yet
+    },
+}
+
+/// The weights of the edges of the pool for the weighted search, kept next to the counters,
+/// with the total weight of the edges from the current subset to every vertex.
+struct EdgeWeights {
+    pool_weights: Vec<f64>, // of the edges to pool_neighbours of the counters
+    weight_array: Vec<f64>,
+    full_weight: f64, // total weight of the vertices outside the pool adjacent to the whole current subset
+    changes: usize, // since the sums were recomputed, as the updates accumulate rounding errors
+}
+
+/// Common neighbour counters of the current subset of the pool.
 struct CountArray {
-    highest_degree_vec: Vec<usize>, 
-    highest_degree_set: HashSet<usize>, 
-    highest_degree_count: usize, 
-    subgraph_size: usize, 
-    count_array: Vec<usize>,
+    counters: Counters,
+    weights: Option<EdgeWeights>,
+    in_pool: Vec<bool>,
+    in_subset: Vec<bool>, // by the position in the pool
+    highest_degree_count: usize,
+    subgraph_size: usize,
+    full_count: usize, // vertices outside the pool adjacent to the whole current subset
 }
 
 impl CountArray {
-    fn new(highest_degree_vec: &Vec<usize>, subgraph_size: usize, g: &Graph) -> CountArray {
-        let mut count_array = vec![0; g.get_num_of_vertices()];
-        
-        (0..subgraph_size).for_each(|i| {
-            g.neighbours_idx(highest_degree_vec[i]).unwrap().iter().for_each(|j| {
-                count_array[*j] += 1;
-            });
-        });
-        
-        CountArray {
-            highest_degree_vec: highest_degree_vec.clone(), 
-            highest_degree_set: highest_degree_vec.clone().into_iter().collect(), 
-            highest_degree_count: highest_degree_vec.len(),
+    /// Creates the counters for the subset of the pool given by its characteristic vector,
+    /// packing the neighbourhoods if it makes the updates cheaper.
+    /// Returns an Err value if subgraph_size exceeds MAX_BIPARTITE_SIZE.
+    fn new<G: GraphOps>(highest_degree_vec: &[usize], curr_subset: &[usize], subgraph_size: usize, g: &G) -> Result<CountArray, &'static str> {
+        let threshold = u16::try_from(subgraph_size).map_err(|_| "The size of the bipartite subgraph exceeds MAX_BIPARTITE_SIZE.")?;
+        let n = g.get_num_of_vertices();
+        let mut in_pool = vec![false; n];
+        highest_degree_vec.iter().for_each(|v| in_pool[*v] = true);
+
+        // a change of the subset costs two scattered neighbourhood updates for the counters and subgraph_size + 1 sequential
+        // bitmap passes for the packed neighbourhoods, which are measured to be about 4 times cheaper per word than an update
+        let words = n.div_ceil(WORD_BITS);
+        let pool_degrees = highest_degree_vec.iter().map(|v| g.degree(*v)).sum::<usize>();
+        let counters = if (subgraph_size + 1) * words * highest_degree_vec.len() < 8 * pool_degrees {
+            let neighbourhoods =
+                highest_degree_vec
+                .iter()
+                .map(|v| {
+                    let mut bits = vec![0u64; words];
+                    g.for_each_neighbour(*v, &mut |u| if !in_pool[u] { bits[u / WORD_BITS] |= 1 << (u % WORD_BITS) });
+                    bits
+                })
+                .collect();
+            Counters::Packed { neighbourhoods, common: vec![0; words], stale: true }
+        } else {
+            let (pool_offsets, pool_neighbours) = CountArray::flatten_pool(highest_degree_vec, g);
+            Counters::Scalar { pool_offsets, pool_neighbours, threshold, count_array: vec![0; n] }
+        };
+        Ok(CountArray::from_counters(counters, None, in_pool, curr_subset, subgraph_size))
+    }
+
+    /// Creates the counters like new, together with the total weight of the edges from the subset to its common neighbours
+    /// outside the pool, where weight(v, u) is the weight of the edge between v in the pool and u.
+    /// The weights need a counter for every vertex, so the neighbourhoods are never packed.
+    /// Returns an Err value if subgraph_size exceeds MAX_BIPARTITE_SIZE.
+    fn new_weighted<G: GraphOps, W: Fn(usize, usize) -> f64>(highest_degree_vec: &[usize], curr_subset: &[usize], subgraph_size: usize, g: &G, weight: &W) -> Result<CountArray, &'static str> {
+        let threshold = u16::try_from(subgraph_size).map_err(|_| "The size of the bipartite subgraph exceeds MAX_BIPARTITE_SIZE.")?;
+        let n = g.get_num_of_vertices();
+        let mut in_pool = vec![false; n];
+        highest_degree_vec.iter().for_each(|v| in_pool[*v] = true);
+
+        let (pool_offsets, pool_neighbours) = CountArray::flatten_pool(highest_degree_vec, g);
+        let pool_weights = 
+            highest_degree_vec
+            .iter()
+            .enumerate()
+            .flat_map(|(i, v)| pool_neighbours[pool_offsets[i]..pool_offsets[i + 1]].iter().map(|u| weight(*v, *u)))
+            .collect();
+        let weights = EdgeWeights { pool_weights, weight_array: vec![0.0; n], full_weight: 0.0, changes: 0 };
+        let counters = Counters::Scalar { pool_offsets, pool_neighbours, threshold, count_array: vec![0; n] };
+        Ok(CountArray::from_counters(counters, Some(weights), in_pool, curr_subset, subgraph_size))
+    }
+
+    /// Copies the neighbourhoods of the pool vertices into one contiguous buffer, returning the offsets of the pool vertices in it.
+    fn flatten_pool<G: GraphOps>(highest_degree_vec: &[usize], g: &G) -> (Vec<usize>, Vec<usize>) {
+        let mut pool_offsets = Vec::with_capacity(highest_degree_vec.len() + 1);
+        let mut pool_neighbours = Vec::with_capacity(highest_degree_vec.iter().map(|v| g.degree(*v)).sum());
+        pool_offsets.push(0);
+        for v in highest_degree_vec {
+            g.for_each_neighbour(*v, &mut |u| pool_neighbours.push(u));
+            pool_offsets.push(pool_neighbours.len());
+        }
+        (pool_offsets, pool_neighbours)
+    }
+
+    /// Creates the counters of the subset given by its characteristic vector.
+    fn from_counters(counters: Counters, weights: Option<EdgeWeights>, in_pool: Vec<bool>, curr_subset: &[usize], subgraph_size: usize) -> CountArray {
+        let mut count_array = CountArray {
+            counters,
+            weights,
+            in_pool,
+            in_subset: vec![false; curr_subset.len()],
+            highest_degree_count: curr_subset.len(),
             subgraph_size,
-            count_array,
+            full_count: 0,
+        };
+        for i in (0..curr_subset.len()).filter(|i| curr_subset[*i] == 1) {
+            count_array.add_pool_vertex(i);
+        }
+        if let Counters::Scalar { threshold, count_array: counts, .. } = &count_array.counters {
+            // counted from scratch, as with threshold 0 the vertices outside all neighbourhoods reach it without an update
+            count_array.full_count =
+                counts
+                .iter()
+                .enumerate()
+                .filter(|(idx, c)| **c == *threshold && !count_array.in_pool[*idx])
+                .count();
         }
+        count_array.resync_weights();
+        count_array
     }
 
-    fn two_bit_change(&mut self, g: &Graph, change_0: usize, change_1: usize) {
-        g.neighbours_idx(self.highest_degree_vec[change_0]).unwrap().iter().for_each(|j| {
-            self.count_array[*j] -= 1;
-        });
+    /// Swaps the vertex change_0 of the pool for the vertex change_1.
+    /// The number of vertices reaching the threshold is updated on the fly,
+    /// so that no scan over the whole count array is needed afterwards.
+    fn two_bit_change(&mut self, change_0: usize, change_1: usize) {
+        self.remove_pool_vertex(change_0);
+        self.add_pool_vertex(change_1);
+    }
 
-        g.neighbours_idx(self.highest_degree_vec[change_1]).unwrap().iter().for_each(|j| {
-            self.count_array[*j] += 1;
-        });
+    /// Removes the i-th vertex of the pool from the current subset.
+    fn remove_pool_vertex(&mut self, i: usize) {
+        self.in_subset[i] = false;
+        match (&mut self.counters, &mut self.weights) {
+            (Counters::Scalar { pool_offsets, pool_neighbours, threshold, count_array }, None) => {
+                for j in &pool_neighbours[pool_offsets[i]..pool_offsets[i + 1]] {
+                    if count_array[*j] == *threshold && !self.in_pool[*j] {
+                        self.full_count -= 1;
+                    }
+                    count_array[*j] -= 1;
+                }
+            },
+            (Counters::Scalar { pool_offsets, pool_neighbours, threshold, count_array }, Some(weights)) => {
+                let edges = pool_offsets[i]..pool_offsets[i + 1];
+                for (v, w) in pool_neighbours[edges.clone()].iter().zip(&weights.pool_weights[edges]) {
+                    if count_array[*v] == *threshold && !self.in_pool[*v] {
+                        self.full_count -= 1;
+                        weights.full_weight -= weights.weight_array[*v];
+                    }
+                    count_array[*v] -= 1;
+                    weights.weight_array[*v] -= w;
+                }
+                weights.changes += 1;
+            },
+            (Counters::Packed { stale, .. }, _) => *stale = true,
+        }
     }
 
-    fn is_ok(&self) -> bool {
-        let count = 
-            self.count_array
-            .iter()
-            .enumerate()
-            .filter(|(idx, c)| **c == self.subgraph_size && !self.highest_degree_set.contains(idx))
-            .count();
-        
-        count >= self.highest_degree_count
+    /// Adds the i-th vertex of the pool to the current subset.
+    fn add_pool_vertex(&mut self, i: usize) {
+        self.in_subset[i] = true;
+        match (&mut self.counters, &mut self.weights) {
+            (Counters::Scalar { pool_offsets, pool_neighbours, threshold, count_array }, None) => {
+                for j in &pool_neighbours[pool_offsets[i]..pool_offsets[i + 1]] {
+                    count_array[*j] += 1;
+                    if count_array[*j] == *threshold && !self.in_pool[*j] {
+                        self.full_count += 1;
+                    }
+                }
+            },
+            (Counters::Scalar { pool_offsets, pool_neighbours, threshold, count_array }, Some(weights)) => {
+                let edges = pool_offsets[i]..pool_offsets[i + 1];
+                for (v, w) in pool_neighbours[edges.clone()].iter().zip(&weights.pool_weights[edges]) {
+                    count_array[*v] += 1;
+                    weights.weight_array[*v] += w;
+                    if count_array[*v] == *threshold && !self.in_pool[*v] {
+                        self.full_count += 1;
+                        weights.full_weight += weights.weight_array[*v];
+                    }
+                }
+                weights.changes += 1;
+            },
+            (Counters::Packed { stale, .. }, _) => *stale = true,
+        }
+    }
+
+    /// Recomputes the sums of edge weights of the weighted counters from scratch.
+    fn resync_weights(&mut self) {
+        if let (Counters::Scalar { pool_offsets, pool_neighbours, threshold, count_array }, Some(weights)) = (&self.counters, &mut self.weights) {
+            // only the neighbours of the pool have non-zero sums
+            pool_neighbours.iter().for_each(|v| weights.weight_array[*v] = 0.0);
+            for i in (0..self.in_subset.len()).filter(|i| self.in_subset[*i]) {
+                let edges = pool_offsets[i]..pool_offsets[i + 1];
+                for (v, w) in pool_neighbours[edges.clone()].iter().zip(&weights.pool_weights[edges]) {
+                    weights.weight_array[*v] += w;
+                }
+            }
+            weights.full_weight =
+                (0..count_array.len())
+                .filter(|v| count_array[*v] == *threshold && !self.in_pool[*v])
+                .map(|v| weights.weight_array[v])
+                .sum();
+            weights.changes = 0;
+        }
+    }
+
+    /// Returns the total weight of the edges from the current subset to the vertices outside the pool adjacent to all its members,
+    /// or 0 for counters without weights. The sums are recomputed every WEIGHT_RESYNC_INTERVAL changes, so that the rounding errors
+    /// of the updates do not accumulate over the search.
+    fn full_weight(&mut self) -> f64 {
+        if self.weights.as_ref().is_some_and(|weights| weights.changes >= WEIGHT_RESYNC_INTERVAL) {
+            self.resync_weights();
+        }
+        self.weights.as_ref().map_or(0.0, |weights| weights.full_weight)
+    }
+
+    /// Returns the number of vertices outside the pool adjacent to the whole current subset,
+    /// intersecting the packed neighbourhoods first if the subset has changed.
+    fn full_count(&mut self) -> usize {
+        if let Counters::Packed { neighbourhoods, common, stale: stale @ true } = &mut self.counters {
+            let mut members = (0..neighbourhoods.len()).filter(|i| self.in_subset[*i]);
+            match members.next() {
+                Some(first) => {
+                    common.copy_from_slice(&neighbourhoods[first]);
+                    for i in members {
+                        common.iter_mut().zip(&neighbourhoods[i]).for_each(|(c, bits)| *c &= bits);
+                    }
+                },
+                None => {
+                    // every vertex outside the pool is adjacent to the whole empty subset
+                    common.fill(0);
+                    (0..self.in_pool.len()).filter(|v| !self.in_pool[*v]).for_each(|v| common[v / WORD_BITS] |= 1 << (v % WORD_BITS));
+                },
+            }
+            self.full_count = common.iter().map(|bits| bits.count_ones() as usize).sum();
+            *stale = false;
+        }
+        self.full_count
+    }
+
+    /// Checks if enough vertices are adjacent to the whole current subset.
+    /// Works in constant time for the counters thanks to the incrementally tracked full_count.
+    fn is_ok(&mut self) -> bool {
+        self.full_count() >= self.highest_degree_count
+    }
+
+    fn d_solution(&mut self) -> HashSet<usize> {
+        self.full_vertices(self.subgraph_size)
+    }
+
+    /// Returns at most limit vertices outside the pool adjacent to the whole current subset, the ones with the lowest indices.
+    fn full_vertices(&mut self, limit: usize) -> HashSet<usize> {
+        self.full_count();
+        let n = self.in_pool.len();
+        match &self.counters {
+            Counters::Scalar { threshold, count_array, .. } =>
+                count_array
+                .iter()
+                .enumerate()
+                .filter(|(idx, c)| **c == *threshold && !self.in_pool[*idx])
+                .map(|(idx, _)| idx)
+                .take(limit)
+                .collect::<HashSet<usize>>(),
+            Counters::Packed { common, .. } =>
+                (0..n)
+                .filter(|v| common[v / WORD_BITS] >> (v % WORD_BITS) & 1 == 1)
+                .take(limit)
+                .collect::<HashSet<usize>>(),
+        }
     }
+}
+
+
+/// Maps the characteristic vector of a subset of the pool to the original vertices.
+fn c_solution(curr_subset: &[usize], highest_degree_vertices: &[usize]) -> HashSet<usize> {
+    curr_subset
+    .iter()
+    .enumerate()
+    .filter(|(_, is_in)| **is_in == 1) // elements of curr subset
+    .map(|(idx, _)| highest_degree_vertices[idx]) // map to the original vertices
+    .collect::<HashSet<usize>>()
+}
+
+/// The q and r values described in the paper "Finding bipartite subgraphs efficiently" by Dhruv Mubayi and Gyorgy Turan,
+/// together with the quantities they are computed from.
+/// A graph satisfying the density assumption of the paper contains K_{q,r}, which is the guaranteed size of the found subgraph.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QrParameters {
+    /// The size of the side of the guaranteed subgraph within the pool.
+    pub q: usize,
+    /// The size of the other side of the guaranteed subgraph.
+    pub r: usize,
+    /// The value of q before rounding down.
+    pub q_exact: f64,
+    /// The value of r before rounding down.
+    pub r_exact: f64,
+    /// The density of the graph, i.e. |E| divided by |V| choose 2.
+    pub density: f64,
+}
 
-    fn d_solution(&self) -> HashSet<usize> {
-        self.count_array
-        .iter()
-        .enumerate()
-        .filter(|(idx, c)| **c == self.subgraph_size && !self.highest_degree_set.contains(idx))
-        .map(|(idx, _)| idx)
-        .take(self.subgraph_size)
-        .collect::<HashSet<usize>>()
+impl QrParameters {
+    /// Returns the number of edges of the guaranteed K_{q,r}.
+    pub fn guaranteed_edges(&self) -> usize {
+        self.q * self.r
     }
 }
 
+/// Computes the exact (not rounded) values of q and r and the density for a graph with n >= 2 vertices and m > 0 edges.
+/// The denominator of q equals 1 + ln(2 n^2 / m), it is computed from the density d as
+/// 1 + 2 ln 2 - ln d + ln(1 + 1 / (n - 1)), so that there is no cancellation for dense graphs.
+fn qr_exact(n: usize, m: usize) -> (f64, f64, f64) {
+    let (n, m) = (n as f64, m as f64);
+    let density = m / (n * (n - 1.0) / 2.0);
+    let q_numerator = (n / 2.0).ln();
+    let q_denominator = 1.0 + 2.0 * std::f64::consts::LN_2 - density.ln() + (1.0 / (n - 1.0)).ln_1p();
+    let q = q_numerator / q_denominator;
+    let r = q.floor() * (n / m) * n;
+    (q, r, density)
+}
 
 /// Calculates the q and r values described in the paper "Finding bipartite subgraphs efficiently" by Dhruv Mubayi and Gyorgy Turan.
+/// The values are rounded down and saturate at 0, so for graphs not satisfying the assumptions of the paper
+/// the result is meaningless, qr_parameters_checked should be used to detect such graphs.
 /// # Examples:
 /// ```
 /// use labisu::bipartite::qr_parameters;
@@ -72,22 +376,58 @@ impl CountArray {
 /// let (q, r) = qr_parameters(&k1500);
 /// assert_eq!(q, 2);
 /// assert_eq!(r, 4);
+/// assert_eq!((0, 0), qr_parameters(&Graph::empty()));
+/// ```
+pub fn qr_parameters<G: GraphOps>(graph: &G) -> (usize, usize) {
+    let n = graph.get_num_of_vertices();
+    let m = graph.get_num_of_edges();
+    if n < 2 || m == 0 {
+        return (0, 0)
+    }
+    let (q, r, _) = qr_exact(n, m);
+    // casts of negative and NaN values saturate at 0
+    (q.floor() as usize, r.floor() as usize)
+}
+
+/// Calculates the q and r values like qr_parameters, but returns an Err value if the graph does not satisfy
+/// the assumptions of the paper, i.e. it has less than two vertices, no edges, |E|^2 < 64 |V|^3 or q would be 0.
+/// # Examples:
 /// ```
-pub fn qr_parameters(graph: &Graph) -> (usize, usize) {
-    let n = graph.get_num_of_vertices() as f64;
-    let m = graph.get_num_of_edges() as f64;
-    let q_numerator = n.ln() - (2.0f64).ln();
-    let q_denominator = (2.0f64).ln() + 1.0 + n.ln() + n.ln() - m.ln();
-    let q = ((q_numerator / q_denominator) as f64).floor();
-    let r_numerator = q * n * n;
-    let r_denominator = m;
-    let r = ((r_numerator / r_denominator) as f64).floor();
-    (q as usize, r as usize)
+/// use labisu::bipartite::qr_parameters_checked;
+/// use labisu::graphs::Graph;
+/// let parameters = qr_parameters_checked(&Graph::complete(1500)).unwrap();
+/// assert_eq!((2, 4), (parameters.q, parameters.r));
+/// assert_eq!(8, parameters.guaranteed_edges());
+/// assert!(parameters.q_exact > 2.0);
+/// assert!(qr_parameters_checked(&Graph::cycle(100)).is_err());
+/// ```
+pub fn qr_parameters_checked<G: GraphOps>(graph: &G) -> Result<QrParameters, &'static str> {
+    let n = graph.get_num_of_vertices();
+    let m = graph.get_num_of_edges();
+    if n < 2 {
+        return Err("The graph has less than two vertices.")
+    }
+    if m == 0 {
+        return Err("The graph has no edges.")
+    }
+    if (m as f64).powi(2) < 64.0 * (n as f64).powi(3) {
+        return Err("The graph is too sparse, |E|^2 < 64 |V|^3.")
+    }
+
+    let (q_exact, r_exact, density) = qr_exact(n, m);
+    if !q_exact.is_finite() || !r_exact.is_finite() {
+        return Err("The parameters are not finite.")
+    }
+    if q_exact < 1.0 {
+        return Err("The graph is too sparse, q is smaller than 1.")
+    }
+    Ok(QrParameters { q: q_exact.floor() as usize, r: r_exact.floor() as usize, q_exact, r_exact, density })
 }
  
 /// Based on algorithm from "Finding bipartite subgraphs efficiently" by Dhruv Mubayi and Gyorgy Turan
 /// If the number of edges equals 0, then the algorithm returns two empty sets.
 /// The algorithm returns two sets of vertices, which are grouped in two halves of the found complete bipartite graph.
+/// Panics if bipartite_size exceeds MAX_BIPARTITE_SIZE, find_bipartite_checked returns an Err value instead.
 /// # Examples:
 /// ```
 /// use labisu::bipartite::find_bipartite;
@@ -99,51 +439,609 @@ pub fn qr_parameters(graph: &Graph) -> (usize, usize) {
 /// assert_eq!(left, HashSet::from([0,1,2]));  
 /// assert_eq!(right, HashSet::from([10,11,12])); 
 /// ```
-pub fn find_bipartite(graph: &Graph, highest_degree_size: usize, bipartite_size: usize) -> (HashSet<usize>, HashSet<usize>) {
+/// The graph may also be shared, e.g. with a running crawler, without cloning it.
+/// ```
+/// use labisu::bipartite::find_bipartite;
+/// use labisu::graphs::Graph;
+/// use std::sync::Arc;
+/// 
+/// let shared = Arc::new(Graph::complete(300));
+/// let (left, right) = find_bipartite(&shared, 10, 3);
+/// assert_eq!(3, left.len());
+/// assert_eq!(3, right.len());
+/// ```
+pub fn find_bipartite<G: GraphOps>(graph: &G, highest_degree_size: usize, bipartite_size: usize) -> (HashSet<usize>, HashSet<usize>) {
+    find_bipartite_checked(graph, highest_degree_size, bipartite_size).expect("The size of the bipartite subgraph is too large.")
+}
+
+/// Runs find_bipartite, but returns an Err value if bipartite_size exceeds MAX_BIPARTITE_SIZE.
+/// # Examples:
+/// ```
+/// use labisu::bipartite::{find_bipartite_checked, MAX_BIPARTITE_SIZE};
+/// use labisu::graphs::Graph;
+/// let k300 = Graph::complete(300);
+/// assert_eq!(3, find_bipartite_checked(&k300, 10, 3).unwrap().0.len());
+/// assert!(find_bipartite_checked(&k300, 10, MAX_BIPARTITE_SIZE + 1).is_err());
+/// ```
+pub fn find_bipartite_checked<G: GraphOps>(graph: &G, highest_degree_size: usize, bipartite_size: usize) -> Result<(HashSet<usize>, HashSet<usize>), &'static str> {
+    if bipartite_size > MAX_BIPARTITE_SIZE {
+        return Err("The size of the bipartite subgraph exceeds MAX_BIPARTITE_SIZE.")
+    }
     let n = graph.get_num_of_vertices();
     let m = graph.get_num_of_edges();
     
     if 0 < m && m.pow(2) < 64 * n.pow(3) {
         for i in graph.vertices() {
             if let Some(j) = graph.neighbours_idx(i).unwrap().iter().next() {
-                return (HashSet::from([i]), HashSet::from([*j])); 
+                return Ok((HashSet::from([i]), HashSet::from([*j])));
             }
         }       
     }
 
-    let c_solution = |curr_subset: &[usize], highest_degree_vertices : &[usize]| -> HashSet<usize> {
-        curr_subset
-        .iter()
-        .enumerate()
-        .filter(|(_, is_in)| **is_in == 1) // elements of curr subset
-        .map(|(idx, _)| highest_degree_vertices[idx]) // map to the original vertices
-        .collect::<HashSet<usize>>()
-    };
-
     let highest_degree_vertices = graph.highest_degree_vertices(highest_degree_size);     
-    let gray_generator = GraySubsets::new(highest_degree_size, bipartite_size);
+    Ok(search_pool(graph, &highest_degree_vertices, bipartite_size)?.0.unwrap_or_default())
+}
+
+/// The two sides of a complete bipartite subgraph.
+type Sides = (HashSet<usize>, HashSet<usize>);
+
+/// The pairs of vertices of a pool which cannot be together in a subset with enough common neighbours outside the pool,
+/// used to skip such subsets without updating the counters.
+struct PoolConflicts {
+    infeasible: Vec<bool>, // the vertex alone has fewer neighbours outside the pool than the threshold
+    conflicts: Vec<Vec<usize>>, // the positions of the vertices with too few common neighbours outside the pool
+    conflict_count: Vec<usize>, // the number of vertices of the current subset conflicting with the vertex
+    violations: usize, // infeasible vertices and conflicting pairs within the current subset
+}
+
+impl PoolConflicts {
+    /// Finds the conflicts of the pool, i.e. the pairs with fewer than pool.len() common neighbours outside the pool.
+    fn new<G: GraphOps>(graph: &G, pool: &[usize], curr_subset: &[usize]) -> PoolConflicts {
+        let (n, threshold) = (graph.get_num_of_vertices(), pool.len());
+        let mut in_pool = vec![false; n];
+        pool.iter().for_each(|v| in_pool[*v] = true);
+        let outside =
+            pool
+            .iter()
+            .map(|v| {
+                let mut neighbours = vec![];
+                graph.for_each_neighbour(*v, &mut |u| if !in_pool[u] { neighbours.push(u) });
+                neighbours
+            })
+            .collect::<Vec<Vec<usize>>>();
+
+        let infeasible = outside.iter().map(|neighbours| neighbours.len() < threshold).collect::<Vec<bool>>();
+        let mut conflicts = vec![vec![]; pool.len()];
+        let mut marked = vec![false; n];
+        for i in 0..pool.len() {
+            outside[i].iter().for_each(|u| marked[*u] = true);
+            for j in i + 1..pool.len() {
+                if outside[j].iter().filter(|u| marked[**u]).count() < threshold {
+                    conflicts[i].push(j);
+                    conflicts[j].push(i);
+                }
+            }
+            outside[i].iter().for_each(|u| marked[*u] = false);
+        }
+
+        let mut pool_conflicts = PoolConflicts { infeasible, conflicts, conflict_count: vec![0; pool.len()], violations: 0 };
+        for i in (0..pool.len()).filter(|i| curr_subset[*i] == 1) {
+            pool_conflicts.add(i);
+        }
+        pool_conflicts
+    }
+
+    /// Checks if no subset of the pool can be skipped.
+    fn is_empty(&self) -> bool {
+        !self.infeasible.contains(&true) && self.conflicts.iter().all(Vec::is_empty)
+    }
+
+    /// Removes the i-th vertex of the pool from the current subset.
+    fn remove(&mut self, i: usize) {
+        self.violations -= self.infeasible[i] as usize + self.conflict_count[i];
+        self.conflicts[i].iter().for_each(|j| self.conflict_count[*j] -= 1);
+    }
+
+    /// Adds the i-th vertex of the pool to the current subset.
+    fn add(&mut self, i: usize) {
+        self.violations += self.infeasible[i] as usize + self.conflict_count[i];
+        self.conflicts[i].iter().for_each(|j| self.conflict_count[*j] += 1);
+    }
+}
+
+/// Walks the subsets of the pool in the order of the Gray code, starting with the current subset, keeping the counters up to date
+/// and calling visit with the characteristic vector of every subset until it returns true.
+/// Returns whether the walk was stopped by visit, with curr_subset left at the accepted subset, and the number of visited subsets.
+fn gray_search<F>(gray_generator: GraySubsets, curr_subset: &mut [usize], b: &mut CountArray, mut visit: F) -> (bool, usize)
+where F: FnMut(&[usize], &mut CountArray) -> bool {
+    let mut visited = 1;
+    if visit(curr_subset, b) {
+        return (true, visited)
+    }
+    for (change_0, change_1) in gray_generator {
+        b.two_bit_change(change_0, change_1);
+        curr_subset[change_0] = 0;
+        curr_subset[change_1] = 1;
+        visited += 1;
+        if visit(curr_subset, b) {
+            return (true, visited)
+        }
+    }
+    (false, visited)
+}
+
+/// Runs the Gray code search of find_bipartite over the subsets of bipartite_size of given pool.
+/// The subsets containing a vertex with too few neighbours outside the pool, or a pair of vertices with too few common ones,
+/// cannot succeed, so they are skipped without updating the counters, which are brought up to date only for the other subsets.
+/// Returns the first complete bipartite subgraph found, if any, together with the number of examined (not skipped) subsets,
+/// or an Err value if bipartite_size exceeds MAX_BIPARTITE_SIZE.
+fn search_pool<G: GraphOps>(graph: &G, pool: &[usize], bipartite_size: usize) -> Result<(Option<Sides>, usize), &'static str> {
+    let gray_generator = GraySubsets::new(pool.len(), bipartite_size);
     let mut curr_subset = gray_generator.init();
-    let mut b = CountArray::new(&highest_degree_vertices, bipartite_size, graph);
+    let mut conflicts = PoolConflicts::new(graph, pool, &curr_subset);
+    let mut b = CountArray::new(pool, &curr_subset, bipartite_size, graph)?;
 
-    if b.is_ok() {
-        let c_set = c_solution(&curr_subset, &highest_degree_vertices);
-        let d_set = b.d_solution();
+    if conflicts.is_empty() {
+        let (found, examined) = gray_search(gray_generator, &mut curr_subset, &mut b, |_, b| b.is_ok());
+        let sides = found.then(|| (c_solution(&curr_subset, pool), b.d_solution()));
+        return Ok((sides, examined))
+    }
 
-        return (c_set, d_set);
+    let mut examined = 1;
+    if b.is_ok() {
+        return Ok((Some((c_solution(&curr_subset, pool), b.d_solution())), examined))
     }
 
+    let mut counted_subset = curr_subset.clone(); // the subset the counters are up to date with
     for (change_0, change_1) in gray_generator {
-        b.two_bit_change(graph, change_0, change_1);
+        conflicts.remove(change_0);
+        conflicts.add(change_1);
         curr_subset[change_0] = 0;
         curr_subset[change_1] = 1;
+        if conflicts.violations > 0 {
+            continue
+        }
+
+        for i in 0..pool.len() {
+            if counted_subset[i] == 1 && curr_subset[i] == 0 {
+                b.remove_pool_vertex(i);
+            }
+        }
+        for i in 0..pool.len() {
+            if counted_subset[i] == 0 && curr_subset[i] == 1 {
+                b.add_pool_vertex(i);
+            }
+        }
+        counted_subset.copy_from_slice(&curr_subset);
+        examined += 1;
+
+        if b.is_ok() {
+            return Ok((Some((c_solution(&curr_subset, pool), b.d_solution())), examined))
+        }
+    }
+
+    Ok((None, examined)) // no solution
+}
+
+/// The progress of the Gray code search that can be stored on disk and resumed later.
+/// The counters are not stored as they are recomputed from the current subset on resume.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SearchState {
+    highest_degree_vertices: Vec<usize>,
+    bipartite_size: usize,
+    gray_generator: GraySubsets,
+    curr_subset: Vec<usize>,
+    examined: usize,
+    best_count: usize,
+    best_subset: Vec<usize>,
+    #[cfg(feature = "stats")]
+    #[serde(default)]
+    histogram: Vec<usize>, // histogram[c] is the number of examined subsets with c common neighbours outside the pool
+}
+
+/// The result of a bounded run of the resumable search.
+pub enum SearchOutcome {
+    /// A complete bipartite subgraph was found.
+    Found(HashSet<usize>, HashSet<usize>),
+    /// The whole search space was examined without success.
+    NotFound,
+    /// The step limit was reached, the search can be continued from the state.
+    Suspended(SearchState),
+}
+
+impl SearchState {
+    /// Creates the initial state of the search used by find_bipartite.
+    /// Panics if bipartite_size exceeds MAX_BIPARTITE_SIZE.
+    /// # Examples
+    /// ```
+    /// use labisu::bipartite::SearchState;
+    /// use labisu::graphs::Graph;
+    /// let k300 = Graph::complete(300);
+    /// let state = SearchState::new(&k300, 10, 3);
+    /// assert_eq!(0, state.get_examined());
+    /// ```
+    pub fn new<G: GraphOps>(graph: &G, highest_degree_size: usize, bipartite_size: usize) -> SearchState {
+        assert!(bipartite_size <= MAX_BIPARTITE_SIZE, "The size of the bipartite subgraph exceeds MAX_BIPARTITE_SIZE.");
+        let gray_generator = GraySubsets::new(highest_degree_size, bipartite_size);
+        let curr_subset = gray_generator.init();
+        SearchState {
+            highest_degree_vertices: graph.highest_degree_vertices(highest_degree_size),
+            bipartite_size,
+            best_subset: curr_subset.clone(),
+            gray_generator,
+            curr_subset,
+            examined: 0,
+            best_count: 0,
+            #[cfg(feature = "stats")]
+            histogram: vec![],
+        }
+    }
+
+    /// Returns the number of subsets examined so far.
+    pub fn get_examined(&self) -> usize {
+        self.examined
+    }
+
+    /// Returns the best subset of the pool found so far, i.e. the one with the most vertices adjacent to all its members,
+    /// together with the number of such vertices.
+    pub fn best_partial(&self) -> (HashSet<usize>, usize) {
+        (c_solution(&self.best_subset, &self.highest_degree_vertices), self.best_count)
+    }
+
+    /// Returns the histogram of the numbers of common neighbours outside the pool over the examined subsets,
+    /// i.e. the value at index c is the number of examined subsets with exactly c such neighbours.
+    /// It shows how close the search came to the threshold even when it failed.
+    #[cfg(feature = "stats")]
+    pub fn get_histogram(&self) -> &[usize] {
+        &self.histogram
+    }
+
+    /// Writes the state to a json file with given filename.
+    /// # Examples
+    /// ```
+    /// use labisu::bipartite::SearchState;
+    /// use labisu::graphs::Graph;
+    /// let k300 = Graph::complete(300);
+    /// let state = SearchState::new(&k300, 10, 3);
+    /// assert!(state.write_to_json("state.json").is_ok());
+    /// let read = SearchState::read_from_json("state.json").unwrap();
+    /// assert_eq!(0, read.get_examined());
+    /// ```
+    pub fn write_to_json(&self, filename: &str) -> serde_json::Result<()> {
+        let mut file = AtomicFile::create(filename).map_err(serde_json::Error::io)?;
+        serde_json::to_writer(&mut file, self)?;
+        file.commit().map_err(serde_json::Error::io)
+    }
+
+    /// Reads a state from a json file with given filename.
+    pub fn read_from_json(filename: &str) -> serde_json::Result<SearchState> {
+        let file = File::open(filename).map_err(serde_json::Error::io)?;
+        serde_json::from_reader(file)
+    }
+}
+
+/// Continues the search of find_bipartite from the given state, examining at most max_steps subsets.
+/// Unlike find_bipartite it does not take the shortcut for sparse graphs, so that the search space is well defined.
+/// The graph has to be the same as the one the state was created for.
+/// # Examples
+/// ```
+/// use labisu::bipartite::{resume_bipartite, SearchOutcome, SearchState};
+/// use labisu::graphs::Graph;
+/// use std::collections::HashSet;
+/// let k300 = Graph::complete(300);
+/// let state = SearchState::new(&k300, 10, 3);
+/// match resume_bipartite(&k300, state, 100) {
+///     SearchOutcome::Found(left, right) => {
+///         assert_eq!(left, HashSet::from([0,1,2]));
+///         assert_eq!(right, HashSet::from([10,11,12]));
+///     },
+///     _ => panic!("K_{{3,3}} is present in K_300"),
+/// }
+/// ```
+pub fn resume_bipartite<G: GraphOps>(graph: &G, mut state: SearchState, max_steps: usize) -> SearchOutcome {
+    match advance(graph, &mut state, max_steps) {
+        Some(outcome) => outcome,
+        None => SearchOutcome::Suspended(state),
+    }
+}
 
+/// Advances the search by at most max_steps subsets.
+/// Returns None if the step limit was reached before the search finished.
+fn advance<G: GraphOps>(graph: &G, state: &mut SearchState, max_steps: usize) -> Option<SearchOutcome> {
+    let mut b = CountArray::new(&state.highest_degree_vertices, &state.curr_subset, state.bipartite_size, graph).expect("The size of the bipartite subgraph exceeds MAX_BIPARTITE_SIZE.");
+
+    let examine = |state: &mut SearchState, b: &mut CountArray| -> Option<SearchOutcome> {
+        state.examined += 1;
+        let full_count = b.full_count();
+        #[cfg(feature = "stats")]
+        {
+            if state.histogram.len() <= full_count {
+                state.histogram.resize(full_count + 1, 0);
+            }
+            state.histogram[full_count] += 1;
+        }
+        if full_count > state.best_count {
+            state.best_count = full_count;
+            state.best_subset = state.curr_subset.clone();
+        }
         if b.is_ok() {
-            let c_set = c_solution(&curr_subset, &highest_degree_vertices);
-            let d_set = b.d_solution();
+            let c_set = c_solution(&state.curr_subset, &state.highest_degree_vertices);
+            return Some(SearchOutcome::Found(c_set, b.d_solution()))
+        }
+        None
+    };
+
+    let mut steps = 0;
+    if state.examined == 0 {
+        if let Some(outcome) = examine(state, &mut b) {
+            return Some(outcome)
+        }
+        steps += 1;
+    }
+
+    while steps < max_steps {
+        match state.gray_generator.next() {
+            Some((change_0, change_1)) => {
+                b.two_bit_change(change_0, change_1);
+                state.curr_subset[change_0] = 0;
+                state.curr_subset[change_1] = 1;
+                if let Some(outcome) = examine(state, &mut b) {
+                    return Some(outcome)
+                }
+                steps += 1;
+            },
+            None => return Some(SearchOutcome::NotFound),
+        }
+    }
+
+    None
+}
+
+/// Runs the same search as find_bipartite_certified and returns its outcome (Found or NotFound) together with the histogram
+/// of the numbers of common neighbours outside the pool over the examined subsets (see SearchState::get_histogram).
+/// # Examples:
+/// ```
+/// use labisu::bipartite::{find_bipartite_histogram, SearchOutcome};
+/// use labisu::graphs::Graph;
+/// 
+/// let mut star = Graph::from_names((0..6).map(|i| format!("v_{}", i)).collect());
+/// (1..6).for_each(|i| { star.add_edge_idx(0, i); });
+/// let (outcome, histogram) = find_bipartite_histogram(&star, 3, 2);
+/// assert!(matches!(outcome, SearchOutcome::NotFound));
+/// assert_eq!(vec![3], histogram);
+/// ```
+#[cfg(feature = "stats")]
+pub fn find_bipartite_histogram<G: GraphOps>(graph: &G, highest_degree_size: usize, bipartite_size: usize) -> (SearchOutcome, Vec<usize>) {
+    let mut state = SearchState::new(graph, highest_degree_size, bipartite_size);
+    let outcome = advance(graph, &mut state, usize::MAX).unwrap_or(SearchOutcome::NotFound);
+    (outcome, state.histogram)
+}
+
+/// Splits the lexicographic ranks of all k-subsets of a pool of size n into num_workers consecutive ranges of almost equal length.
+/// Each range can be searched independently with find_bipartite_range, e.g. on a different machine.
+/// # Examples
+/// ```
+/// use labisu::bipartite::partition_search_space;
+/// let ranges = partition_search_space(10, 3, 4);
+/// assert_eq!(ranges, vec![0..30, 30..60, 60..90, 90..120]);
+/// let ranges = partition_search_space(60, 30, 1000);
+/// assert_eq!(1000, ranges.len());
+/// assert_eq!(labisu::combinatorics::binomial(60, 30), ranges[999].end);
+/// ```
+pub fn partition_search_space(n: usize, k: usize, num_workers: usize) -> Vec<std::ops::Range<usize>> {
+    // the products are computed in u128, as they overflow usize for large numbers of subsets and workers
+    let total = binomial(n, k) as u128;
+    let num_workers = std::cmp::max(num_workers, 1);
+    let bound = |worker: usize| (total * worker as u128 / num_workers as u128) as usize;
+    (0..num_workers)
+    .map(|worker| bound(worker)..bound(worker + 1))
+    .collect()
+}
+
+/// Searches the same space as find_bipartite, but only the subsets of the pool whose lexicographic ranks lie in the given range.
+/// It does not take the shortcut for sparse graphs, so that the results of all ranges together cover the whole search.
+/// Ranks beyond the number of subsets are ignored. If no complete bipartite subgraph is found in the range, then two empty sets are returned.
+/// Panics if bipartite_size exceeds MAX_BIPARTITE_SIZE.
+/// # Examples
+/// ```
+/// use labisu::bipartite::{find_bipartite_range, partition_search_space};
+/// use labisu::graphs::Graph;
+/// use std::collections::HashSet;
+/// let k300 = Graph::complete(300);
+/// let ranges = partition_search_space(10, 3, 4);
+/// let (left, right) = find_bipartite_range(&k300, 10, 3, ranges[1].clone());
+/// assert_eq!(left, HashSet::from([0,6,7]));
+/// assert_eq!(right, HashSet::from([10,11,12]));
+/// ```
+pub fn find_bipartite_range<G: GraphOps>(graph: &G, highest_degree_size: usize, bipartite_size: usize, ranks: std::ops::Range<usize>) -> (HashSet<usize>, HashSet<usize>) {
+    let ranks = ranks.start..ranks.end.min(binomial(highest_degree_size, bipartite_size));
+    if ranks.is_empty() {
+        return (HashSet::new(), HashSet::new())
+    }
 
-            return (c_set, d_set)
+    let highest_degree_vertices = graph.highest_degree_vertices(highest_degree_size);
+    let mut combination = unrank_combination(ranks.start, highest_degree_size, bipartite_size);
+    let mut curr_subset = vec![0; highest_degree_size];
+    combination.iter().for_each(|i| curr_subset[*i] = 1);
+    let mut b = CountArray::new(&highest_degree_vertices, &curr_subset, bipartite_size, graph).expect("The size of the bipartite subgraph exceeds MAX_BIPARTITE_SIZE.");
+
+    for rank in ranks.clone() {
+        if rank > ranks.start {
+            let previous = combination.clone();
+            next_combination(&mut combination, highest_degree_size);
+            for i in previous.iter().filter(|i| !combination.contains(i)) {
+                b.remove_pool_vertex(*i);
+                curr_subset[*i] = 0;
+            }
+            for i in combination.iter().filter(|i| !previous.contains(i)) {
+                b.add_pool_vertex(*i);
+                curr_subset[*i] = 1;
+            }
+        }
+
+        if b.is_ok() {
+            return (c_solution(&curr_subset, &highest_degree_vertices), b.d_solution())
         }
     }
 
     (HashSet::new(), HashSet::new()) // no solution
+}
+
+/// A certificate that an exhaustive search over the pool of the highest degree vertices failed.
+/// It states that no subset of the pool of size bipartite_size has more than max_common_neighbours
+/// common neighbours outside the pool, i.e. there is no K_{s,t} with s = bipartite_size, t > max_common_neighbours,
+/// one side in the pool and the other one outside of it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FreenessCertificate {
+    /// The vertices of the pool, in order of non-increasing degree.
+    pub pool: Vec<usize>,
+    /// The size of the examined subsets of the pool.
+    pub bipartite_size: usize,
+    /// The number of common neighbours outside the pool required for success.
+    pub threshold: usize,
+    /// The number of examined subsets.
+    pub subsets_examined: usize,
+    /// The largest number of common neighbours outside the pool over all examined subsets.
+    pub max_common_neighbours: usize,
+}
+
+impl FreenessCertificate {
+    /// Checks if the certificate covers the whole search space, i.e. all subsets of the pool were examined.
+    pub fn is_complete(&self) -> bool {
+        self.subsets_examined == binomial(self.pool.len(), self.bipartite_size)
+    }
+
+    /// Checks if the certificate proves that there is no K_{s,t} with s = bipartite_size, one side in the pool 
+    /// and the other side outside of it.
+    pub fn excludes(&self, t: usize) -> bool {
+        self.is_complete() && t > self.max_common_neighbours
+    }
+}
+
+/// Runs the same search as find_bipartite, but without the shortcut for sparse graphs, so that it is always exhaustive.
+/// If no complete bipartite subgraph is found, then a certificate of its absence among the pool is returned
+/// instead of two empty sets, which distinguishes a provable absence from a failure of the heuristic.
+/// # Examples:
+/// ```
+/// use labisu::bipartite::find_bipartite_certified;
+/// use labisu::graphs::Graph;
+/// 
+/// let k300 = Graph::complete(300);
+/// assert!(find_bipartite_certified(&k300, 10, 3).is_ok());
+/// 
+/// let mut star = Graph::from_names((0..6).map(|i| format!("v_{}", i)).collect());
+/// (1..6).for_each(|i| { star.add_edge_idx(0, i); });
+/// let certificate = find_bipartite_certified(&star, 3, 2).unwrap_err();
+/// assert!(certificate.is_complete());
+/// assert_eq!(0, certificate.max_common_neighbours);
+/// assert!(certificate.excludes(1));
+/// ```
+pub fn find_bipartite_certified<G: GraphOps>(graph: &G, highest_degree_size: usize, bipartite_size: usize) -> Result<(HashSet<usize>, HashSet<usize>), FreenessCertificate> {
+    let mut state = SearchState::new(graph, highest_degree_size, bipartite_size);
+    if let Some(SearchOutcome::Found(left, right)) = advance(graph, &mut state, usize::MAX) {
+        return Ok((left, right))
+    }
+
+    Err(FreenessCertificate {
+        pool: state.highest_degree_vertices,
+        bipartite_size,
+        threshold: highest_degree_size,
+        subsets_examined: state.examined,
+        max_common_neighbours: state.best_count,
+    })
+}
+
+/// Checks if the two sets of vertices are disjoint and every vertex of the left one is adjacent to every vertex of the right one,
+/// i.e. if they form a complete bipartite subgraph of the graph. Returns false if one of the vertices does not exist.
+/// # Examples:
+/// ```
+/// use labisu::bipartite::{find_bipartite, is_complete_bipartite};
+/// use labisu::graphs::Graph;
+/// use std::collections::HashSet;
+/// 
+/// let k300 = Graph::complete(300);
+/// let (left, right) = find_bipartite(&k300, 10, 3);
+/// assert!(is_complete_bipartite(&k300, &left, &right));
+/// assert!(!is_complete_bipartite(&Graph::path(3), &HashSet::from([0]), &HashSet::from([2])));
+/// ```
+pub fn is_complete_bipartite<G: GraphOps>(graph: &G, left: &HashSet<usize>, right: &HashSet<usize>) -> bool {
+    left.is_disjoint(right)
+    && left.iter().all(|v| 
+        match graph.neighbours_idx(*v) {
+            Ok(neighbours) => right.is_subset(&neighbours),
+            Err(_) => false,
+        }
+    )
+}
+
+/// Returns the vertices adjacent to all vertices of the set, or all vertices if the set is empty.
+fn common_neighbours<G: GraphOps>(graph: &G, set: &HashSet<usize>) -> HashSet<usize> {
+    let mut common: Option<HashSet<usize>> = None;
+    for v in set {
+        let neighbours = graph.neighbours_idx(*v).unwrap();
+        common = Some(match common {
+            Some(common) => common.intersection(&neighbours).copied().collect(),
+            None => neighbours,
+        });
+    }
+    common.unwrap_or_else(|| graph.vertices().collect())
+}
+
+/// Greedily grows a complete bipartite subgraph from a seed, e.g. a pair of vertices with similar neighbourhoods,
+/// by adding vertices alternately to the smaller side. Among the vertices adjacent to the whole other side,
+/// the one keeping the most candidates for the other side is added (ties broken by the smaller index).
+/// The growth stops when neither side can be extended.
+/// If the seed is not a complete bipartite subgraph (or contains a vertex not in the graph), then it returns an Err value.
+/// # Examples:
+/// ```
+/// use labisu::bipartite::{expand_from_seed, is_complete_bipartite};
+/// use labisu::graphs::Graph;
+/// use std::collections::HashSet;
+/// 
+/// let mut g = Graph::complete_multipartite(&[3, 4]);
+/// g.add_vertex("extra");
+/// g.add_edge_idx(0, 7);
+/// let (left, right) = expand_from_seed(&g, &HashSet::from([0]), &HashSet::from([3])).unwrap();
+/// assert_eq!(HashSet::from([0, 1, 2]), left);
+/// assert_eq!(HashSet::from([3, 4, 5, 6]), right);
+/// assert!(expand_from_seed(&g, &HashSet::from([0]), &HashSet::from([1])).is_err());
+/// ```
+pub fn expand_from_seed<G: GraphOps>(graph: &G, left_seed: &HashSet<usize>, right_seed: &HashSet<usize>) -> Result<(HashSet<usize>, HashSet<usize>), &'static str> {
+    if left_seed.iter().chain(right_seed).any(|v| *v >= graph.get_num_of_vertices()) || !is_complete_bipartite(graph, left_seed, right_seed) {
+        return Err("The seed is not a complete bipartite subgraph.")
+    }
+    let mut left = left_seed.clone();
+    let mut right = right_seed.clone();
+    let mut common_of_left = common_neighbours(graph, &left);
+    let mut common_of_right = common_neighbours(graph, &right);
+
+    // extends the side by the best candidate, returns false if there is none
+    let extend = |side: &mut HashSet<usize>, other: &HashSet<usize>, common_of_side: &mut HashSet<usize>, common_of_other: &HashSet<usize>| -> bool {
+        let candidates = common_of_other.iter().filter(|v| !side.contains(v) && !other.contains(v));
+        let kept = |x: usize| -> usize {
+            let neighbours = graph.neighbours_idx(x).unwrap();
+            common_of_side.iter().filter(|v| **v != x && !side.contains(v) && !other.contains(v) && neighbours.contains(v)).count()
+        };
+        let best = candidates.map(|x| (kept(*x), std::cmp::Reverse(*x))).max();
+        match best {
+            Some((_, std::cmp::Reverse(x))) => {
+                let neighbours = graph.neighbours_idx(x).unwrap();
+                common_of_side.retain(|v| neighbours.contains(v));
+                side.insert(x);
+                true
+            },
+            None => false,
+        }
+    };
+
+    loop {
+        let left_first = left.len() <= right.len();
+        let extended = 
+            (left_first && extend(&mut left, &right, &mut common_of_left, &common_of_right))
+            || extend(&mut right, &left, &mut common_of_right, &common_of_left)
+            || (!left_first && extend(&mut left, &right, &mut common_of_left, &common_of_right));
+        if !extended {
+            return Ok((left, right))
+        }
+    }
 }
\ No newline at end of file
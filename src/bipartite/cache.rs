@@ -0,0 +1,102 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::time::Instant;
+
+use crate::atomic::AtomicFile;
+use crate::graphs::Graph;
+use super::find_bipartite;
+
+/// A result of find_bipartite stored in the cache together with statistics of its computation.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CachedResult {
+    /// The left side of the found complete bipartite subgraph, sorted.
+    pub left: Vec<usize>,
+    /// The right side of the found complete bipartite subgraph, sorted.
+    pub right: Vec<usize>,
+    /// The time of the original computation in seconds.
+    pub elapsed_secs: f64,
+    /// The number of times the result was served from the cache.
+    pub hits: usize,
+}
+
+/// An on-disk cache of results of find_bipartite, keyed by the fingerprint of the graph and the parameters.
+/// The cache is a json file, which is rewritten after every new result.
+pub struct ResultCache {
+    filename: String,
+    results: HashMap<String, CachedResult>,
+}
+
+impl ResultCache {
+    /// Opens the cache stored in the file with given filename, or creates an empty one if the file does not exist.
+    pub fn open(filename: &str) -> serde_json::Result<ResultCache> {
+        let results = match File::open(filename) {
+            Ok(file) => serde_json::from_reader(file)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => HashMap::new(),
+            Err(e) => return Err(serde_json::Error::io(e)),
+        };
+        Ok(ResultCache { filename: filename.to_string(), results })
+    }
+
+    /// Returns the key of the result for given graph and parameters.
+    fn key(graph: &Graph, highest_degree_size: usize, bipartite_size: usize) -> String {
+        format!("{:016x}_{}_{}", graph.fingerprint(), highest_degree_size, bipartite_size)
+    }
+
+    /// Returns the cached result with its statistics, if there is one.
+    pub fn get(&self, graph: &Graph, highest_degree_size: usize, bipartite_size: usize) -> Option<&CachedResult> {
+        self.results.get(&ResultCache::key(graph, highest_degree_size, bipartite_size))
+    }
+
+    /// Returns the number of cached results.
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Checks if the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// Returns the result of find_bipartite for given graph and parameters, computing and storing it only if it is not cached.
+    /// # Examples
+    /// ```
+    /// use labisu::bipartite::ResultCache;
+    /// use labisu::graphs::Graph;
+    /// let _ = std::fs::remove_file("cache.json");
+    /// let k300 = Graph::complete(300);
+    /// let mut cache = ResultCache::open("cache.json").unwrap();
+    /// let computed = cache.find_bipartite(&k300, 10, 3).unwrap();
+    /// 
+    /// let mut reopened = ResultCache::open("cache.json").unwrap();
+    /// assert_eq!(computed, reopened.find_bipartite(&k300, 10, 3).unwrap());
+    /// assert_eq!(1, reopened.get(&k300, 10, 3).unwrap().hits);
+    /// assert_eq!(1, reopened.len());
+    /// ```
+    pub fn find_bipartite(&mut self, graph: &Graph, highest_degree_size: usize, bipartite_size: usize) -> serde_json::Result<(HashSet<usize>, HashSet<usize>)> {
+        let key = ResultCache::key(graph, highest_degree_size, bipartite_size);
+        if let Some(cached) = self.results.get_mut(&key) {
+            cached.hits += 1;
+            return Ok((cached.left.iter().copied().collect(), cached.right.iter().copied().collect()))
+        }
+
+        let start = Instant::now();
+        let (left, right) = find_bipartite(graph, highest_degree_size, bipartite_size);
+        let elapsed_secs = start.elapsed().as_secs_f64();
+
+        let mut sorted_left: Vec<usize> = left.iter().copied().collect();
+        let mut sorted_right: Vec<usize> = right.iter().copied().collect();
+        sorted_left.sort_unstable();
+        sorted_right.sort_unstable();
+        self.results.insert(key, CachedResult { left: sorted_left, right: sorted_right, elapsed_secs, hits: 0 });
+        self.write()?;
+        Ok((left, right))
+    }
+
+    /// Writes the cache to its file.
+    pub fn write(&self) -> serde_json::Result<()> {
+        let mut file = AtomicFile::create(&self.filename).map_err(serde_json::Error::io)?;
+        serde_json::to_writer(&mut file, &self.results)?;
+        file.commit().map_err(serde_json::Error::io)
+    }
+}
@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+
+use crate::combinatorics::{binomial, next_combination};
+use crate::graphs::GraphOps;
+
+/// The largest size of a side for which count_bicliques counts the subgraphs.
+pub const MAX_COUNTED_SIDE: usize = 3;
+
+/// Counts the subgraphs of the graph isomorphic to K_{s,t}, not necessarily induced, for 1 <= s, t <= 3.
+/// For every set S of min(s, t) vertices with c common neighbours there are (c choose max(s, t)) such subgraphs with S as a side,
+/// so only the numbers of common neighbours of such sets are needed. They are counted from the neighbourhoods:
+/// the degrees for one vertex, and for two or three vertices the pairs or triples in the neighbourhood of every vertex,
+/// which takes time proportional to the sum of (degree choose min(s, t)) over the vertices.
+/// For s = t every subgraph has two such sides, so the sum is halved.
+/// If s or t is out of range, then it returns an Err value.
+/// # Examples
+/// ```
+/// use labisu::bipartite::count_bicliques;
+/// use labisu::graphs::Graph;
+/// let k5 = Graph::complete(5);
+/// assert_eq!(Ok(10), count_bicliques(&k5, 1, 1));
+/// assert_eq!(Ok(20), count_bicliques(&k5, 3, 1));
+/// // the 4-cycles
+/// assert_eq!(Ok(15), count_bicliques(&k5, 2, 2));
+/// assert_eq!(Ok(9), count_bicliques(&Graph::complete_multipartite(&[3, 3]), 2, 2));
+/// assert_eq!(Ok(10), count_bicliques(&Graph::complete(6), 3, 3));
+/// assert_eq!(Ok(0), count_bicliques(&Graph::petersen(), 2, 2));
+/// assert!(count_bicliques(&k5, 0, 2).is_err());
+/// assert!(count_bicliques(&k5, 2, 4).is_err());
+/// ```
+pub fn count_bicliques<G: GraphOps>(graph: &G, s: usize, t: usize) -> Result<usize, &'static str> {
+    if !(1..=MAX_COUNTED_SIDE).contains(&s) || !(1..=MAX_COUNTED_SIDE).contains(&t) {
+        return Err("The sides of the counted subgraphs have to have between 1 and 3 vertices.")
+    }
+    let (small, large) = (s.min(t), s.max(t));
+    let total = if small == 1 {
+        graph.vertices().map(|v| binomial(graph.degree(v), large)).sum::<usize>()
+    } else {
+        // the number of common neighbours of every set of small vertices which has any
+        let mut common = HashMap::<Vec<usize>, usize>::new();
+        for v in graph.vertices() {
+            let mut neighbours = graph.neighbours_idx(v).unwrap().into_iter().collect::<Vec<usize>>();
+            neighbours.sort_unstable();
+            if neighbours.len() < small {
+                continue
+            }
+            let mut positions = (0..small).collect::<Vec<usize>>();
+            loop {
+                *common.entry(positions.iter().map(|i| neighbours[*i]).collect()).or_insert(0) += 1;
+                if !next_combination(&mut positions, neighbours.len()) {
+                    break
+                }
+            }
+        }
+        common.values().map(|c| binomial(*c, large)).sum::<usize>()
+    };
+    Ok(if s == t { total / 2 } else { total })
+}
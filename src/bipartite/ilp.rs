@@ -0,0 +1,100 @@
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Error, ErrorKind, Write};
+
+use crate::atomic::AtomicFile;
+use crate::graphs::GraphOps;
+
+/// Writes a sum of terms, wrapping it so that the lines stay short as required by some LP readers.
+fn write_sum<W: Write>(writer: &mut W, terms: impl Iterator<Item = String>) -> std::io::Result<()> {
+    for (i, term) in terms.enumerate() {
+        if i > 0 && i % 16 == 0 {
+            writeln!(writer)?;
+        }
+        write!(writer, " {}", term)?;
+    }
+    Ok(())
+}
+
+/// Writes the maximum balanced biclique problem as an integer program in the CPLEX LP format to the file with given filename.
+/// Binary variables x_v and y_v state that vertex v is on the left and on the right side respectively.
+/// The sides are disjoint, the non-adjacent pairs cannot be on opposite sides, the sides have equal sizes
+/// and the size of the left side is maximized.
+/// Returns an Err value for a graph without vertices, as the program would have no variables.
+/// # Examples
+/// ```
+/// use labisu::bipartite::write_biclique_lp;
+/// use labisu::graphs::Graph;
+/// write_biclique_lp(&Graph::cycle(4), "c4.lp").unwrap();
+/// let lp = std::fs::read_to_string("c4.lp").unwrap();
+/// assert!(lp.starts_with("Maximize"));
+/// assert!(lp.contains("disjoint_0: x_0 + y_0 <= 1"));
+/// assert!(lp.contains("non_edge_0_2: x_0 + y_2 <= 1"));
+/// assert!(write_biclique_lp(&Graph::empty(), "empty.lp").is_err());
+/// ```
+pub fn write_biclique_lp<G: GraphOps>(graph: &G, filename: &str) -> std::io::Result<()> {
+    if graph.get_num_of_vertices() == 0 {
+        return Err(Error::new(ErrorKind::InvalidInput, "The graph has to have at least one vertex."))
+    }
+    let mut writer = AtomicFile::create(filename)?;
+
+    writeln!(writer, "Maximize")?;
+    write!(writer, " size:")?;
+    write_sum(&mut writer, graph.vertices().map(|v| if v == 0 { format!("x_{}", v) } else { format!("+ x_{}", v) }))?;
+    writeln!(writer)?;
+
+    writeln!(writer, "Subject To")?;
+    for u in graph.vertices() {
+        writeln!(writer, " disjoint_{}: x_{} + y_{} <= 1", u, u, u)?;
+        let neighbours = graph.neighbours_idx(u).unwrap();
+        for v in graph.vertices().filter(|v| *v != u && !neighbours.contains(v)) {
+            writeln!(writer, " non_edge_{}_{}: x_{} + y_{} <= 1", u, v, u, v)?;
+        }
+    }
+    write!(writer, " balance:")?;
+    write_sum(&mut writer, graph.vertices().flat_map(|v| {
+        let x = if v == 0 { format!("x_{}", v) } else { format!("+ x_{}", v) };
+        [x, format!("- y_{}", v)]
+    }))?;
+    writeln!(writer, " = 0")?;
+
+    writeln!(writer, "Binary")?;
+    write_sum(&mut writer, graph.vertices().flat_map(|v| [format!("x_{}", v), format!("y_{}", v)]))?;
+    writeln!(writer)?;
+    writeln!(writer, "End")?;
+    writer.commit()
+}
+
+/// Reads a solution of the program written by write_biclique_lp from a file in the Gurobi .sol format,
+/// i.e. lines with a variable name and its value, where lines starting with '#' are comments.
+/// Returns the two sides of the biclique.
+/// # Examples
+/// ```
+/// use labisu::bipartite::read_biclique_solution;
+/// use std::collections::HashSet;
+/// std::fs::write("c4.sol", "# Objective value = 2\nx_0 1\nx_1 0\nx_2 1\ny_1 1\ny_3 0.9999999\ny_0 -0\n").unwrap();
+/// let (left, right) = read_biclique_solution("c4.sol").unwrap();
+/// assert_eq!(HashSet::from([0, 2]), left);
+/// assert_eq!(HashSet::from([1, 3]), right);
+/// ```
+pub fn read_biclique_solution(filename: &str) -> std::io::Result<(HashSet<usize>, HashSet<usize>)> {
+    let reader = BufReader::new(File::open(filename)?);
+    let (mut left, mut right) = (HashSet::new(), HashSet::new());
+    for line in reader.lines() {
+        let line = line?;
+        let mut parts = line.split_whitespace();
+        let (name, value) = match (parts.next(), parts.next()) {
+            (Some(name), Some(value)) if !name.starts_with('#') => (name, value),
+            _ => continue,
+        };
+        if value.parse::<f64>().map_or(true, |value| value < 0.5) {
+            continue
+        }
+        if let Some(v) = name.strip_prefix("x_").and_then(|v| v.parse().ok()) {
+            left.insert(v);
+        } else if let Some(v) = name.strip_prefix("y_").and_then(|v| v.parse().ok()) {
+            right.insert(v);
+        }
+    }
+    Ok((left, right))
+}
@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use crate::atomic::AtomicFile;
+use crate::graphs::{Graph, GraphOps};
+use super::{common_neighbours, is_complete_bipartite, ReportSet};
+
+/// The structure around a found complete bipartite subgraph, used for the qualitative analysis of a result of a solver.
+/// # Examples
+/// ```
+/// use labisu::bipartite::BicliqueNeighbourhood;
+/// use labisu::graphs::Graph;
+/// use std::collections::HashSet;
+/// let mut g = Graph::complete_multipartite(&[3, 4]);
+/// g.add_vertex("extra");
+/// g.add_edge_idx(7, 3);
+/// g.add_edge_idx(7, 4);
+/// let neighbourhood = BicliqueNeighbourhood::new(&g, &HashSet::from([0, 1]), &HashSet::from([3, 4])).unwrap();
+/// assert_eq!(vec![4, 4], neighbourhood.left_degrees);
+/// assert_eq!(vec![4, 4], neighbourhood.right_degrees);
+/// assert_eq!(vec![2, 7], neighbourhood.left_extensions.indices);
+/// assert_eq!(vec![5, 6], neighbourhood.right_extensions.indices);
+/// assert_eq!(2 * 2 + 2 * 2, neighbourhood.boundary_edges);
+/// let subgraph = neighbourhood.induced_subgraph(&g);
+/// assert_eq!(4, subgraph.get_num_of_edges());
+/// assert!(neighbourhood.write_to_json("neighbourhood.json").is_ok());
+/// assert!(BicliqueNeighbourhood::new(&g, &HashSet::from([0]), &HashSet::from([1])).is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BicliqueNeighbourhood {
+    /// The left side of the subgraph.
+    pub left: ReportSet,
+    /// The right side of the subgraph.
+    pub right: ReportSet,
+    /// The degrees in the graph of the vertices of the left side, in the order of their indices.
+    pub left_degrees: Vec<usize>,
+    /// The degrees in the graph of the vertices of the right side, in the order of their indices.
+    pub right_degrees: Vec<usize>,
+    /// The vertices outside the subgraph adjacent to the whole right side, i.e. the ones which could extend the left side.
+    pub left_extensions: ReportSet,
+    /// The vertices outside the subgraph adjacent to the whole left side, i.e. the ones which could extend the right side.
+    pub right_extensions: ReportSet,
+    /// The number of edges between the subgraph and the rest of the graph.
+    pub boundary_edges: usize,
+    /// The number of edges within the sides, which are not part of the complete bipartite subgraph.
+    pub inner_edges: usize,
+}
+
+impl BicliqueNeighbourhood {
+    /// Describes the neighbourhood of given complete bipartite subgraph of the graph.
+    /// If the sides are empty or do not form a complete bipartite subgraph, then it returns an Err value.
+    pub fn new(graph: &Graph, left: &HashSet<usize>, right: &HashSet<usize>) -> Result<BicliqueNeighbourhood, &'static str> {
+        if left.is_empty() || right.is_empty() || !is_complete_bipartite(graph, left, right) {
+            return Err("The sides do not form a complete bipartite subgraph.")
+        }
+        let left = ReportSet::new(graph, left);
+        let right = ReportSet::new(graph, right);
+        let in_subgraph = |v: &usize| left.indices.binary_search(v).is_ok() || right.indices.binary_search(v).is_ok();
+        let extensions = |side: &ReportSet| {
+            let mut outside = common_neighbours(graph, &side.indices.iter().copied().collect());
+            outside.retain(|v| !in_subgraph(v));
+            ReportSet::new(graph, &outside)
+        };
+
+        let mut boundary_edges = 0;
+        let mut inner_edges = 0;
+        for side in [&left, &right] {
+            for v in &side.indices {
+                graph.for_each_neighbour(*v, &mut |u| {
+                    if !in_subgraph(&u) {
+                        boundary_edges += 1;
+                    } else if side.indices.binary_search(&u).is_ok() && *v < u {
+                        inner_edges += 1;
+                    }
+                });
+            }
+        }
+
+        Ok(BicliqueNeighbourhood {
+            left_degrees: left.indices.iter().map(|v| graph.degree(*v)).collect(),
+            right_degrees: right.indices.iter().map(|v| graph.degree(*v)).collect(),
+            left_extensions: extensions(&right),
+            right_extensions: extensions(&left),
+            left,
+            right,
+            boundary_edges,
+            inner_edges,
+        })
+    }
+
+    /// Returns the subgraph induced by the sides, with the left side first, e.g. to be exported with Graph::write_to_dot.
+    pub fn induced_subgraph(&self, graph: &Graph) -> Graph {
+        let vertices = self.left.indices.iter().chain(&self.right.indices).copied().collect::<Vec<usize>>();
+        graph.induced_subgraph(&vertices).expect("The report does not describe this graph.")
+    }
+
+    /// Writes the report to a json file with given filename.
+    pub fn write_to_json(&self, filename: &str) -> serde_json::Result<()> {
+        let mut file = AtomicFile::create(filename).map_err(serde_json::Error::io)?;
+        serde_json::to_writer_pretty(&mut file, self)?;
+        file.commit().map_err(serde_json::Error::io)
+    }
+}
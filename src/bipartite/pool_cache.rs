@@ -0,0 +1,158 @@
+use std::collections::HashSet;
+
+use crate::graphs::GraphOps;
+
+/// The number of bits in a word of a bitset.
+const WORD_BITS: usize = 64;
+
+/// Returns the number of vertices in the intersection of two bitsets.
+fn common_count(a: &[u64], b: &[u64]) -> usize {
+    a.iter().zip(b).map(|(x, y)| (x & y).count_ones() as usize).sum()
+}
+
+/// The pool of the highest degree vertices of a graph with their neighbourhoods stored as bitsets,
+/// built once and shared by repeated runs of find_bipartite with different parameters on the same graph, e.g. in a parameter sweep.
+/// The pool of every highest_degree_size up to the size of the cache is a prefix of the cached one, so a single cache serves all of them.
+/// The bitsets take highest_degree_size * n / 8 bytes. They are also compared with the neighbourhoods of the pool in the graph
+/// given to every search, so a cache is not used with a graph in which the pool has other neighbourhoods.
+/// # Examples
+/// ```
+/// use labisu::bipartite::{find_bipartite, PoolCache};
+/// use labisu::graphs::Graph;
+/// let k300 = Graph::complete(300);
+/// let cache = PoolCache::new(&k300, 20);
+/// for (s, t) in [(10, 3), (20, 5), (12, 2)] {
+///     let (left, right) = cache.find_bipartite(&k300, s, t).unwrap();
+///     assert_eq!(find_bipartite(&k300, s, t), (left, right));
+/// }
+/// assert!(cache.find_bipartite(&k300, 21, 3).is_err());
+/// assert!(cache.find_bipartite(&Graph::complete(10), 10, 3).is_err());
+/// // graphs with the same numbers of vertices and edges, but other neighbourhoods
+/// let without = |a: usize, b: usize| {
+///     let mut graph = Graph::from_names((0..300).map(|i| i.to_string()).collect());
+///     for u in 0..300 {
+///         for v in u + 1..300 {
+///             if (u, v) != (a, b) {
+///                 graph.add_edge_idx(u, v);
+///             }
+///         }
+///     }
+///     graph
+/// };
+/// let cache = PoolCache::new(&without(0, 1), 300);
+/// assert!(cache.find_bipartite(&without(0, 1), 10, 3).is_ok());
+/// assert!(cache.find_bipartite(&without(0, 2), 10, 3).is_err());
+/// ```
+pub struct PoolCache {
+    pool: Vec<usize>,
+    neighbourhoods: Vec<Vec<u64>>,
+    num_of_vertices: usize,
+    num_of_edges: usize,
+}
+
+impl PoolCache {
+    /// Builds the bitset neighbourhoods of the max_highest_degree_size vertices of the highest degree (all vertices if there are fewer of them).
+    pub fn new<G: GraphOps>(graph: &G, max_highest_degree_size: usize) -> PoolCache {
+        let n = graph.get_num_of_vertices();
+        let pool = graph.highest_degree_vertices(max_highest_degree_size.min(n));
+        let words = n.div_ceil(WORD_BITS);
+        let neighbourhoods =
+            pool
+            .iter()
+            .map(|v| {
+                let mut bits = vec![0u64; words];
+                graph.for_each_neighbour(*v, &mut |u| bits[u / WORD_BITS] |= 1 << (u % WORD_BITS));
+                bits
+            })
+            .collect();
+        PoolCache { pool, neighbourhoods, num_of_vertices: n, num_of_edges: graph.get_num_of_edges() }
+    }
+
+    /// Returns the cached pool, i.e. the vertices of the highest degree in the order used by find_bipartite.
+    pub fn pool(&self) -> &[usize] {
+        &self.pool
+    }
+
+    /// Runs the same search as find_bipartite with given parameters using the cached pool, so only the search itself is repeated.
+    /// The subsets of the pool are examined in lexicographic order and a subset is abandoned as soon as the common neighbourhood
+    /// of its prefix is too small, so if several complete bipartite subgraphs exist, the one found may differ from the one of find_bipartite.
+    /// Returns an Err value if highest_degree_size exceeds the size of the cache or the graph differs from the cached one in the numbers of vertices and edges or the neighbourhoods of the pool.
+    pub fn find_bipartite<G: GraphOps>(&self, graph: &G, highest_degree_size: usize, bipartite_size: usize) -> Result<(HashSet<usize>, HashSet<usize>), &'static str> {
+        if !self.matches(graph) {
+            return Err("The graph is not the one the cache was built for.")
+        }
+        if highest_degree_size > self.pool.len() {
+            return Err("The pool is larger than the cache.")
+        }
+
+        let (n, m) = (self.num_of_vertices, self.num_of_edges);
+        if 0 < m && m.pow(2) < 64 * n.pow(3) {
+            // the shortcut of find_bipartite for sparse graphs
+            for i in graph.vertices() {
+                if let Some(j) = graph.neighbours_idx(i).unwrap().iter().next() {
+                    return Ok((HashSet::from([i]), HashSet::from([*j])))
+                }
+            }
+        }
+
+        // the vertices outside the pool, which are the candidates for the other side
+        let mut outside = vec![u64::MAX; n.div_ceil(WORD_BITS)];
+        if n % WORD_BITS != 0 {
+            *outside.last_mut().unwrap() = (1 << (n % WORD_BITS)) - 1;
+        }
+        for v in &self.pool[..highest_degree_size] {
+            outside[v / WORD_BITS] &= !(1 << (v % WORD_BITS));
+        }
+
+        let mut chosen = Vec::with_capacity(bipartite_size);
+        match self.extend(&outside, &mut chosen, 0, highest_degree_size, bipartite_size) {
+            Some(common) => {
+                let left = chosen.iter().map(|i| self.pool[*i]).collect();
+                let right =
+                    (0..n)
+                    .filter(|v| common[v / WORD_BITS] >> (v % WORD_BITS) & 1 == 1)
+                    .take(bipartite_size)
+                    .collect();
+                Ok((left, right))
+            },
+            None => Ok((HashSet::new(), HashSet::new())),
+        }
+    }
+
+    /// Checks if the graph has the numbers of vertices and edges of the cached graph and the same neighbourhoods of the pool.
+    fn matches<G: GraphOps>(&self, graph: &G) -> bool {
+        if graph.get_num_of_vertices() != self.num_of_vertices || graph.get_num_of_edges() != self.num_of_edges {
+            return false
+        }
+        self.pool.iter().zip(&self.neighbourhoods).all(|(v, bits)| {
+            let mut degree = 0;
+            let mut cached = true;
+            graph.for_each_neighbour(*v, &mut |u| {
+                degree += 1;
+                cached &= bits[u / WORD_BITS] >> (u % WORD_BITS) & 1 == 1;
+            });
+            cached && degree == common_count(bits, bits)
+        })
+    }
+
+    /// Extends the chosen subset of the pool with the vertices from index start onwards, until it has bipartite_size vertices
+    /// with at least highest_degree_size common neighbours outside the pool. Returns the bitset of the common neighbours if it succeeds.
+    fn extend(&self, common: &[u64], chosen: &mut Vec<usize>, start: usize, highest_degree_size: usize, bipartite_size: usize) -> Option<Vec<u64>> {
+        if chosen.len() == bipartite_size {
+            return (common_count(common, common) >= highest_degree_size).then(|| common.to_vec())
+        }
+        let last = (highest_degree_size + chosen.len() + 1).checked_sub(bipartite_size)?;
+        for i in start..last {
+            if common_count(common, &self.neighbourhoods[i]) < highest_degree_size {
+                continue
+            }
+            let next = common.iter().zip(&self.neighbourhoods[i]).map(|(x, y)| x & y).collect::<Vec<u64>>();
+            chosen.push(i);
+            if let Some(found) = self.extend(&next, chosen, i + 1, highest_degree_size, bipartite_size) {
+                return Some(found)
+            }
+            chosen.pop();
+        }
+        None
+    }
+}
@@ -0,0 +1,129 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::File;
+use std::time::{Duration, Instant};
+
+use crate::atomic::AtomicFile;
+use crate::graphs::Graph;
+use super::{find_bipartite, is_complete_bipartite, qr_parameters_checked, QrParameters};
+
+/// The parameters of a run of find_bipartite together with the size of the graph it was run on.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReportParameters {
+    /// The number of the highest degree vertices in the pool.
+    pub highest_degree_size: usize,
+    /// The size of the side of the subgraph within the pool.
+    pub bipartite_size: usize,
+    /// The number of vertices of the graph.
+    pub num_of_vertices: usize,
+    /// The number of edges of the graph.
+    pub num_of_edges: usize,
+    /// The q and r values of the graph, if they are defined for it.
+    pub qr: Option<QrParameters>,
+}
+
+/// A side of the found subgraph given both by indices and names of its vertices, sorted by indices.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportSet {
+    /// The sorted indices of the vertices.
+    pub indices: Vec<usize>,
+    /// The names of the vertices, in the order of their indices.
+    pub names: Vec<String>,
+}
+
+impl ReportSet {
+    /// Creates a side of the subgraph from a set of indices of the graph.
+    pub(super) fn new(graph: &Graph, set: &HashSet<usize>) -> ReportSet {
+        let mut indices = set.iter().copied().collect::<Vec<usize>>();
+        indices.sort_unstable();
+        let names = indices.iter().map(|idx| graph.idx_to_name(*idx).unwrap_or_default()).collect();
+        ReportSet { indices, names }
+    }
+}
+
+/// Statistics of the found subgraph.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportStats {
+    /// The number of edges of the found complete bipartite subgraph.
+    pub found_edges: usize,
+    /// The fingerprint of the graph, identifying it regardless of vertex names.
+    pub fingerprint: u64,
+}
+
+/// A machine-readable report of a run of find_bipartite.
+/// # Examples
+/// ```
+/// use labisu::bipartite::BipartiteReport;
+/// use labisu::graphs::Graph;
+/// let k300 = Graph::complete(300);
+/// let report = BipartiteReport::new(&k300, 10, 3);
+/// assert!(report.verified);
+/// assert_eq!(vec![0, 1, 2], report.left.indices);
+/// assert_eq!("vertex_10", report.right.names[0]);
+/// assert_eq!(9, report.stats.found_edges);
+/// assert!(report.write_report("report.json").is_ok());
+/// assert_eq!(report, BipartiteReport::read_report("report.json").unwrap());
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BipartiteReport {
+    /// The parameters of the run.
+    pub params: ReportParameters,
+    /// The side of the found subgraph within the pool, empty if nothing was found.
+    pub left: ReportSet,
+    /// The other side of the found subgraph, empty if nothing was found.
+    pub right: ReportSet,
+    /// Whether the sides are nonempty and form a complete bipartite subgraph of the graph.
+    pub verified: bool,
+    /// The time of the search.
+    pub duration: Duration,
+    /// Statistics of the found subgraph.
+    pub stats: ReportStats,
+}
+
+impl BipartiteReport {
+    /// Runs find_bipartite on the graph with given parameters and reports its result.
+    pub fn new(graph: &Graph, highest_degree_size: usize, bipartite_size: usize) -> BipartiteReport {
+        let start = Instant::now();
+        let (left, right) = find_bipartite(graph, highest_degree_size, bipartite_size);
+        BipartiteReport::from_result(graph, highest_degree_size, bipartite_size, &left, &right, start.elapsed())
+    }
+
+    /// Reports a result computed elsewhere, e.g. read from a cache or a solver, verifying it against the graph.
+    pub fn from_result(graph: &Graph, highest_degree_size: usize, bipartite_size: usize, left: &HashSet<usize>, right: &HashSet<usize>, duration: Duration) -> BipartiteReport {
+        BipartiteReport {
+            params: ReportParameters {
+                highest_degree_size,
+                bipartite_size,
+                num_of_vertices: graph.get_num_of_vertices(),
+                num_of_edges: graph.get_num_of_edges(),
+                qr: qr_parameters_checked(graph).ok(),
+            },
+            left: ReportSet::new(graph, left),
+            right: ReportSet::new(graph, right),
+            verified: !left.is_empty() && !right.is_empty() && is_complete_bipartite(graph, left, right),
+            duration,
+            stats: ReportStats {
+                found_edges: left.len() * right.len(),
+                fingerprint: graph.fingerprint(),
+            },
+        }
+    }
+
+    /// Checks if a subgraph was found.
+    pub fn is_found(&self) -> bool {
+        !self.left.indices.is_empty()
+    }
+
+    /// Writes the report to a json file with given filename.
+    pub fn write_report(&self, filename: &str) -> serde_json::Result<()> {
+        let mut file = AtomicFile::create(filename).map_err(serde_json::Error::io)?;
+        serde_json::to_writer_pretty(&mut file, self)?;
+        file.commit().map_err(serde_json::Error::io)
+    }
+
+    /// Reads a report from a json file with given filename.
+    pub fn read_report(filename: &str) -> serde_json::Result<BipartiteReport> {
+        let file = File::open(filename).map_err(serde_json::Error::io)?;
+        serde_json::from_reader(file)
+    }
+}
@@ -0,0 +1,136 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+use crate::graphs::Graph;
+use super::{search_pool, MAX_BIPARTITE_SIZE};
+
+/// The way the pool of a restart of find_bipartite_restarts is chosen.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PoolStrategy {
+    /// The vertices of the highest degree, as in find_bipartite.
+    TopDegree,
+    /// A random subset of the twice as many vertices of the highest degree.
+    RandomTopDegree,
+    /// A random subset of the t-core, which contains every complete bipartite subgraph with sides of size t.
+    RandomCore,
+}
+
+impl PoolStrategy {
+    /// Returns the strategy of the restart with given number: the first restart is the deterministic one,
+    /// the next ones alternate between the random strategies.
+    fn of_restart(restart: usize) -> PoolStrategy {
+        match restart {
+            0 => PoolStrategy::TopDegree,
+            r if r % 2 == 1 => PoolStrategy::RandomTopDegree,
+            _ => PoolStrategy::RandomCore,
+        }
+    }
+
+    /// Chooses a pool of at most s vertices.
+    fn pool(&self, graph: &Graph, s: usize, core: &[usize], rng: &mut StdRng) -> Vec<usize> {
+        let n = graph.get_num_of_vertices();
+        let mut pool = match self {
+            PoolStrategy::TopDegree => return graph.highest_degree_vertices(s.min(n)),
+            PoolStrategy::RandomTopDegree => graph.highest_degree_vertices((2 * s).min(n)),
+            PoolStrategy::RandomCore => core.to_vec(),
+        };
+        pool.shuffle(rng);
+        pool.truncate(s);
+        pool
+    }
+}
+
+/// The statistics of a single restart of find_bipartite_restarts.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RestartStats {
+    /// The way the pool was chosen.
+    pub strategy: PoolStrategy,
+    /// The vertices of the pool.
+    pub pool: Vec<usize>,
+    /// Whether a complete bipartite subgraph was found.
+    pub found: bool,
+    /// The number of examined subsets of the pool, not counting the ones skipped as unable to succeed.
+    pub examined: usize,
+    /// The time of the restart.
+    pub duration: Duration,
+}
+
+/// The best result of find_bipartite_restarts together with the statistics of all restarts.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RestartsReport {
+    /// The side of the best subgraph within its pool, empty if none was found.
+    pub left: HashSet<usize>,
+    /// The other side of the best subgraph, empty if none was found.
+    pub right: HashSet<usize>,
+    /// The statistics of the restarts, in the order they were run.
+    pub restarts: Vec<RestartStats>,
+}
+
+impl RestartsReport {
+    /// Returns the fraction of restarts which found a complete bipartite subgraph, or 0 if there were none.
+    pub fn success_rate(&self) -> f64 {
+        match self.restarts.len() {
+            0 => 0.0,
+            len => self.restarts.iter().filter(|stats| stats.found).count() as f64 / len as f64,
+        }
+    }
+}
+
+/// Runs the search of find_bipartite several times with different pools of s vertices, looking for K_{t,t} every time.
+/// The first restart uses the vertices of the highest degree like find_bipartite, the next ones alternate between
+/// random subsets of the 2s vertices of the highest degree and random subsets of the t-core (see PoolStrategy).
+/// Unlike find_bipartite it does not give up on graphs too sparse for the guarantee of the paper.
+/// All restarts are run, so that the statistics show how often the heuristic succeeds, and the best result is returned,
+/// i.e. the one with the largest smaller side, the earliest one in case of a tie. The random pools depend only on the seed.
+/// Panics if t exceeds MAX_BIPARTITE_SIZE.
+/// # Examples
+/// ```
+/// use labisu::bipartite::{find_bipartite_restarts, is_complete_bipartite, PoolStrategy};
+/// use labisu::graphs::Graph;
+/// let k300 = Graph::complete(300);
+/// let report = find_bipartite_restarts(&k300, 10, 3, 5, 42);
+/// assert_eq!(5, report.restarts.len());
+/// assert_eq!(PoolStrategy::TopDegree, report.restarts[0].strategy);
+/// assert_eq!(PoolStrategy::RandomTopDegree, report.restarts[1].strategy);
+/// assert_eq!(PoolStrategy::RandomCore, report.restarts[2].strategy);
+/// assert_eq!(1.0, report.success_rate());
+/// assert_eq!(3, report.left.len());
+/// assert!(is_complete_bipartite(&k300, &report.left, &report.right));
+/// let again = find_bipartite_restarts(&k300, 10, 3, 5, 42);
+/// assert!(report.restarts.iter().zip(&again.restarts).all(|(a, b)| a.pool == b.pool));
+/// // no vertex of a cycle has enough neighbours outside the pool, so only the first subset is examined
+/// assert_eq!(1, find_bipartite_restarts(&Graph::cycle(30), 6, 2, 1, 0).restarts[0].examined);
+/// // a path contains no K_{3,3} and its 3-core is empty, so no restart succeeds
+/// let report = find_bipartite_restarts(&Graph::path(10), 6, 3, 4, 0);
+/// assert_eq!(0.0, report.success_rate());
+/// assert!(report.left.is_empty() && report.right.is_empty());
+/// ```
+pub fn find_bipartite_restarts(graph: &Graph, s: usize, t: usize, restarts: usize, seed: u64) -> RestartsReport {
+    assert!(t <= MAX_BIPARTITE_SIZE, "The size of the bipartite subgraph exceeds MAX_BIPARTITE_SIZE.");
+    let mut rng = StdRng::seed_from_u64(seed);
+    let core = graph.core(t);
+    let core = graph.vertices().filter(|v| core[*v]).collect::<Vec<usize>>();
+    let mut report = RestartsReport { left: HashSet::new(), right: HashSet::new(), restarts: Vec::with_capacity(restarts) };
+    for restart in 0..restarts {
+        let start = Instant::now();
+        let strategy = PoolStrategy::of_restart(restart);
+        let pool = strategy.pool(graph, s, &core, &mut rng);
+        // a pool smaller than t, e.g. of an empty t-core, cannot contain a side of K_{t,t}
+        let (result, examined) = if pool.len() < t {
+            (None, 0)
+        } else {
+            search_pool(graph, &pool, t).expect("The size of the bipartite subgraph exceeds MAX_BIPARTITE_SIZE.")
+        };
+        report.restarts.push(RestartStats { strategy, pool, found: result.is_some(), examined, duration: start.elapsed() });
+        if let Some((left, right)) = result {
+            if left.len().min(right.len()) > report.left.len().min(report.right.len()) {
+                (report.left, report.right) = (left, right);
+            }
+        }
+    }
+    report
+}
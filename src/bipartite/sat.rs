@@ -0,0 +1,156 @@
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+
+use crate::atomic::AtomicFile;
+use crate::graphs::GraphOps;
+
+/// A reduction of the question "does the graph contain K_{s,t}?" to satisfiability of a CNF formula.
+/// Variable v + 1 states that vertex v is on the left side and variable n + v + 1 that it is on the right side,
+/// the remaining variables are auxiliary counters of the sequential counter encoding of the cardinality constraints.
+pub struct SatEncoding {
+    num_of_vertices: usize,
+    num_of_variables: usize,
+    clauses: Vec<Vec<i64>>,
+}
+
+impl SatEncoding {
+    /// Encodes the existence of a complete bipartite subgraph with sides of sizes at least s and t.
+    /// # Examples
+    /// ```
+    /// use labisu::bipartite::SatEncoding;
+    /// use labisu::graphs::Graph;
+    /// let encoding = SatEncoding::new(&Graph::cycle(4), 2, 2);
+    /// assert_eq!(8, encoding.get_num_of_vertex_variables());
+    /// assert!(encoding.get_num_of_variables() > 8);
+    /// ```
+    pub fn new<G: GraphOps>(graph: &G, s: usize, t: usize) -> SatEncoding {
+        let n = graph.get_num_of_vertices();
+        let mut encoding = SatEncoding { num_of_vertices: n, num_of_variables: 2 * n, clauses: vec![] };
+        let left = |v: usize| v as i64 + 1;
+        let right = |v: usize| (n + v) as i64 + 1;
+
+        for u in graph.vertices() {
+            let neighbours = graph.neighbours_idx(u).unwrap();
+            encoding.clauses.push(vec![-left(u), -right(u)]);
+            for v in graph.vertices().filter(|v| *v != u && !neighbours.contains(v)) {
+                encoding.clauses.push(vec![-left(u), -right(v)]);
+            }
+        }
+
+        encoding.at_least((0..n).map(left).collect(), s);
+        encoding.at_least((0..n).map(right).collect(), t);
+        encoding
+    }
+
+    /// Adds the sequential counter encoding of "at least k of the literals are true".
+    /// Counter variable (i, j) may be true only if at least j of the first i literals are true.
+    fn at_least(&mut self, literals: Vec<i64>, k: usize) {
+        if k == 0 {
+            return
+        }
+        if k > literals.len() {
+            self.clauses.push(vec![]);
+            return
+        }
+
+        let mut prev: Vec<i64> = vec![]; // counters of the first i - 1 literals, prev[j - 1] for at least j
+        for (i, literal) in literals.iter().enumerate() {
+            let curr: Vec<i64> = 
+                (0..std::cmp::min(i + 1, k))
+                .map(|_| { self.num_of_variables += 1; self.num_of_variables as i64 })
+                .collect();
+            for (j, counter) in curr.iter().enumerate() {
+                let mut with_literal = vec![-counter, *literal];
+                let mut with_prev = vec![-counter];
+                if let Some(same) = prev.get(j) {
+                    with_literal.push(*same);
+                    with_prev.push(*same);
+                }
+                self.clauses.push(with_literal);
+                if j > 0 {
+                    with_prev.push(prev[j - 1]);
+                    self.clauses.push(with_prev);
+                }
+            }
+            prev = curr;
+        }
+        self.clauses.push(vec![prev[k - 1]]);
+    }
+
+    /// Returns the number of variables of the formula.
+    pub fn get_num_of_variables(&self) -> usize {
+        self.num_of_variables
+    }
+
+    /// Returns the number of variables describing the sides of the vertices, i.e. twice the number of vertices.
+    pub fn get_num_of_vertex_variables(&self) -> usize {
+        2 * self.num_of_vertices
+    }
+
+    /// Returns the number of clauses of the formula.
+    pub fn get_num_of_clauses(&self) -> usize {
+        self.clauses.len()
+    }
+
+    /// Writes the formula in the DIMACS CNF format to the file with given filename.
+    /// # Examples
+    /// ```
+    /// use labisu::bipartite::SatEncoding;
+    /// use labisu::graphs::Graph;
+    /// let encoding = SatEncoding::new(&Graph::cycle(4), 2, 2);
+    /// encoding.write_dimacs("c4.cnf").unwrap();
+    /// let header = std::fs::read_to_string("c4.cnf").unwrap().lines().next().unwrap().to_string();
+    /// assert_eq!(format!("p cnf {} {}", encoding.get_num_of_variables(), encoding.get_num_of_clauses()), header);
+    /// ```
+    pub fn write_dimacs(&self, filename: &str) -> std::io::Result<()> {
+        let mut writer = AtomicFile::create(filename)?;
+        writeln!(writer, "p cnf {} {}", self.num_of_variables, self.clauses.len())?;
+        for clause in &self.clauses {
+            for literal in clause {
+                write!(writer, "{} ", literal)?;
+            }
+            writeln!(writer, "0")?;
+        }
+        writer.commit()
+    }
+
+    /// Maps a model, given as a list of literals true in it, back to the two sides of the complete bipartite subgraph.
+    /// # Examples
+    /// ```
+    /// use labisu::bipartite::SatEncoding;
+    /// use labisu::graphs::Graph;
+    /// use std::collections::HashSet;
+    /// let encoding = SatEncoding::new(&Graph::cycle(4), 2, 2);
+    /// let (left, right) = encoding.decode_model(&[1, -2, 3, -4, -5, 6, -7, 8]);
+    /// assert_eq!(HashSet::from([0, 2]), left);
+    /// assert_eq!(HashSet::from([1, 3]), right);
+    /// ```
+    pub fn decode_model(&self, model: &[i64]) -> (HashSet<usize>, HashSet<usize>) {
+        let n = self.num_of_vertices as i64;
+        let left = model.iter().filter(|l| (1..=n).contains(*l)).map(|l| (l - 1) as usize).collect();
+        let right = model.iter().filter(|l| (n + 1..=2 * n).contains(*l)).map(|l| (l - n - 1) as usize).collect();
+        (left, right)
+    }
+
+    /// Reads the output of a SAT solver from the file with given filename and decodes the model.
+    /// Both the competition format ("s SATISFIABLE" and "v" lines) and the MiniSat format ("SAT" and a line of literals)
+    /// are accepted. Returns None if the formula is unsatisfiable, i.e. there is no such complete bipartite subgraph.
+    pub fn read_solution(&self, filename: &str) -> std::io::Result<Option<(HashSet<usize>, HashSet<usize>)>> {
+        let reader = BufReader::new(File::open(filename)?);
+        let mut model = vec![];
+        for line in reader.lines() {
+            let line = line?;
+            let line = line.trim();
+            if line.contains("UNSAT") {
+                return Ok(None)
+            }
+            if line.starts_with('c') || line.starts_with('s') || line == "SAT" {
+                continue
+            }
+            let literals = line.strip_prefix('v').unwrap_or(line);
+            model.extend(literals.split_whitespace().filter_map(|literal| literal.parse::<i64>().ok()));
+        }
+        Ok(Some(self.decode_model(&model)))
+    }
+}
@@ -0,0 +1,100 @@
+use std::collections::HashSet;
+
+use crate::combinatorics::GraySubsets;
+use crate::graphs::GraphOps;
+use super::{c_solution, gray_search, CountArray};
+
+/// A complete bipartite subgraph together with the total weight of its edges.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WeightedBiclique {
+    /// The side of the subgraph within the pool.
+    pub left: HashSet<usize>,
+    /// The other side of the subgraph, i.e. all common neighbours of the left side outside the pool.
+    pub right: HashSet<usize>,
+    /// The total weight of the edges between the sides.
+    pub weight: f64,
+}
+
+/// Runs the Gray code search of find_bipartite over the subsets of the pool, calling visit with the pool, the characteristic vector of the subset
+/// and the counters, which hold the total weight of the edges to its common neighbours outside the pool. The search stops when visit returns true.
+/// Panics if bipartite_size exceeds MAX_BIPARTITE_SIZE.
+fn weighted_search<G, W, F>(graph: &G, weight: &W, highest_degree_size: usize, bipartite_size: usize, mut visit: F)
+where G: GraphOps, W: Fn(usize, usize) -> f64, F: FnMut(&[usize], &[usize], &mut CountArray) -> bool {
+    let pool = graph.highest_degree_vertices(highest_degree_size);
+    let gray_generator = GraySubsets::new(highest_degree_size, bipartite_size);
+    let mut curr_subset = gray_generator.init();
+    let mut b = CountArray::new_weighted(&pool, &curr_subset, bipartite_size, graph, weight).expect("The size of the bipartite subgraph exceeds MAX_BIPARTITE_SIZE.");
+    gray_search(gray_generator, &mut curr_subset, &mut b, |curr_subset, b| visit(&pool, curr_subset, b));
+}
+
+/// Builds the result for the current subset, recomputing the weight exactly.
+fn weighted_solution<W: Fn(usize, usize) -> f64>(pool: &[usize], curr_subset: &[usize], b: &mut CountArray, weight: &W) -> WeightedBiclique {
+    let left = c_solution(curr_subset, pool);
+    let right = b.full_vertices(usize::MAX);
+    let weight = left.iter().flat_map(|v| right.iter().map(move |u| (*v, *u))).map(|(v, u)| weight(v, u)).sum();
+    WeightedBiclique { left, right, weight }
+}
+
+/// Runs the same search as find_bipartite over subsets of bipartite_size of the pool of the highest degree vertices,
+/// but a subset is accepted if the total weight of the edges to all its common neighbours outside the pool reaches min_weight,
+/// instead of the number of these neighbours reaching the size of the pool.
+/// The weight of the edge between v and u is given by weight(v, u), where v is in the pool, and should be non-negative.
+/// Returns None if no subset is accepted. Panics if bipartite_size exceeds MAX_BIPARTITE_SIZE.
+/// # Examples:
+/// ```
+/// use labisu::bipartite::find_bipartite_weighted;
+/// use labisu::graphs::Graph;
+/// use std::collections::HashSet;
+///
+/// let k6 = Graph::complete(6);
+/// let heavy = |v: usize, u: usize| if v == 5 || u == 5 { 10.0 } else { 1.0 };
+/// let found = find_bipartite_weighted(&k6, heavy, 2, 1, 13.0).unwrap();
+/// assert_eq!(HashSet::from([0]), found.left);
+/// assert_eq!(HashSet::from([2, 3, 4, 5]), found.right);
+/// assert_eq!(13.0, found.weight);
+/// assert!(find_bipartite_weighted(&k6, heavy, 2, 1, 100.0).is_none());
+/// ```
+pub fn find_bipartite_weighted<G, W>(graph: &G, weight: W, highest_degree_size: usize, bipartite_size: usize, min_weight: f64) -> Option<WeightedBiclique>
+where G: GraphOps, W: Fn(usize, usize) -> f64 {
+    let mut found = None;
+    weighted_search(graph, &weight, highest_degree_size, bipartite_size, |pool, curr_subset, b| {
+        if b.full_weight() >= min_weight {
+            found = Some(weighted_solution(pool, curr_subset, b, &weight));
+            return true
+        }
+        false
+    });
+    found
+}
+
+/// Examines all subsets of bipartite_size of the pool of the highest degree vertices and returns the complete bipartite subgraph
+/// between a subset and all its common neighbours outside the pool with the largest total weight of edges.
+/// The weight of the edge between v and u is given by weight(v, u), where v is in the pool, and should be non-negative.
+/// Returns None if no subset has a common neighbour outside the pool. Panics if bipartite_size exceeds MAX_BIPARTITE_SIZE.
+/// # Examples:
+/// ```
+/// use labisu::bipartite::max_weight_bipartite;
+/// use labisu::graphs::Graph;
+/// use std::collections::HashSet;
+///
+/// let k33 = Graph::complete_multipartite(&[3, 3]);
+/// let weight = |v: usize, u: usize| (v * u) as f64;
+/// let best = max_weight_bipartite(&k33, weight, 3, 2).unwrap();
+/// assert_eq!(HashSet::from([1, 2]), best.left);
+/// assert_eq!(HashSet::from([3, 4, 5]), best.right);
+/// assert_eq!(36.0, best.weight);
+/// ```
+pub fn max_weight_bipartite<G, W>(graph: &G, weight: W, highest_degree_size: usize, bipartite_size: usize) -> Option<WeightedBiclique>
+where G: GraphOps, W: Fn(usize, usize) -> f64 {
+    let mut best: Option<WeightedBiclique> = None;
+    let mut best_weight = f64::NEG_INFINITY;
+    weighted_search(graph, &weight, highest_degree_size, bipartite_size, |pool, curr_subset, b| {
+        let full_weight = b.full_weight();
+        if full_weight > best_weight && b.full_count() > 0 {
+            best_weight = full_weight;
+            best = Some(weighted_solution(pool, curr_subset, b, &weight));
+        }
+        false
+    });
+    best
+}
@@ -1,3 +1,8 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp;
+use serde::{Deserialize, Serialize};
+
 /// Returns the value of n choose k.
 /// # Examples
 /// ```
@@ -7,22 +12,292 @@
 /// assert_eq!(252, binomial(10, 5));
 /// assert_eq!(1, binomial(10, 10));
 /// assert_eq!(0, binomial(10, 11));
+/// assert_eq!(118264581564861424, binomial(60, 30));
 pub fn binomial(n: usize, k: usize) -> usize {
     if k > n { return 0 }
-    let mut n = n;
-    let mut k = std::cmp::min(k, n - k);
-    let (mut acc_up, mut acc_down) = (1, 1);
-    while k > 0 {
-        acc_up *= n;
-        acc_down *= k;
-        n -= 1;
-        k -= 1;
+    let k = cmp::min(k, n - k);
+    // after i steps acc equals (n choose i), so every division is exact
+    (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+}
+
+/// Returns the position of a k-subset of {0,1,...,n-1} in the lexicographic order of all k-subsets.
+/// The subset has to be given as a sorted vector of its elements.
+/// # Examples
+/// ```
+/// use labisu::combinatorics::rank_combination;
+/// assert_eq!(0, rank_combination(&[0, 1, 2], 5));
+/// assert_eq!(1, rank_combination(&[0, 1, 3], 5));
+/// assert_eq!(9, rank_combination(&[2, 3, 4], 5));
+/// ```
+pub fn rank_combination(subset: &[usize], n: usize) -> usize {
+    let k = subset.len();
+    let mut rank = 0;
+    let mut first_free = 0;
+    for (i, elem) in subset.iter().enumerate() {
+        for x in first_free..*elem {
+            rank += binomial(n - x - 1, k - i - 1);
+        }
+        first_free = elem + 1;
+    }
+    rank
+}
+
+/// Returns the k-subset of {0,1,...,n-1} with given position in the lexicographic order.
+/// It is the inverse of rank_combination.
+/// Panics if rank is not smaller than the number of k-subsets, i.e. binomial(n, k).
+/// # Examples
+/// ```
+/// use labisu::combinatorics::{rank_combination, unrank_combination, binomial};
+/// assert_eq!(vec![0, 1, 3], unrank_combination(1, 5, 3));
+/// for rank in 0..binomial(7, 3) {
+///     assert_eq!(rank, rank_combination(&unrank_combination(rank, 7, 3), 7));
+/// }
+/// assert!(std::panic::catch_unwind(|| unrank_combination(35, 7, 3)).is_err());
+/// ```
+pub fn unrank_combination(rank: usize, n: usize, k: usize) -> Vec<usize> {
+    assert!(rank < binomial(n, k), "The rank is out of range of the k-subsets.");
+    let mut rank = rank;
+    let mut subset = Vec::with_capacity(k);
+    let mut x = 0;
+    for i in 0..k {
+        while binomial(n - x - 1, k - i - 1) <= rank {
+            rank -= binomial(n - x - 1, k - i - 1);
+            x += 1;
+        }
+        subset.push(x);
+        x += 1;
+    }
+    subset
+}
+
+/// Changes the sorted k-subset of {0,1,...,n-1} into its successor in the lexicographic order.
+/// Returns false if the subset was the last one, in which case it is left unchanged.
+/// # Examples
+/// ```
+/// use labisu::combinatorics::next_combination;
+/// let mut subset = vec![0, 3, 4];
+/// assert!(next_combination(&mut subset, 5));
+/// assert_eq!(vec![1, 2, 3], subset);
+/// let mut last = vec![2, 3, 4];
+/// assert!(!next_combination(&mut last, 5));
+/// ```
+pub fn next_combination(subset: &mut [usize], n: usize) -> bool {
+    let k = subset.len();
+    match (0..k).rev().find(|i| subset[*i] < n - k + i) {
+        Some(i) => {
+            subset[i] += 1;
+            for j in i + 1..k {
+                subset[j] = subset[j - 1] + 1;
+            }
+            true
+        },
+        None => false
     }
-    acc_up / acc_down
+}
+
+/// Returns the position of a k-subset of the natural numbers in the colexicographic order of all k-subsets,
+/// in which subsets are compared by their largest elements first. Unlike the lexicographic order it does not depend on n.
+/// The subset has to be given as a sorted vector of its elements.
+/// # Examples
+/// ```
+/// use labisu::combinatorics::rank_combination_colex;
+/// assert_eq!(0, rank_combination_colex(&[0, 1, 2]));
+/// assert_eq!(1, rank_combination_colex(&[0, 1, 3]));
+/// assert_eq!(2, rank_combination_colex(&[0, 2, 3]));
+/// assert_eq!(9, rank_combination_colex(&[2, 3, 4]));
+/// ```
+pub fn rank_combination_colex(subset: &[usize]) -> usize {
+    subset.iter().enumerate().map(|(i, elem)| binomial(*elem, i + 1)).sum()
+}
+
+/// Returns the k-subset of the natural numbers with given position in the colexicographic order.
+/// It is the inverse of rank_combination_colex.
+/// # Examples
+/// ```
+/// use labisu::combinatorics::{rank_combination_colex, unrank_combination_colex};
+/// assert_eq!(vec![0, 2, 3], unrank_combination_colex(2, 3));
+/// for rank in 0..100 {
+///     assert_eq!(rank, rank_combination_colex(&unrank_combination_colex(rank, 4)));
+/// }
+/// ```
+pub fn unrank_combination_colex(rank: usize, k: usize) -> Vec<usize> {
+    let mut rank = rank;
+    let mut subset = vec![0; k];
+    for i in (1..=k).rev() {
+        // the largest x with (x choose i) not greater than the rank
+        let mut x = i - 1;
+        while binomial(x + 1, i) <= rank {
+            x += 1;
+        }
+        rank -= binomial(x, i);
+        subset[i - 1] = x;
+    }
+    subset
+}
+
+/// Changes the sorted k-subset of {0,1,...,n-1} into its successor in the colexicographic order.
+/// Returns false if the subset was the last one, in which case it is left unchanged.
+/// # Examples
+/// ```
+/// use labisu::combinatorics::next_combination_colex;
+/// let mut subset = vec![1, 2, 3];
+/// assert!(next_combination_colex(&mut subset, 5));
+/// assert_eq!(vec![0, 1, 4], subset);
+/// let mut last = vec![2, 3, 4];
+/// assert!(!next_combination_colex(&mut last, 5));
+/// ```
+pub fn next_combination_colex(subset: &mut [usize], n: usize) -> bool {
+    let k = subset.len();
+    let bound = |i: usize, subset: &[usize]| if i + 1 < k { subset[i + 1] } else { n };
+    match (0..k).find(|i| subset[*i] + 1 < bound(*i, subset)) {
+        Some(i) => {
+            subset[i] += 1;
+            for (j, elem) in subset[..i].iter_mut().enumerate() {
+                *elem = j;
+            }
+            true
+        },
+        None => false
+    }
+}
+
+/// Converts the position of a k-subset of {0,1,...,n-1} in the lexicographic order into its position in the colexicographic order.
+/// # Examples
+/// ```
+/// use labisu::combinatorics::{binomial, colex_to_lex_rank, lex_to_colex_rank, rank_combination, rank_combination_colex};
+/// assert_eq!(rank_combination_colex(&[0, 3, 4]), lex_to_colex_rank(rank_combination(&[0, 3, 4], 5), 5, 3));
+/// for rank in 0..binomial(8, 3) {
+///     assert_eq!(rank, colex_to_lex_rank(lex_to_colex_rank(rank, 8, 3), 8, 3));
+/// }
+/// ```
+pub fn lex_to_colex_rank(rank: usize, n: usize, k: usize) -> usize {
+    rank_combination_colex(&unrank_combination(rank, n, k))
+}
+
+/// Converts the position of a k-subset of {0,1,...,n-1} in the colexicographic order into its position in the lexicographic order.
+/// It is the inverse of lex_to_colex_rank.
+pub fn colex_to_lex_rank(rank: usize, n: usize, k: usize) -> usize {
+    rank_combination(&unrank_combination_colex(rank, k), n)
+}
+
+/// An iterator over the k-subsets of {0,1,...,n-1} in the lexicographic order, as sorted vectors.
+/// Unlike GraySubsets, subsets sharing a prefix are consecutive, so a search can skip all extensions of a prefix at once.
+/// # Examples
+/// ```
+/// use labisu::combinatorics::{binomial, rank_combination, LexSubsets};
+/// let subsets = LexSubsets::new(5, 2).collect::<Vec<Vec<usize>>>();
+/// assert_eq!(vec![0, 1], subsets[0]);
+/// assert_eq!(vec![0, 2], subsets[1]);
+/// assert_eq!(vec![3, 4], subsets[9]);
+/// assert_eq!(binomial(5, 2), subsets.len());
+/// assert!(subsets.iter().enumerate().all(|(rank, subset)| rank == rank_combination(subset, 5)));
+/// assert_eq!(1, LexSubsets::new(3, 0).count());
+/// assert_eq!(0, LexSubsets::new(3, 4).count());
+/// ```
+#[derive(Clone, Serialize, Deserialize)]
+pub struct LexSubsets {
+    n: usize,
+    subset: Vec<usize>,
+    done: bool,
+}
+
+impl LexSubsets {
+    /// Creates a new LexSubsets iterator over k-subsets of {0,1,...,n-1}.
+    pub fn new(n: usize, k: usize) -> LexSubsets {
+        LexSubsets { n, subset: (0..k).collect(), done: k > n }
+    }
+}
+
+impl Iterator for LexSubsets {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None
+        }
+        let current = self.subset.clone();
+        self.done = !next_combination(&mut self.subset, self.n);
+        Some(current)
+    }
+}
+
+/// An iterator over the k-subsets of {0,1,...,n-1} in the colexicographic order, as sorted vectors.
+/// The subsets of {0,1,...,m-1} come before all the others, so the order of the subsets of a smaller set is a prefix of it.
+/// # Examples
+/// ```
+/// use labisu::combinatorics::{rank_combination_colex, ColexSubsets};
+/// let subsets = ColexSubsets::new(5, 2).collect::<Vec<Vec<usize>>>();
+/// assert_eq!(vec![0, 1], subsets[0]);
+/// assert_eq!(vec![0, 2], subsets[1]);
+/// assert_eq!(vec![1, 2], subsets[2]);
+/// assert_eq!(vec![3, 4], subsets[9]);
+/// assert!(subsets.iter().enumerate().all(|(rank, subset)| rank == rank_combination_colex(subset)));
+/// assert_eq!(ColexSubsets::new(4, 2).collect::<Vec<Vec<usize>>>(), subsets[..6]);
+/// ```
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ColexSubsets {
+    n: usize,
+    subset: Vec<usize>,
+    done: bool,
+}
+
+impl ColexSubsets {
+    /// Creates a new ColexSubsets iterator over k-subsets of {0,1,...,n-1}.
+    pub fn new(n: usize, k: usize) -> ColexSubsets {
+        ColexSubsets { n, subset: (0..k).collect(), done: k > n }
+    }
+}
+
+impl Iterator for ColexSubsets {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None
+        }
+        let current = self.subset.clone();
+        self.done = !next_combination_colex(&mut self.subset, self.n);
+        Some(current)
+    }
+}
+
+/// Checks whether a sequence of degrees is graphical, i.e. it is the degree sequence of a simple graph, with the Erdős–Gallai theorem.
+/// The degrees may be given in any order.
+/// # Examples
+/// ```
+/// use labisu::combinatorics::is_graphical;
+/// assert!(is_graphical(&[3, 3, 3, 3]));
+/// assert!(is_graphical(&[1, 2, 1]));
+/// assert!(is_graphical(&[]));
+/// assert!(!is_graphical(&[3, 3, 1, 1]));
+/// assert!(!is_graphical(&[2, 2, 1]));
+/// assert!(!is_graphical(&[4, 1, 1, 1]));
+/// ```
+pub fn is_graphical(degrees: &[usize]) -> bool {
+    let mut sorted = degrees.to_vec();
+    sorted.sort_unstable_by(|a, b| b.cmp(a));
+    let n = sorted.len();
+    let mut prefix = vec![0; n + 1];
+    for i in 0..n {
+        prefix[i + 1] = prefix[i] + sorted[i];
+    }
+    if !prefix[n].is_multiple_of(2) {
+        return false
+    }
+    for k in 1..=n {
+        // the degrees after the k-th one which are at least k are the ones before position p
+        let p = sorted.partition_point(|degree| *degree >= k).max(k);
+        let right = (p - k) * k + prefix[n] - prefix[p];
+        if prefix[k] > k * (k - 1) + right {
+            return false
+        }
+    }
+    true
 }
 
 /// A struct for generation of Gray codes required to keep 
 /// the data necessary for the algorithm.
+#[derive(Clone, Serialize, Deserialize)]
 pub struct GraySubsets {
     n: usize,
     t: usize,
@@ -112,3 +387,173 @@ impl Iterator for GraySubsets {
         None
     }
 }
+
+/// An unsigned integer used as a set of at most BITS elements, the element i being the bit i.
+pub trait Bitmask: Copy + Eq {
+    /// The largest number of elements of a set.
+    const BITS: usize;
+
+    /// Returns the set {0,1,...,k-1}.
+    fn lowest(k: usize) -> Self;
+
+    /// Returns the set with the element i added or removed.
+    fn toggled(self, i: usize) -> Self;
+}
+
+macro_rules! impl_bitmask {
+    ($($t:ty),*) => {$(
+        impl Bitmask for $t {
+            const BITS: usize = <$t>::BITS as usize;
+
+            fn lowest(k: usize) -> $t {
+                if k >= <$t as Bitmask>::BITS { <$t>::MAX } else { (1 << k) - 1 }
+            }
+
+            fn toggled(self, i: usize) -> $t {
+                self ^ (1 << i)
+            }
+        }
+    )*};
+}
+
+impl_bitmask!(u64, u128);
+
+/// An adaptor of GraySubsets which keeps the current k-subset of {0,1,...,n-1} as a bitmask of type u64 or u128,
+/// so that users can test it with bit operations instead of keeping a vector of zeros and ones.
+/// The subsets are in the same order as the ones of GraySubsets.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct GraySubsetsBitmask<M: Bitmask = u128> {
+    gray: GraySubsets,
+    mask: M,
+}
+
+impl<M: Bitmask> GraySubsetsBitmask<M> {
+    /// Creates a new GraySubsetsBitmask iterator over k-subsets of {0,1,...,n-1}.
+    /// Returns an Err value if n is larger than the number of bits of the mask, i.e. 64 for u64 and 128 for u128.
+    pub fn new(n: usize, k: usize) -> Result<GraySubsetsBitmask<M>, &'static str> {
+        if n > M::BITS {
+            return Err("The set has more elements than the mask has bits.")
+        }
+        Ok(GraySubsetsBitmask { gray: GraySubsets::new(n, k), mask: M::lowest(k.min(n)) })
+    }
+
+    /// Returns the initial subset, the mask of {0,1,...,k-1}.
+    pub fn init(&self) -> M {
+        M::lowest(self.gray.t.min(self.gray.n))
+    }
+}
+
+impl<M: Bitmask> Iterator for GraySubsetsBitmask<M> {
+    type Item = (M, usize, usize);
+
+    /// Returns the next subset after the initial one together with the transition of GraySubsets leading to it,
+    /// i.e. the element removed from the previous subset and the element added to it.
+    /// # Examples
+    /// ```
+    /// use labisu::combinatorics::{binomial, GraySubsets, GraySubsetsBitmask};
+    /// let mut gray = GraySubsetsBitmask::<u64>::new(10, 4).unwrap();
+    /// let mut previous = gray.init();
+    /// assert_eq!(0b1111, previous);
+    /// let mut count = 1;
+    /// for ((mask, removed, added), transition) in gray.by_ref().zip(GraySubsets::new(10, 4)) {
+    ///     assert_eq!((removed, added), transition);
+    ///     assert_eq!(previous ^ mask, (1 << removed) | (1 << added));
+    ///     assert_eq!(4, mask.count_ones());
+    ///     previous = mask;
+    ///     count += 1;
+    /// }
+    /// assert_eq!(binomial(10, 4), count);
+    /// let large = GraySubsetsBitmask::<u128>::new(100, 99).unwrap();
+    /// assert_eq!(99, large.count());
+    /// assert!(GraySubsetsBitmask::<u64>::new(65, 2).is_err());
+    /// ```
+    fn next(&mut self) -> Option<Self::Item> {
+        let (removed, added) = self.gray.next()?;
+        self.mask = self.mask.toggled(removed).toggled(added);
+        Some((self.mask, removed, added))
+    }
+}
+
+/// A sampler of indices 0,1,...,n-1 with probabilities proportional to given weights, with Walker's alias method.
+/// The table is built in linear time with Vose's algorithm, after which every sample takes constant time,
+/// e.g. vertices proportionally to their degrees (see graphs::sample_by_degree).
+/// The weights are fixed, so a distribution which changes after every sample needs a new sampler.
+/// # Examples
+/// ```
+/// use labisu::combinatorics::WeightedSampler;
+/// let sampler = WeightedSampler::new(&[1.0, 0.0, 3.0, 4.0]).unwrap();
+/// assert!((sampler.probability(0) - 0.125).abs() < 1e-12);
+/// assert_eq!(0.0, sampler.probability(1));
+/// assert!((sampler.probability(3) - 0.5).abs() < 1e-12);
+/// // a uniform grid of numbers is spread over the indices according to the probabilities
+/// let grid = (0..1000).map(|i| sampler.sample_from_uniform((i as f64 + 0.5) / 1000.0)).collect::<Vec<usize>>();
+/// assert_eq!(500, grid.iter().filter(|i| **i == 3).count());
+/// assert!(!grid.contains(&1));
+/// #[cfg(feature = "std")]
+/// {
+///     use rand::{rngs::StdRng, SeedableRng};
+///     let mut rng = StdRng::seed_from_u64(42);
+///     let count = (0..8000).filter(|_| sampler.sample(&mut rng) == 2).count();
+///     assert!(2500 < count && count < 3500);
+/// }
+/// assert!(WeightedSampler::new(&[]).is_err());
+/// assert!(WeightedSampler::new(&[1.0, -1.0]).is_err());
+/// assert!(WeightedSampler::new(&[0.0, 0.0]).is_err());
+/// ```
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WeightedSampler {
+    probabilities: Vec<f64>,
+    aliases: Vec<usize>,
+}
+
+impl WeightedSampler {
+    /// Creates a sampler for given weights.
+    /// If there are no weights, a weight is negative or not finite, or all of them are 0, then it returns an Err value.
+    pub fn new(weights: &[f64]) -> Result<WeightedSampler, &'static str> {
+        if weights.iter().any(|weight| !weight.is_finite() || *weight < 0.0) {
+            return Err("The weights have to be finite and non-negative.")
+        }
+        let total = weights.iter().sum::<f64>();
+        if !(total > 0.0 && total.is_finite()) {
+            return Err("The weights have to have a positive finite sum.")
+        }
+        let n = weights.len();
+        let mut probabilities = weights.iter().map(|weight| weight / total * n as f64).collect::<Vec<f64>>();
+        let mut aliases = (0..n).collect::<Vec<usize>>();
+        let (mut small, mut large): (Vec<usize>, Vec<usize>) = (0..n).partition(|i| probabilities[*i] < 1.0);
+        while let (Some(s), Some(l)) = (small.last().copied(), large.last().copied()) {
+            small.pop();
+            aliases[s] = l;
+            probabilities[l] -= 1.0 - probabilities[s];
+            if probabilities[l] < 1.0 {
+                large.pop();
+                small.push(l);
+            }
+        }
+        // what is left is 1 up to rounding errors
+        small.into_iter().chain(large).for_each(|i| probabilities[i] = 1.0);
+        Ok(WeightedSampler { probabilities, aliases })
+    }
+
+    /// Returns the probability of sampling given index, as represented by the table. Panics if the index is out of range.
+    pub fn probability(&self, i: usize) -> f64 {
+        let n = self.probabilities.len();
+        let aliased = (0..n).filter(|j| *j != i && self.aliases[*j] == i).map(|j| 1.0 - self.probabilities[j]).sum::<f64>();
+        (self.probabilities[i] + aliased) / n as f64
+    }
+
+    /// Returns the index sampled with a number drawn uniformly from [0, 1): its integer part after scaling by n picks a column of the table,
+    /// and its fractional part decides between the column and its alias. It lets callers bring their own source of randomness.
+    pub fn sample_from_uniform(&self, u: f64) -> usize {
+        let n = self.probabilities.len();
+        let scaled = u * n as f64;
+        let i = (scaled as usize).min(n - 1);
+        if scaled - (i as f64) < self.probabilities[i] { i } else { self.aliases[i] }
+    }
+
+    /// Returns an index sampled with given random number generator.
+    #[cfg(feature = "std")]
+    pub fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        self.sample_from_uniform(rng.gen::<f64>())
+    }
+}
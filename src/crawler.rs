@@ -1,8 +1,402 @@
-use std::collections::{VecDeque};
-use std::sync::{Arc, RwLock, mpsc};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex, RwLock, mpsc};
+use std::time::{Duration, Instant};
 
-use super::graphs::Graph;
-use super::scraper::Scraper;
+use super::atomic::AtomicFile;
+use super::evolving::EvolvingGraph;
+use super::graphs::{DegreeSketch, EdgeLog, Graph, GraphOps, GraphSnapshot, TopDegrees};
+use super::dns::DnsCache;
+use super::filter::{self, LinkFilter};
+use super::normalize::{default_normalizer, UrlNormalizer};
+use super::pipeline::CrawlPipeline;
+use super::robots::RobotsCache;
+use super::scraper::{DropStats, FetchError, LinkClass, Page, Scraper, DEFAULT_MAX_BODY_SIZE};
+use super::warc::WarcWriter;
+use url::Url;
+
+/// A closure analyzing the graph while it is being crawled, given also the current hubs.
+type Analysis = Box<dyn FnMut(&Graph, &[usize]) + Send>;
+
+/// A closure given snapshots of the graph while it is being crawled.
+type SnapshotAnalysis = Box<dyn FnMut(GraphSnapshot) + Send>;
+
+/// A closure watching the degree distribution while the graph is being crawled.
+type DegreeAnalysis = Box<dyn FnMut(&DegreeSketch) + Send>;
+
+/// Everything recorded during a single crawl.
+struct CrawlRecord {
+    graph: Graph,
+    vertex_rounds: Vec<usize>,
+    edges: Vec<EdgeRecord>, // in the order of discovery
+    content_hashes: Vec<Option<u64>>, // of the bodies of successfully fetched pages
+    layers: Vec<LayerStats>, // by depth
+    frontier: Frontier,
+    write_errors: Vec<String>, // of the files written during the crawl
+}
+
+/// The graph a crawl starts with.
+struct CrawlStart {
+    graph: Graph,
+    queue: VecDeque<(usize, usize)>, // (depth, node_id) of the pages to scan
+    layers: Vec<LayerStats>, // of the vertices of the graph
+    edges: Vec<EdgeRecord>,
+}
+
+/// An edge added during a crawl, with where and when it was found.
+struct EdgeRecord {
+    from: usize,
+    to: usize,
+    round: usize,
+    class: LinkClass,
+    depth: usize, // of the page the edge was found on
+    fetched_after: Duration, // since the start of the crawl
+}
+
+/// Statistics of a single depth level of a crawl.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct LayerStats {
+    /// The number of pages scanned at the depth.
+    pub pages: usize,
+    /// The number of vertices found at the depth, i.e. first linked from a page at the previous depth.
+    pub new_vertices: usize,
+    /// The number of edges added while scanning the pages at the depth.
+    pub new_edges: usize,
+    /// The number of accepted links on the pages at the depth, including the ones leading to known vertices.
+    pub out_links: usize,
+}
+
+impl LayerStats {
+    /// Returns the average number of accepted links on a page at the depth, or 0 if no page was scanned.
+    pub fn average_out_degree(&self) -> f64 {
+        match self.pages {
+            0 => 0.0,
+            pages => self.out_links as f64 / pages as f64,
+        }
+    }
+}
+
+/// The result of a crawl together with the statistics describing how it went.
+#[derive(Clone)]
+pub struct CrawlReport {
+    /// The crawled graph, after the pipeline of the crawler if it has one.
+    pub graph: Graph,
+    /// The statistics of every depth level, starting with the root at depth 0.
+    pub layers: Vec<LayerStats>,
+    /// The statistics of the requests sent to every host.
+    pub host_stats: HashMap<String, HostStats>,
+    /// The numbers of dropped links by reason.
+    pub drop_stats: DropStats,
+    /// The pages found beyond the largest depth, where the next stage of the crawl can start (see Crawler::with_frontier).
+    pub frontier: Frontier,
+    /// The errors of writing the files of the crawl: of the edge log, after which the crawl went on without writing it, and of the sinks.
+    pub write_errors: Vec<String>,
+    provenance: Vec<EdgeProvenance>,
+    provenance_index: HashMap<String, HashMap<String, usize>>, // the position in provenance of every edge by the smaller and the larger name of its ends
+}
+
+impl CrawlReport {
+    /// Returns the origin of every crawled edge in the order of discovery, including the edges removed by the pipeline.
+    pub fn provenance(&self) -> &[EdgeProvenance] {
+        &self.provenance
+    }
+
+    /// Returns where and when the edge between the pages with given urls was discovered, in either direction, if it was crawled.
+    /// ```
+    /// use labisu::crawler::Crawler;
+    /// let report = Crawler::new("https://pwr.edu.pl/".to_owned(), 1, vec!["pwr.edu".to_owned()], vec![]).crawl_report();
+    /// assert_eq!(report.graph.get_num_of_edges(), report.provenance().len());
+    /// for edge in report.provenance() {
+    ///     assert_eq!("https://pwr.edu.pl/", edge.source);
+    ///     assert_eq!((0, 1), (edge.depth, edge.round));
+    ///     assert_eq!(Some(edge), report.edge_provenance(&edge.target, &edge.source));
+    /// }
+    /// ```
+    pub fn edge_provenance(&self, from: &str, to: &str) -> Option<&EdgeProvenance> {
+        let i = self.provenance_index.get(from.min(to))?.get(from.max(to))?;
+        Some(&self.provenance[*i])
+    }
+
+    /// Writes the statistics and the frontier of the report, i.e. everything except the graph, to a json file with given filename.
+    /// ```
+    /// use labisu::crawler::Crawler;
+    /// let report = Crawler::new("https://pwr.edu.pl/".to_owned(), 0, vec![], vec![]).crawl_report();
+    /// report.write_to_json("report.json").unwrap();
+    /// let contents = std::fs::read_to_string("report.json").unwrap();
+    /// assert!(contents.contains("\"layers\""));
+    /// ```
+    pub fn write_to_json(&self, filename: &str) -> serde_json::Result<()> {
+        let report = serde_json::json!({
+            "layers": self.layers,
+            "host_stats": self.host_stats,
+            "drop_stats": self.drop_stats,
+            "frontier": self.frontier,
+            "provenance": self.provenance,
+        });
+        let mut file = AtomicFile::create(filename).map_err(serde_json::Error::io)?;
+        serde_json::to_writer_pretty(&mut file, &report)?;
+        file.commit().map_err(serde_json::Error::io)
+    }
+}
+
+/// Where and when an edge of a crawled graph was discovered, to trace the origin of unexpected edges.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EdgeProvenance {
+    /// The url of the page whose fetch produced the edge, i.e. the page the link was found on.
+    pub source: String,
+    /// The url of the linked page.
+    pub target: String,
+    /// The depth of the source page.
+    pub depth: usize,
+    /// The round of scraping in which the edge was found, or 0 for an edge of the frontier the crawl started with.
+    pub round: usize,
+    /// The time from the start of the crawl to the fetch of the source page, or zero for an edge of the frontier.
+    pub fetched_after: Duration,
+    /// The part of the source page the link was found in.
+    pub class: LinkClass,
+}
+
+/// The estimate of a crawl made by Crawler::dry_run, without downloading the bodies of any pages.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DryRunReport {
+    /// The numbers of pages which would be fetched at every depth, starting with the root at depth 0.
+    pub pages_per_depth: Vec<usize>,
+    /// The numbers of pages which would be fetched from every host (as given by the url).
+    pub pages_per_host: BTreeMap<String, usize>,
+    /// The number of pages which would not be fetched, because robots.txt of their hosts disallows them.
+    pub disallowed: usize,
+    /// The number of links of the cached graph rejected by the filter of the crawler.
+    pub filtered: usize,
+    /// The number of pages whose HEAD requests failed or returned an error status (400 or higher).
+    pub unreachable: usize,
+    /// The sum of the sizes of the bodies stated by the HEAD responses with a Content-Length header.
+    pub known_bytes: u64,
+}
+
+impl DryRunReport {
+    /// Returns the number of pages which would be fetched.
+    pub fn total(&self) -> usize {
+        self.pages_per_depth.iter().sum()
+    }
+
+    /// Writes the report to a json file with given filename.
+    pub fn write_to_json(&self, filename: &str) -> serde_json::Result<()> {
+        let mut file = AtomicFile::create(filename).map_err(serde_json::Error::io)?;
+        serde_json::to_writer_pretty(&mut file, self)?;
+        file.commit().map_err(serde_json::Error::io)
+    }
+}
+
+/// A page waiting to be scanned by the crawler.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FrontierEntry {
+    /// The url of the page.
+    pub url: String,
+    /// The depth of the page, i.e. its distance from the root of the crawl which found it.
+    pub depth: usize,
+    /// The url of the page on which the link to the page was found, or None for a page added by hand.
+    pub discovered_from: Option<String>,
+}
+
+/// The pages waiting to be scanned, which can be exported from a crawl, edited or scheduled elsewhere, and injected into another crawler.
+/// # Examples
+/// ```
+/// use labisu::crawler::{Frontier, FrontierEntry};
+/// let mut frontier = Frontier::new();
+/// frontier.entries.push(FrontierEntry { url: "https://pwr.edu.pl/".to_owned(), depth: 0, discovered_from: None });
+/// frontier.write_to_json("frontier.json").unwrap();
+/// assert_eq!(frontier, Frontier::read_from_json("frontier.json").unwrap());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Frontier {
+    /// The pages, in the order of discovery.
+    pub entries: Vec<FrontierEntry>,
+}
+
+impl Frontier {
+    /// Creates an empty frontier.
+    pub fn new() -> Frontier {
+        Frontier::default()
+    }
+
+    /// Creates a frontier of the pages with given urls added by hand at depth 0, e.g. to estimate a crawl of a list of urls with Crawler::dry_run.
+    pub fn from_urls(urls: &[String]) -> Frontier {
+        let entries = urls.iter().map(|url| FrontierEntry { url: url.clone(), depth: 0, discovered_from: None }).collect();
+        Frontier { entries }
+    }
+
+    /// Writes the frontier to a json file with given filename.
+    pub fn write_to_json(&self, filename: &str) -> serde_json::Result<()> {
+        let mut file = AtomicFile::create(filename).map_err(serde_json::Error::io)?;
+        serde_json::to_writer_pretty(&mut file, self)?;
+        file.commit().map_err(serde_json::Error::io)
+    }
+
+    /// Reads a frontier from a json file with given filename.
+    pub fn read_from_json(filename: &str) -> serde_json::Result<Frontier> {
+        let file = std::fs::File::open(filename).map_err(serde_json::Error::io)?;
+        serde_json::from_reader(std::io::BufReader::new(file))
+    }
+}
+
+/// An output to which the crawler writes the result of every crawl, so that one run produces all formats needed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Sink {
+    /// The graph in json, written with Graph::write_to_json to the file with given filename.
+    Json(String),
+    /// The graph as a csv edge list of names, written with Graph::write_to_csv_edge_list to the file with given filename.
+    CsvEdgeList(String),
+    /// The statistics of the crawl, written with CrawlReport::write_to_json to the file with given filename.
+    Report(String),
+}
+
+/// Statistics of the requests sent to a single host during a crawl.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct HostStats {
+    /// The number of requests sent to the host.
+    pub requests: usize,
+    /// The number of requests which failed for any reason, including an error status.
+    pub failures: usize,
+    /// The total time of the requests.
+    pub total_latency: Duration,
+    /// The number of failures caused by an unresolvable host name.
+    pub resolution_failures: usize,
+    /// The number of responses with an error status (400 or higher).
+    pub http_errors: usize,
+    /// Whether the host exceeded the error budget, so that its pages were not fetched anymore.
+    pub blacklisted: bool,
+}
+
+impl HostStats {
+    /// Returns the mean time of a request, or zero if there were no requests.
+    pub fn mean_latency(&self) -> Duration {
+        match self.requests {
+            0 => Duration::ZERO,
+            requests => self.total_latency / requests as u32,
+        }
+    }
+
+    /// Records a request and blacklists the host if its failures exceed the error budget.
+    fn record(&mut self, latency: Duration, result: &Result<Page, FetchError>, error_budget: Option<usize>) {
+        self.requests += 1;
+        self.total_latency += latency;
+        let failed = match result {
+            Ok(page) if page.status >= 400 => { self.http_errors += 1; true },
+            Ok(_) => false,
+            Err(FetchError::Resolution) => { self.resolution_failures += 1; true },
+            Err(_) => true,
+        };
+        if failed {
+            self.failures += 1;
+        }
+        if error_budget.is_some_and(|budget| self.failures > budget) {
+            self.blacklisted = true;
+        }
+    }
+}
+
+/// A preset of the settings deciding how hard the crawler hits the hosts, to be selected with Crawler::with_politeness.
+/// # Examples
+/// ```
+/// use labisu::crawler::Politeness;
+/// assert_eq!(Ok(Politeness::Respectful), "respectful".parse());
+/// assert!("rude".parse::<Politeness>().is_err());
+/// assert!(Politeness::Respectful.respects_robots());
+/// assert!(!Politeness::Aggressive.respects_robots());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Politeness {
+    /// A scraper on every physical core, no delays or retries, robots.txt ignored and no error budget, i.e. the settings of Crawler::new.
+    Aggressive,
+    /// At most 4 concurrent scrapers, 250 ms between requests to a host, 1 retry, robots.txt obeyed and an error budget of 20 failures per host.
+    #[default]
+    Default,
+    /// A single scraper, 1 s between requests to a host (or more if robots.txt asks for it), 2 retries and an error budget of 5 failures per host.
+    Respectful,
+}
+
+impl Politeness {
+    /// Returns the largest number of concurrent scrapers, or None for one per physical core.
+    pub fn max_threads(&self) -> Option<usize> {
+        match self {
+            Politeness::Aggressive => None,
+            Politeness::Default => Some(4),
+            Politeness::Respectful => Some(1),
+        }
+    }
+
+    /// Returns the smallest time between the starts of requests to the same host.
+    pub fn host_delay(&self) -> Duration {
+        match self {
+            Politeness::Aggressive => Duration::ZERO,
+            Politeness::Default => Duration::from_millis(250),
+            Politeness::Respectful => Duration::from_secs(1),
+        }
+    }
+
+    /// Returns the number of times a failed request is repeated.
+    pub fn retries(&self) -> usize {
+        match self {
+            Politeness::Aggressive => 0,
+            Politeness::Default => 1,
+            Politeness::Respectful => 2,
+        }
+    }
+
+    /// Checks if the rules of robots.txt are obeyed.
+    pub fn respects_robots(&self) -> bool {
+        *self != Politeness::Aggressive
+    }
+
+    /// Returns the number of failed requests allowed per host (see Crawler::with_error_budget), or None for no limit.
+    pub fn error_budget(&self) -> Option<usize> {
+        match self {
+            Politeness::Aggressive => None,
+            Politeness::Default => Some(20),
+            Politeness::Respectful => Some(5),
+        }
+    }
+}
+
+impl std::str::FromStr for Politeness {
+    type Err = &'static str;
+
+    /// Parses the name of a preset in lowercase, e.g. given on the command line.
+    fn from_str(name: &str) -> Result<Politeness, &'static str> {
+        match name {
+            "aggressive" => Ok(Politeness::Aggressive),
+            "default" => Ok(Politeness::Default),
+            "respectful" => Ok(Politeness::Respectful),
+            _ => Err("The politeness has to be one of aggressive, default and respectful."),
+        }
+    }
+}
+
+/// The times before which no request to a host may start, shared by the scrapers to keep the delay between requests to a host.
+#[derive(Default)]
+struct HostGate {
+    next: Mutex<HashMap<String, Instant>>,
+}
+
+impl HostGate {
+    /// Reserves the next slot of the host and blocks the calling thread until it starts.
+    fn wait(&self, host: &str, delay: Duration) {
+        if delay.is_zero() {
+            return
+        }
+        let start = {
+            let mut next = self.next.lock().unwrap();
+            let now = Instant::now();
+            let start = next.get(host).map_or(now, |next| (*next).max(now));
+            next.insert(host.to_owned(), start + delay);
+            start
+        };
+        std::thread::sleep(start.saturating_duration_since(Instant::now()));
+    }
+}
 
 enum Index {
     StrIndex(String),
@@ -13,8 +407,33 @@ enum Index {
 pub struct Crawler {
     root: String,
     max_depth: usize,
-    must_contain: Vec<String>,
-    stop_words: Vec<String>,
+    filter: Arc<dyn LinkFilter>,
+    normalizer: Arc<dyn UrlNormalizer>,
+    analysis: Option<(Duration, Mutex<Analysis>)>,
+    snapshot_analysis: Option<(Duration, Mutex<SnapshotAnalysis>)>,
+    degree_analysis: Option<(Duration, Mutex<DegreeAnalysis>)>,
+    num_of_hubs: usize,
+    edge_log: Option<String>,
+    warc: Option<String>,
+    #[cfg(feature = "headless")]
+    browser: Option<String>,
+    #[cfg(feature = "headless")]
+    browser_sandbox: bool,
+    error_budget: Option<usize>,
+    max_threads: Option<usize>,
+    host_delay: Duration,
+    retries: usize,
+    respect_robots: bool,
+    host_overrides: Vec<(String, IpAddr)>,
+    accept_invalid_certs: bool,
+    root_certificates: Option<String>,
+    max_body_size: usize,
+    content_links_only: bool,
+    pipeline: Option<CrawlPipeline>,
+    sinks: Vec<Sink>,
+    frontier: Option<Frontier>,
+    host_stats: Mutex<HashMap<String, HostStats>>, // of the last crawl
+    drop_stats: Mutex<DropStats>, // of the last crawl
 }
 
 impl Crawler {
@@ -27,9 +446,413 @@ impl Crawler {
         Crawler {
             root,
             max_depth,
-            must_contain,
-            stop_words,
+            filter: Arc::new(filter::words(&must_contain, &stop_words)),
+            normalizer: Arc::new(default_normalizer()),
+            analysis: None,
+            snapshot_analysis: None,
+            degree_analysis: None,
+            num_of_hubs: 0,
+            edge_log: None,
+            warc: None,
+            #[cfg(feature = "headless")]
+            browser: None,
+            #[cfg(feature = "headless")]
+            browser_sandbox: true,
+            error_budget: None,
+            max_threads: None,
+            host_delay: Duration::ZERO,
+            retries: 0,
+            respect_robots: false,
+            host_overrides: vec![],
+            accept_invalid_certs: false,
+            root_certificates: None,
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+            content_links_only: false,
+            pipeline: None,
+            sinks: vec![],
+            frontier: None,
+            host_stats: Mutex::new(HashMap::new()),
+            drop_stats: Mutex::new(DropStats::default()),
+        }
+    }
+
+    /// Sets a file to which the crawler writes an edge log (see graphs::EdgeLog) during the crawl.
+    /// The log is flushed after each round of scraping, so it always holds the graph crawled so far
+    /// and can be folded into a Graph with Graph::read_from_edge_log, even if the crawl was interrupted.
+    /// If writing the log fails, the crawl goes on without it and the error is returned in CrawlReport::write_errors.
+    /// ```
+    /// use labisu::crawler::Crawler;
+    /// use labisu::graphs::Graph;
+    /// let crawler = Crawler::new("https://pwr.edu.pl/".to_owned(), 0, vec![], vec![]).with_edge_log("crawl.ndjson");
+    /// let links = crawler.crawl();
+    /// let read = Graph::read_from_edge_log("crawl.ndjson").unwrap();
+    /// assert_eq!(links.get_num_of_edges(), read.get_num_of_edges());
+    ///
+    /// let report = Crawler::new("https://pwr.edu.pl/".to_owned(), 0, vec![], vec![]).with_edge_log("missing/crawl.ndjson").crawl_report();
+    /// assert_eq!(1, report.write_errors.len());
+    /// ```
+    pub fn with_edge_log(mut self, filename: &str) -> Crawler {
+        self.edge_log = Some(filename.to_owned());
+        self
+    }
+
+    /// Sets a file to which the crawler archives all fetched pages in the WARC format (see warc::WarcWriter),
+    /// so that they can be processed again later without fetching, e.g. with warc::read_warc.
+    /// The archive is flushed after each round of scraping.
+    /// If writing the archive fails, the crawl goes on without it and the error is returned in CrawlReport::write_errors.
+    /// ```
+    /// use labisu::crawler::Crawler;
+    /// use labisu::warc::read_warc;
+    /// let crawler = Crawler::new("https://pwr.edu.pl/".to_owned(), 0, vec![], vec![]).with_warc("crawl.warc");
+    /// crawler.crawl();
+    /// assert!(read_warc("crawl.warc").unwrap().len() <= 1);
+    ///
+    /// let report = Crawler::new("https://pwr.edu.pl/".to_owned(), 0, vec![], vec![]).with_warc("missing/crawl.warc").crawl_report();
+    /// assert_eq!(1, report.write_errors.len());
+    /// ```
+    pub fn with_warc(mut self, filename: &str) -> Crawler {
+        self.warc = Some(filename.to_owned());
+        self
+    }
+
+    /// Makes the crawler render pages with a headless Chromium given by the path to its binary (see Scraper::with_browser),
+    /// so that pages of single-page applications, whose links are injected by JavaScript, are not isolated vertices.
+    #[cfg(feature = "headless")]
+    pub fn with_browser(mut self, binary: &str) -> Crawler {
+        self.browser = Some(binary.to_owned());
+        self
+    }
+
+    /// Sets whether the headless browser runs in its sandbox, which it does by default (see Scraper::with_browser_sandbox).
+    #[cfg(feature = "headless")]
+    pub fn with_browser_sandbox(mut self, sandbox: bool) -> Crawler {
+        self.browser_sandbox = sandbox;
+        self
+    }
+
+    /// Sets the number of failed requests allowed per host. A host with more failures is blacklisted,
+    /// i.e. its pages found later are added to the graph, but they are not fetched, so a long crawl does not waste time on dead domains.
+    /// ```
+    /// use labisu::crawler::Crawler;
+    /// let crawler = Crawler::new("https://pwr.edu.pl/".to_owned(), 1, vec![], vec![]).with_error_budget(5);
+    /// crawler.crawl();
+    /// assert!(crawler.host_stats().values().all(|stats| stats.failures <= 6));
+    /// ```
+    pub fn with_error_budget(mut self, max_failures: usize) -> Crawler {
+        self.error_budget = Some(max_failures);
+        self
+    }
+
+    /// Sets the largest number of concurrent scrapers. By default there is one on every physical core.
+    pub fn with_max_threads(mut self, max_threads: usize) -> Crawler {
+        self.max_threads = Some(max_threads.max(1));
+        self
+    }
+
+    /// Sets the smallest time between the starts of requests to the same host, so that the crawler does not overload it.
+    pub fn with_host_delay(mut self, delay: Duration) -> Crawler {
+        self.host_delay = delay;
+        self
+    }
+
+    /// Sets the number of times a request is repeated if it fails or returns a server error (500 or higher).
+    /// Requests to hosts which cannot be resolved are not repeated. Every attempt is counted in the statistics of the host.
+    pub fn with_retries(mut self, retries: usize) -> Crawler {
+        self.retries = retries;
+        self
+    }
+
+    /// Makes the crawler obey the rules of robots.txt of every host (see robots::Robots), fetched once per crawl.
+    /// Disallowed pages are added to the graph, but they are not fetched, like pages of blacklisted hosts.
+    /// The delay between requests to a host is raised to its Crawl-delay if it is longer.
+    /// The pages of a host whose robots.txt is unreachable are not fetched (see robots::RobotsCache::get).
+    pub fn with_robots(mut self, respect_robots: bool) -> Crawler {
+        self.respect_robots = respect_robots;
+        self
+    }
+
+    /// Sets the concurrency, the delay between requests to a host, the retries, obeying robots.txt and the error budget
+    /// at once from a preset. Any of them can still be changed afterwards with its own method.
+    /// ```
+    /// use labisu::crawler::{Crawler, Politeness};
+    /// let crawler = 
+    ///     Crawler::new("https://pwr.edu.pl/".to_owned(), 1, vec!["pwr.edu".to_owned()], vec![])
+    ///     .with_politeness(Politeness::Default)
+    ///     .with_max_threads(8);
+    /// let links = crawler.crawl();
+    /// assert!(links.get_num_of_vertices() >= 1);
+    /// assert!(crawler.host_stats().values().all(|stats| stats.failures <= 21));
+    /// ```
+    pub fn with_politeness(mut self, politeness: Politeness) -> Crawler {
+        self.max_threads = politeness.max_threads();
+        self.host_delay = politeness.host_delay();
+        self.retries = politeness.retries();
+        self.respect_robots = politeness.respects_robots();
+        self.error_budget = politeness.error_budget();
+        self
+    }
+
+    /// Sets static addresses of hosts, which are used instead of resolving their names (see Scraper::with_host_overrides).
+    /// ```
+    /// use labisu::crawler::Crawler;
+    /// use labisu::dns::read_hosts_file;
+    /// std::fs::write("mirror_hosts", "127.0.0.1 mirror.local\n").unwrap();
+    /// let crawler = 
+    ///     Crawler::new("http://mirror.local:9/".to_owned(), 0, vec![], vec![])
+    ///     .with_host_overrides(read_hosts_file("mirror_hosts").unwrap());
+    /// crawler.crawl();
+    /// assert_eq!(0, crawler.host_stats()["mirror.local"].resolution_failures);
+    /// ```
+    pub fn with_host_overrides(mut self, overrides: Vec<(String, IpAddr)>) -> Crawler {
+        self.host_overrides = overrides;
+        self
+    }
+
+    /// Sets the limit of the number of bytes of a body read by the scrapers (see Scraper::with_max_body_size).
+    pub fn with_max_body_size(mut self, max_body_size: usize) -> Crawler {
+        self.max_body_size = max_body_size;
+        self
+    }
+
+    /// Replaces the filter of the words given to new with given link filter, which is shared by all scrapers of the crawler.
+    /// ```
+    /// use labisu::crawler::Crawler;
+    /// use labisu::filter::{Domain, Extension, LinkFilter};
+    /// use std::sync::Arc;
+    /// let crawler = 
+    ///     Crawler::new("https://pwr.edu.pl/".to_owned(), 1, vec![], vec![])
+    ///     .with_filter(Arc::new(Domain::new("pwr.edu.pl").and(Extension::new(&["pdf", "jpg", "png"]).not())));
+    /// let links = crawler.crawl();
+    /// assert!(links.vertices().all(|idx| links.idx_to_name(idx).unwrap().contains("pwr.edu.pl")));
+    /// ```
+    pub fn with_filter(mut self, filter: Arc<dyn LinkFilter>) -> Crawler {
+        self.filter = filter;
+        self
+    }
+
+    /// Replaces the default normalizer of links with given one, which is shared by all scrapers of the crawler (see Scraper::with_normalizer).
+    /// ```
+    /// use labisu::crawler::Crawler;
+    /// use labisu::normalize::{default_normalizer, StripParams, UrlNormalizer};
+    /// use std::sync::Arc;
+    /// let crawler = 
+    ///     Crawler::new("https://pwr.edu.pl/".to_owned(), 1, vec![], vec![])
+    ///     .with_normalizer(Arc::new(default_normalizer().then(StripParams::new(&["page"]))));
+    /// let links = crawler.crawl();
+    /// assert!(links.vertices().all(|idx| !links.idx_to_name(idx).unwrap().contains("page=")));
+    /// ```
+    pub fn with_normalizer(mut self, normalizer: Arc<dyn UrlNormalizer>) -> Crawler {
+        self.normalizer = normalizer;
+        self
+    }
+
+    /// Makes the crawler follow only links found in the content of pages, skipping navigation menus, headers and footers.
+    pub fn with_content_links_only(mut self) -> Crawler {
+        self.content_links_only = true;
+        self
+    }
+
+    /// Sets the pipeline of cleanup steps applied to the graph returned by crawl.
+    /// Pages are deduplicated by the hashes of their bodies, so that pages which failed to load are never merged.
+    /// ```
+    /// use labisu::crawler::Crawler;
+    /// use labisu::pipeline::CrawlPipeline;
+    /// let crawler = 
+    ///     Crawler::new("https://pwr.edu.pl/".to_owned(), 1, vec!["pwr.edu".to_owned()], vec![])
+    ///     .with_pipeline(CrawlPipeline::new().dedupe_by_content().collapse_hosts());
+    /// let hosts = crawler.crawl();
+    /// assert!(hosts.contains_vertex("pwr.edu.pl"));
+    /// ```
+    pub fn with_pipeline(mut self, pipeline: CrawlPipeline) -> Crawler {
+        self.pipeline = Some(pipeline);
+        self
+    }
+
+    /// Adds an output to which the result of every crawl (after the pipeline) is written when the crawl ends,
+    /// both by crawl and crawl_report. Any number of sinks can be added.
+    /// A sink which cannot be written is skipped and the error is recorded in CrawlReport::write_errors.
+    /// ```
+    /// use labisu::crawler::{Crawler, Sink};
+    /// use labisu::graphs::Graph;
+    /// let crawler =
+    ///     Crawler::new("https://pwr.edu.pl/".to_owned(), 0, vec![], vec![])
+    ///     .with_sink(Sink::Json("sinks.json".to_owned()))
+    ///     .with_sink(Sink::CsvEdgeList("sinks.csv".to_owned()))
+    ///     .with_sink(Sink::Report("sinks_report.json".to_owned()));
+    /// let links = crawler.crawl();
+    /// assert_eq!(links.get_num_of_vertices(), Graph::read_from_json("sinks.json").get_num_of_vertices());
+    /// assert!(std::fs::read_to_string("sinks.csv").unwrap().starts_with("source,target\n"));
+    /// assert!(std::fs::read_to_string("sinks_report.json").unwrap().contains("\"drop_stats\""));
+    /// let report =
+    ///     Crawler::new("https://pwr.edu.pl/".to_owned(), 0, vec![], vec![])
+    ///     .with_sink(Sink::Json("missing/sinks.json".to_owned()))
+    ///     .crawl_report();
+    /// assert_eq!(1, report.write_errors.len());
+    /// ```
+    pub fn with_sink(mut self, sink: Sink) -> Crawler {
+        self.sinks.push(sink);
+        self
+    }
+
+    /// Makes the crawl start from the pages of given frontier instead of the root, e.g. exported by an earlier stage of a crawl
+    /// (see CrawlReport::frontier). The pages keep their depths, so the crawl goes as deep as the largest depth counted from the original root,
+    /// and pages deeper than it are added to the graph without scanning. The pages they were discovered from are added too, with the edges to them,
+    /// but they are not scanned again, so the graphs of the stages can be merged by names.
+    /// ```
+    /// use labisu::crawler::Crawler;
+    /// let first = Crawler::new("https://pwr.edu.pl/".to_owned(), 0, vec!["pwr.edu".to_owned()], vec![]).crawl_report();
+    /// assert!(first.frontier.entries.iter().all(|entry| entry.depth == 1));
+    /// let second = 
+    ///     Crawler::new("https://pwr.edu.pl/".to_owned(), 1, vec!["pwr.edu".to_owned()], vec![])
+    ///     .with_frontier(first.frontier.clone())
+    ///     .crawl_report();
+    /// assert!(second.frontier.entries.iter().all(|entry| entry.depth == 2));
+    /// assert!(first.frontier.entries.iter().all(|entry| second.graph.contains_vertex(&entry.url)));
+    /// ```
+    pub fn with_frontier(mut self, frontier: Frontier) -> Crawler {
+        self.frontier = Some(frontier);
+        self
+    }
+
+    /// Sets the TLS options of the scrapers, so that intranet sites with internal certificate authorities can be crawled:
+    /// whether invalid certificates are accepted (see Scraper::with_invalid_certs_accepted) and a PEM file
+    /// with additional root certificates (see Scraper::with_root_certificates).
+    /// Returns an Err value if the certificates cannot be read.
+    /// ```
+    /// use labisu::crawler::Crawler;
+    /// let crawler = Crawler::new("https://pwr.edu.pl/".to_owned(), 0, vec![], vec![]).with_tls(true, None).unwrap();
+    /// assert!(Crawler::new("https://pwr.edu.pl/".to_owned(), 0, vec![], vec![]).with_tls(false, Some("missing.pem")).is_err());
+    /// ```
+    pub fn with_tls(mut self, accept_invalid_certs: bool, root_certificates: Option<&str>) -> Result<Crawler, &'static str> {
+        if let Some(filename) = root_certificates {
+            Scraper::new(vec![], vec![]).with_root_certificates(filename)?; // validated once, before the crawl
         }
+        self.accept_invalid_certs = accept_invalid_certs;
+        self.root_certificates = root_certificates.map(str::to_owned);
+        Ok(self)
+    }
+
+    /// Returns the statistics of the requests per host (as given by the url) of the last crawl.
+    /// ```
+    /// use labisu::crawler::Crawler;
+    /// let crawler = Crawler::new("https://pwr.edu.pl/".to_owned(), 0, vec![], vec![]);
+    /// crawler.crawl();
+    /// assert_eq!(1, crawler.host_stats()["pwr.edu.pl"].requests);
+    /// ```
+    pub fn host_stats(&self) -> HashMap<String, HostStats> {
+        self.host_stats.lock().unwrap().clone()
+    }
+
+    /// Returns the numbers of hrefs dropped for each reason during the last crawl, 
+    /// where links to pages already linked with the scraped page are counted as duplicates.
+    /// ```
+    /// use labisu::crawler::Crawler;
+    /// let crawler = Crawler::new("https://pwr.edu.pl/".to_owned(), 0, vec![], vec![]);
+    /// let links = crawler.crawl();
+    /// assert_eq!(1, links.get_num_of_vertices());
+    /// assert!(crawler.drop_stats().total() >= crawler.drop_stats().duplicate);
+    /// ```
+    pub fn drop_stats(&self) -> DropStats {
+        self.drop_stats.lock().unwrap().clone()
+    }
+
+    /// Sets a closure that is run on the graph periodically during the crawl, e.g. the biclique finder.
+    /// The closure is called between two rounds of scraping, whenever at least interval has passed since its previous call,
+    /// and once more on the final graph. It gets the graph being crawled itself under a read lock, so no copy is made,
+    /// but the crawl does not progress until the closure returns.
+    /// ```
+    /// use labisu::bipartite::find_bipartite;
+    /// use labisu::crawler::Crawler;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::time::Duration;
+    /// let sizes = Arc::new(Mutex::new(vec![]));
+    /// let sizes_clone = Arc::clone(&sizes);
+    /// let crawler = 
+    ///     Crawler::new("https://pwr.edu.pl/".to_owned(), 0, vec![], vec![])
+    ///     .with_analysis(Duration::from_secs(10), move |graph| {
+    ///         let (left, _) = find_bipartite(graph, 1, 1);
+    ///         sizes_clone.lock().unwrap().push(left.len());
+    ///     });
+    /// crawler.crawl();
+    /// assert!(!sizes.lock().unwrap().is_empty());
+    /// ```
+    pub fn with_analysis<F>(mut self, interval: Duration, analysis: F) -> Crawler 
+    where F: FnMut(&Graph) + Send + 'static {
+        let mut analysis = analysis;
+        self.analysis = Some((interval, Mutex::new(Box::new(move |graph, _| analysis(graph)))));
+        self
+    }
+
+    /// Sets a closure that is run periodically during the crawl like with_analysis, 
+    /// but it also gets up to num_of_hubs vertices of the highest degrees, sorted by non-increasing degree.
+    /// The hubs are maintained while the edges are added (see graphs::TopDegrees), so no sorting is done for the calls.
+    /// ```
+    /// use labisu::crawler::Crawler;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::time::Duration;
+    /// let hubs = Arc::new(Mutex::new(vec![]));
+    /// let hubs_clone = Arc::clone(&hubs);
+    /// let crawler = 
+    ///     Crawler::new("https://pwr.edu.pl/".to_owned(), 1, vec![], vec![])
+    ///     .with_hub_analysis(Duration::from_secs(10), 3, move |_, top| {
+    ///         *hubs_clone.lock().unwrap() = top.to_vec();
+    ///     });
+    /// crawler.crawl();
+    /// assert!(hubs.lock().unwrap().len() <= 3);
+    /// ```
+    pub fn with_hub_analysis<F>(mut self, interval: Duration, num_of_hubs: usize, analysis: F) -> Crawler 
+    where F: FnMut(&Graph, &[usize]) + Send + 'static {
+        self.analysis = Some((interval, Mutex::new(Box::new(analysis))));
+        self.num_of_hubs = num_of_hubs;
+        self
+    }
+
+    /// Sets a closure that is given an immutable snapshot of the graph periodically during the crawl, like with_analysis.
+    /// The snapshot shares the chunks of neighbourhoods and names of the graph being crawled (see graphs::GraphSnapshot::from_graph),
+    /// so taking it costs one reference per chunk, and a chunk is copied only when the crawl modifies it while a snapshot still holds it.
+    /// The closure can send the snapshot to another thread and return immediately, so that the analysis does not stop the crawl.
+    /// ```
+    /// use labisu::crawler::Crawler;
+    /// use labisu::graphs::{GraphOps, GraphSnapshot};
+    /// use std::sync::mpsc;
+    /// use std::time::Duration;
+    /// let (tx, rx) = mpsc::channel();
+    /// let analyzer = std::thread::spawn(move || {
+    ///     rx.into_iter().map(|snapshot: GraphSnapshot| snapshot.get_num_of_vertices()).collect::<Vec<usize>>()
+    /// });
+    /// let crawler = 
+    ///     Crawler::new("https://pwr.edu.pl/".to_owned(), 0, vec![], vec![])
+    ///     .with_snapshot_analysis(Duration::from_secs(10), move |snapshot| tx.send(snapshot).unwrap());
+    /// let graph = crawler.crawl();
+    /// drop(crawler);
+    /// assert_eq!(Some(&graph.get_num_of_vertices()), analyzer.join().unwrap().last());
+    /// ```
+    pub fn with_snapshot_analysis<F>(mut self, interval: Duration, analysis: F) -> Crawler 
+    where F: FnMut(GraphSnapshot) + Send + 'static {
+        self.snapshot_analysis = Some((interval, Mutex::new(Box::new(analysis))));
+        self
+    }
+
+    /// Sets a closure that is given a sketch of the degree distribution periodically during the crawl, like with_analysis.
+    /// The sketch (see graphs::DegreeSketch) is updated with every added edge, so quantiles of degrees can be watched without copying the graph.
+    /// ```
+    /// use labisu::crawler::Crawler;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::time::Duration;
+    /// let medians = Arc::new(Mutex::new(vec![]));
+    /// let medians_clone = Arc::clone(&medians);
+    /// let crawler = 
+    ///     Crawler::new("https://pwr.edu.pl/".to_owned(), 0, vec![], vec![])
+    ///     .with_degree_analysis(Duration::from_secs(10), move |sketch| {
+    ///         medians_clone.lock().unwrap().push(sketch.quantile(0.5));
+    ///     });
+    /// crawler.crawl();
+    /// assert!(!medians.lock().unwrap().is_empty());
+    /// ```
+    pub fn with_degree_analysis<F>(mut self, interval: Duration, analysis: F) -> Crawler 
+    where F: FnMut(&DegreeSketch) + Send + 'static {
+        self.degree_analysis = Some((interval, Mutex::new(Box::new(analysis))));
+        self
     }
 
     /// Crawls the web based on given url and max_depth.
@@ -46,61 +869,402 @@ impl Crawler {
     /// }
     /// ```
     pub fn crawl(&self) -> Graph {
-        let num_of_threads = num_cpus::get_physical();
-        let max_depth = Arc::new(self.max_depth); // to share between threads and not to be changed
-        let graph = {
-            let graph = Graph::from_names(vec![self.root.to_owned()]);
-            Arc::new(RwLock::new(graph))
+        self.crawl_report().graph
+    }
+
+    /// Crawls the web the same way as crawl and returns the graph together with the statistics of depth levels, hosts and dropped links.
+    /// ```
+    /// use labisu::crawler::Crawler;
+    /// let crawler = Crawler::new("https://pwr.edu.pl/".to_owned(), 1, vec!["pwr.edu".to_owned()], vec![]);
+    /// let report = crawler.crawl_report();
+    /// assert_eq!(1, report.layers[0].pages);
+    /// assert_eq!(1, report.layers[0].new_vertices);
+    /// let new_vertices = report.layers.iter().map(|layer| layer.new_vertices).sum::<usize>();
+    /// assert_eq!(report.graph.get_num_of_vertices(), new_vertices);
+    /// ```
+    pub fn crawl_report(&self) -> CrawlReport {
+        let record = self.run();
+        let provenance =
+            record.edges
+            .iter()
+            .map(|edge| EdgeProvenance {
+                source: record.graph.idx_to_name(edge.from).unwrap(),
+                target: record.graph.idx_to_name(edge.to).unwrap(),
+                depth: edge.depth,
+                round: edge.round,
+                fetched_after: edge.fetched_after,
+                class: edge.class,
+            })
+            .collect::<Vec<EdgeProvenance>>();
+        let mut provenance_index: HashMap<String, HashMap<String, usize>> = HashMap::new();
+        for (i, edge) in provenance.iter().enumerate() {
+            let (source, target) = (&edge.source, &edge.target);
+            provenance_index.entry(source.min(target).clone()).or_default().insert(source.max(target).clone(), i);
+        }
+        let graph = match &self.pipeline {
+            Some(pipeline) => pipeline.apply_with_hashes(&record.graph, &record.content_hashes),
+            None => record.graph,
+        };
+        let mut report = CrawlReport {
+            graph,
+            layers: record.layers,
+            host_stats: self.host_stats(),
+            drop_stats: self.drop_stats(),
+            frontier: record.frontier,
+            provenance,
+            write_errors: record.write_errors,
+            provenance_index,
         };
-        let nodes_to_scan = {
-            let mut queue = VecDeque::new();
-            queue.push_back((0,0)); // (depth, node_id)
-            Arc::new(RwLock::new(queue)) 
-        };  
+        for sink in &self.sinks {
+            let error = match sink {
+                Sink::Json(filename) => report.graph.write_to_json(filename).err().map(|e| format!("Unable to write the graph to {filename}: {e}")),
+                Sink::CsvEdgeList(filename) => report.graph.write_to_csv_edge_list(filename).err().map(|e| format!("Unable to write the edge list to {filename}: {e}")),
+                Sink::Report(filename) => report.write_to_json(filename).err().map(|e| format!("Unable to write the report to {filename}: {e}")),
+            };
+            report.write_errors.extend(error);
+        }
+        report
+    }
+
+    /// Crawls the web the same way as crawl, but each vertex and edge is stamped with the round of scraping in which it was found.
+    /// The root is found in round 0 and pages found by scraping the root in round 1.
+    /// ```
+    /// use labisu::crawler::Crawler;
+    /// let crawler = Crawler::new("https://pwr.edu.pl/".to_owned(), 1, vec!["pwr.edu".to_owned()], vec![]);
+    /// let links = crawler.crawl_evolving();
+    /// assert_eq!(Some(0), links.vertex_time(0));
+    /// assert_eq!(1, links.snapshot_at(0).get_num_of_vertices());
+    /// ```
+    pub fn crawl_evolving(&self) -> EvolvingGraph {
+        let record = self.run();
+        let mut evolving = EvolvingGraph::empty();
+        for idx in record.graph.vertices() {
+            evolving.add_vertex(&record.graph.idx_to_name(idx).unwrap(), record.vertex_rounds[idx]);
+        }
+        for edge in record.edges {
+            evolving.add_edge_idx(edge.from, edge.to, edge.round);
+        }
+        evolving
+    }
+
+    /// Crawls the web the same way as crawl, but also returns the part of the page each edge was found in.
+    /// The classes are keyed by the indices of the ends of an edge, the smaller first.
+    /// ```
+    /// use labisu::crawler::Crawler;
+    /// let crawler = Crawler::new("https://pwr.edu.pl/".to_owned(), 1, vec!["pwr.edu".to_owned()], vec![]);
+    /// let (links, classes) = crawler.crawl_classified();
+    /// assert_eq!(links.get_num_of_edges(), classes.len());
+    /// ```
+    pub fn crawl_classified(&self) -> (Graph, HashMap<(usize, usize), LinkClass>) {
+        let record = self.run();
+        let classes = 
+            record.edges
+            .iter()
+            .map(|edge| ((edge.from.min(edge.to), edge.from.max(edge.to)), edge.class))
+            .collect();
+        (record.graph, classes)
+    }
+
+    /// Walks the pages the crawl would fetch without downloading their bodies and estimates how many pages it would fetch at every depth and from every host.
+    /// The walk starts with the root, or with the frontier of the crawler (see with_frontier), so a list of urls to estimate can be given as a frontier.
+    /// As the links of a page are not known without its body, the links of the pages are taken from a cached graph if it is given,
+    /// e.g. from an earlier crawl of the same website read with Graph::read_from_json or Graph::read_from_edge_log,
+    /// where every neighbour of a page accepted by the filter counts as its link; otherwise only the starting pages are walked.
+    /// Pages disallowed by robots.txt are skipped if the crawler respects it, which fetches the robots.txt of every host.
+    /// If send_head is true, a HEAD request is sent to every page (keeping the delays of the hosts) to find the pages which cannot be reached and the sizes of the bodies.
+    /// ```
+    /// use labisu::crawler::Crawler;
+    /// use labisu::graphs::Graph;
+    /// let mut cached = Graph::from_names(vec!["https://a.com/".to_owned(), "https://a.com/1".to_owned(), "https://b.com/".to_owned()]);
+    /// cached.add_edge("https://a.com/", "https://a.com/1");
+    /// cached.add_edge("https://a.com/1", "https://b.com/");
+    /// let estimate = Crawler::new("https://a.com/".to_owned(), 1, vec![], vec![]).dry_run(Some(&cached), false);
+    /// assert_eq!(vec![1, 1], estimate.pages_per_depth);
+    /// assert_eq!(2, estimate.pages_per_host["a.com"]);
+    ///
+    /// let estimate = Crawler::new("https://a.com/".to_owned(), 2, vec!["a.com".to_owned()], vec![]).dry_run(Some(&cached), false);
+    /// assert_eq!(2, estimate.total());
+    /// assert_eq!(1, estimate.filtered);
+    /// ```
+    pub fn dry_run(&self, cached: Option<&Graph>, send_head: bool) -> DryRunReport {
+        let CrawlStart { graph, queue, .. } = self.start();
+        let mut queue = queue.into_iter().map(|(depth, idx)| (depth, graph.idx_to_name(idx).unwrap())).collect::<VecDeque<(usize, String)>>();
+        let mut seen = graph.vertices().map(|idx| graph.idx_to_name(idx).unwrap()).collect::<HashSet<String>>();
+        let scraper = self.new_scraper(&Arc::new(DnsCache::new()));
+        let robots = self.respect_robots.then(RobotsCache::new);
+        let gate = HostGate::default();
+        let mut report = DryRunReport::default();
+
+        while let Some((depth, url)) = queue.pop_front() {
+            let parsed = Url::parse(&url).ok();
+            let host = parsed.as_ref().and_then(|url| url.host_str().map(str::to_owned)).unwrap_or_default();
+            let rules = match (&robots, &parsed) {
+                (Some(robots), Some(parsed)) => Some(robots.get(&scraper, parsed)),
+                _ => None,
+            };
+            if rules.as_ref().zip(parsed.as_ref()).is_some_and(|(rules, parsed)| !rules.allows(parsed)) {
+                report.disallowed += 1;
+                continue
+            }
+            if report.pages_per_depth.len() <= depth {
+                report.pages_per_depth.resize(depth + 1, 0);
+            }
+            report.pages_per_depth[depth] += 1;
+            *report.pages_per_host.entry(host.clone()).or_default() += 1;
+            if send_head {
+                let delay = rules.as_ref().and_then(|rules| rules.crawl_delay()).map_or(self.host_delay, |delay| delay.max(self.host_delay));
+                gate.wait(&host, delay);
+                match scraper.fetch_head(&url) {
+                    Ok(page) if page.status < 400 => {
+                        report.known_bytes +=
+                            page.headers
+                            .iter()
+                            .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+                            .and_then(|(_, value)| value.parse::<u64>().ok())
+                            .unwrap_or(0);
+                    },
+                    _ => report.unreachable += 1,
+                }
+            }
+
+            // links found at the largest depth lead only to known pages, which are not fetched again
+            let Some((cached, idx)) = cached.filter(|_| depth < self.max_depth).and_then(|cached| Some((cached, cached.name_to_idx(&url)?))) else {
+                continue
+            };
+            for link in cached.neighbours_idx(idx).unwrap().iter() {
+                let link = cached.idx_to_name_ref(*link).unwrap();
+                if seen.contains(link) {
+                    continue
+                }
+                if !self.filter.accepts(link) {
+                    report.filtered += 1;
+                    continue
+                }
+                seen.insert(link.to_owned());
+                queue.push_back((depth + 1, link.to_owned()));
+            }
+        }
+        report
+    }
+
+    /// Creates a scraper with the filters (and the browser) of the crawler, resolving host names with the overrides and the shared cache.
+    fn new_scraper(&self, dns_cache: &Arc<DnsCache>) -> Scraper {
+        let scraper = 
+            Scraper::new(vec![], vec![])
+            .with_filter(Arc::clone(&self.filter))
+            .with_normalizer(Arc::clone(&self.normalizer))
+            .with_host_overrides(&self.host_overrides)
+            .with_max_body_size(self.max_body_size)
+            .with_dns_cache(Arc::clone(dns_cache));
+        let scraper = match &self.root_certificates {
+            Some(filename) => scraper.with_root_certificates(filename).expect("Unable to read the certificates."),
+            None => scraper,
+        };
+        let scraper = if self.accept_invalid_certs { scraper.with_invalid_certs_accepted() } else { scraper };
+        #[cfg(feature = "headless")]
+        let scraper = match &self.browser {
+            Some(binary) => scraper.with_browser(binary).with_browser_sandbox(self.browser_sandbox),
+            None => scraper,
+        };
+        scraper
+    }
+
+    /// Returns the graph the crawl starts with: only the root, or the pages of the frontier together with the pages they were discovered from.
+    fn start(&self) -> CrawlStart {
+        let Some(frontier) = &self.frontier else {
+            return CrawlStart {
+                graph: Graph::from_names(vec![self.root.to_owned()]),
+                queue: VecDeque::from([(0, 0)]),
+                layers: vec![LayerStats { new_vertices: 1, ..LayerStats::default() }],
+                edges: vec![],
+            }
+        };
+        let mut graph = Graph::empty();
+        let mut queue = VecDeque::new();
+        let mut layers = vec![];
+        let mut edges = vec![];
+        let mut add = |graph: &mut Graph, url: &str, depth: usize| -> (usize, bool) {
+            if let Some(idx) = graph.name_to_idx(url) {
+                return (idx, false)
+            }
+            graph.add_vertex(url);
+            if layers.len() <= depth {
+                layers.resize(depth + 1, LayerStats::default());
+            }
+            layers[depth].new_vertices += 1;
+            (graph.get_num_of_vertices() - 1, true)
+        };
+        let mut entries = frontier.entries.iter().collect::<Vec<&FrontierEntry>>();
+        entries.sort_by_key(|entry| entry.depth); // stable, so pages of the same depth keep their order
+        for entry in entries {
+            let (idx, is_new) = add(&mut graph, &entry.url, entry.depth);
+            if is_new && entry.depth <= self.max_depth {
+                queue.push_back((entry.depth, idx));
+            }
+            if let Some(from) = &entry.discovered_from {
+                let (from_idx, _) = add(&mut graph, from, entry.depth.saturating_sub(1));
+                if graph.add_edge_idx(from_idx, idx) {
+                    let depth = entry.depth.saturating_sub(1);
+                    edges.push(EdgeRecord { from: from_idx, to: idx, round: 0, class: LinkClass::Content, depth, fetched_after: Duration::ZERO });
+                }
+            }
+        }
+        CrawlStart { graph, queue, layers, edges }
+    }
+
+    /// Runs the crawl and returns the graph together with the recorded rounds.
+    fn run(&self) -> CrawlRecord {
+        let num_of_threads = self.max_threads.unwrap_or_else(num_cpus::get_physical);
+        let max_depth = Arc::new(self.max_depth); // to share between threads and not to be changed
+        let crawl_start = Instant::now();
+        let CrawlStart { graph, queue, mut layers, mut edges } = self.start();
+        let mut curr_num_of_threads = std::cmp::min(num_of_threads, queue.len());
+        let graph = Arc::new(RwLock::new(graph));
+        let nodes_to_scan = Arc::new(RwLock::new(queue)); // (depth, node_id)
+        let dns_cache = Arc::new(DnsCache::new());
         let scrapers = {
             let mut scrapers = Vec::with_capacity(num_of_threads);
             
             (0..num_of_threads)
             .into_iter()
-            .for_each(|_| scrapers.push(Arc::new(Scraper::new(self.must_contain.clone(),
-                                                                      self.stop_words.clone()))));
+            .for_each(|_| scrapers.push(Arc::new(self.new_scraper(&dns_cache))));
             
             scrapers
         }; // scrapers are used but not changed     
         
-        let mut curr_num_of_threads = 1;
+        let mut write_errors = Vec::new();
+        let mut edge_log = None;
+        if let Some(filename) = &self.edge_log {
+            let graph = graph.read().unwrap();
+            let created = EdgeLog::create(filename).and_then(|mut log| {
+                for idx in graph.vertices() {
+                    log.log_vertex(graph.idx_to_name_ref(idx).unwrap())?;
+                }
+                for edge in &edges {
+                    log.log_edge(graph.idx_to_name_ref(edge.from).unwrap(), graph.idx_to_name_ref(edge.to).unwrap())?;
+                }
+                Ok(log)
+            });
+            match created {
+                Ok(log) => edge_log = Some(log),
+                Err(error) => write_errors.push(format!("Unable to write the edge log: {}", error)),
+            }
+        }
+
+        let host_stats = Arc::new(Mutex::new(HashMap::<String, HostStats>::new()));
+        let error_budget = self.error_budget;
+        let content_links_only = self.content_links_only;
+        let (host_delay, retries) = (self.host_delay, self.retries);
+        let robots = self.respect_robots.then(|| Arc::new(RobotsCache::new()));
+        let gate = Arc::new(HostGate::default());
+
+        let mut warc = None;
+        if let Some(filename) = &self.warc {
+            match WarcWriter::create(filename) {
+                Ok(writer) => warc = Some(writer),
+                Err(error) => write_errors.push(format!("Unable to write the archive: {}", error)),
+            }
+        }
+
+        let num_of_start_vertices = graph.read().unwrap().get_num_of_vertices();
+        let mut hubs = TopDegrees::new(self.num_of_hubs);
+        let mut vertex_rounds = vec![0; num_of_start_vertices];
+        let mut content_hashes = vec![None; num_of_start_vertices];
+        if self.num_of_hubs > 0 {
+            edges.iter().for_each(|edge| { hubs.increment(edge.from); hubs.increment(edge.to); });
+        }
+        let mut drop_stats = DropStats::default();
+        let mut frontier = Frontier::new();
+        let mut in_frontier = HashSet::new();
+        let mut round = 0;
+
+        let mut last_analysis = Instant::now();
+        let mut last_snapshot = Instant::now();
+        let mut degrees = self.degree_analysis.as_ref().map(|_| {
+            let graph = graph.read().unwrap();
+            let mut sketch = DegreeSketch::new();
+            graph.vertices().for_each(|idx| sketch.insert(graph.degree(idx)));
+            sketch
+        });
+        let mut last_degrees = Instant::now();
         while curr_num_of_threads > 0 {
+            round += 1;
             let mut threads = Vec::with_capacity(curr_num_of_threads);
             let (tx, rx) = mpsc::channel();
+            let archive = warc.is_some();
 
             for (queue_idx, scraper_arc) in scrapers.iter().enumerate().take(curr_num_of_threads) {
                 let scraper = Arc::clone(scraper_arc);
                 let graph = Arc::clone(&graph);
                 let nodes_to_scan_clone = Arc::clone(&nodes_to_scan);
                 let max_depth = Arc::clone(&max_depth);
+                let host_stats = Arc::clone(&host_stats);
+                let robots = robots.clone();
+                let gate = Arc::clone(&gate);
                 let tx = tx.clone();
 
                 threads.push(std::thread::spawn(move || {
                     let graph = graph.read().unwrap();
                     let (depth, node_id) = *nodes_to_scan_clone.read().unwrap().get(queue_idx).unwrap();
-                    let root_node_name = graph.idx_to_name(node_id).unwrap();
-                    let links = scraper.scrape(&root_node_name);
-                    let links = 
+                    let url = graph.idx_to_name_ref(node_id).unwrap();
+                    let host = Url::parse(url).ok().and_then(|url| url.host_str().map(str::to_owned)).unwrap_or_default();
+                    let blacklisted = host_stats.lock().unwrap().get(&host).is_some_and(|stats| stats.blacklisted);
+                    let rules = match (&robots, Url::parse(url)) {
+                        (Some(robots), Ok(parsed)) if !blacklisted => Some((robots.get(&scraper, &parsed), parsed)),
+                        _ => None,
+                    };
+                    let disallowed = rules.as_ref().is_some_and(|(rules, parsed)| !rules.allows(parsed));
+                    let delay = rules.as_ref().and_then(|(rules, _)| rules.crawl_delay()).map_or(host_delay, |delay| delay.max(host_delay));
+                    let page = 
+                        if blacklisted || disallowed {
+                            None
+                        } else {
+                            let mut attempt = 0;
+                            loop {
+                                gate.wait(&host, delay);
+                                let start = Instant::now();
+                                let result = scraper.fetch_checked(url);
+                                host_stats.lock().unwrap().entry(host.clone()).or_default().record(start.elapsed(), &result, error_budget);
+                                let retry = match &result {
+                                    Ok(page) => page.status >= 500,
+                                    Err(error) => *error != FetchError::Resolution,
+                                };
+                                if !retry || attempt == retries {
+                                    break result.ok()
+                                }
+                                attempt += 1;
+                            }
+                        };
+                    let fetched_at = crawl_start.elapsed();
+                    let content_hash = page.as_ref().filter(|page| page.status < 400).map(|page| {
+                        let mut hasher = DefaultHasher::new();
+                        page.body.hash(&mut hasher);
+                        hasher.finish()
+                    });
+                    let (mut links, drops) = page.as_ref().map(|page| scraper.tallied_page_links(page)).unwrap_or_default();
+                    if content_links_only {
+                        links.retain(|_, class| *class == LinkClass::Content);
+                    }
+                    let page = if archive { page } else { None };
+                    // links found at the largest depth lead only to known pages, the others form the frontier
+                    let (links, mut beyond): (HashMap<String, LinkClass>, HashMap<String, LinkClass>) = 
                         if depth == *max_depth {
-                            links.into_iter().filter(|link| graph.contains_vertex(link)).collect()
+                            links.into_iter().partition(|(link, _)| graph.contains_vertex(link))
                         } else {
-                            links
+                            (links, HashMap::new())
                         };
-                    let links = links.into_iter().map(|link| {
+                    let mut beyond = beyond.drain().map(|(link, _)| link).collect::<Vec<String>>();
+                    beyond.sort_unstable();
+                    let links = links.into_iter().map(|(link, class)| {
                         let link = link;
                         let index = graph.name_to_idx(&link);
                         match index {
-                            Some(idx) => Index::NumIndex(idx),
-                            None             => Index::StrIndex(link)
+                            Some(idx) => (Index::NumIndex(idx), class),
+                            None             => (Index::StrIndex(link), class)
                         }
-                    }).collect::<Vec<Index>>();
+                    }).collect::<Vec<(Index, LinkClass)>>();
 
-                    tx.send((queue_idx, links)).unwrap();
+                    tx.send((queue_idx, links, beyond, drops, content_hash, page, fetched_at)).unwrap();
                 }));
             }
 
@@ -112,41 +1276,147 @@ impl Crawler {
             let mut nodes_to_scan_write = nodes_to_scan.write().unwrap();
 
             for _ in 0..curr_num_of_threads {
-                let (queue_idx, links) = rx.recv().unwrap();
+                let (queue_idx, links, beyond, drops, content_hash, page, fetched_at) = rx.recv().unwrap();
+                drop_stats.merge(&drops);
+                if let Some(page) = page {
+                    write_warc(&mut warc, &mut write_errors, |warc| warc.write_page(&page));
+                }
                 let (depth, node_id) = *nodes_to_scan_write.get(queue_idx).unwrap();
-                let root_node_name = graph_write.idx_to_name(node_id).unwrap();
-                for link in links {
-                    match link {
+                for url in beyond {
+                    if in_frontier.insert(url.clone()) {
+                        let discovered_from = Some(graph_write.idx_to_name(node_id).unwrap());
+                        frontier.entries.push(FrontierEntry { url, depth: depth + 1, discovered_from });
+                    }
+                }
+                content_hashes[node_id] = content_hash;
+                layers[depth].pages += 1;
+                layers[depth].out_links += links.len();
+                let new_links = links.iter().filter(|(link, _)| matches!(link, Index::StrIndex(_))).count();
+                // the new pages are leaves until they are scanned, so their neighbour sets start empty
+                graph_write.reserve(new_links, 0);
+                for (link, class) in links {
+                    let (link_id, is_new_edge) = match link {
                         Index::StrIndex(link) => {
                             let graph_idx = graph_write.name_to_idx(&link);
                             match graph_idx {
-                                Some(link_id) => {
-                                    graph_write.add_edge_idx(node_id, link_id);
-                                },
+                                Some(link_id) => (link_id, graph_write.add_edge_idx(node_id, link_id)),
                                 None => {
                                     graph_write.add_vertex(&link);
                                     let link_id = graph_write.get_num_of_vertices() - 1;
-                                    graph_write.add_edge(&root_node_name, &link);
+                                    if let Some(degrees) = degrees.as_mut() {
+                                        degrees.insert(0);
+                                    }
+                                    vertex_rounds.push(round);
+                                    if layers.len() == depth + 1 {
+                                        layers.push(LayerStats::default());
+                                    }
+                                    layers[depth + 1].new_vertices += 1;
+                                    content_hashes.push(None);
+                                    write_edge_log(&mut edge_log, &mut write_errors, |log| log.log_vertex(&link));
                                     nodes_to_scan_write.push_back((depth + 1, link_id));
+                                    (link_id, graph_write.add_edge_idx(node_id, link_id))
                                 }
                             }
                         },
-                        Index::NumIndex(link_id) => {
-                            graph_write.add_edge_idx(node_id, link_id);
+                        Index::NumIndex(link_id) => (link_id, graph_write.add_edge_idx(node_id, link_id))
+                    };
+
+                    if is_new_edge {
+                        if let Some(degrees) = degrees.as_mut() {
+                            degrees.increment(graph_write.degree(node_id) - 1);
+                            degrees.increment(graph_write.degree(link_id) - 1);
                         }
+                        edges.push(EdgeRecord { from: node_id, to: link_id, round, class, depth, fetched_after: fetched_at });
+                        layers[depth].new_edges += 1;
+                        if self.num_of_hubs > 0 {
+                            hubs.increment(node_id);
+                            hubs.increment(link_id);
+                        }
+                        let (from, to) = (graph_write.idx_to_name_ref(node_id).unwrap(), graph_write.idx_to_name_ref(link_id).unwrap());
+                        write_edge_log(&mut edge_log, &mut write_errors, |log| log.log_edge(from, to));
+                    } else {
+                        drop_stats.duplicate += 1;
                     }
                 }
             }
 
+            write_edge_log(&mut edge_log, &mut write_errors, EdgeLog::flush);
+            write_warc(&mut warc, &mut write_errors, WarcWriter::flush);
+
             // deleting scanned nodes
             (0..curr_num_of_threads).into_iter().for_each(|_| {
                 nodes_to_scan_write.pop_front();
             });
 
             curr_num_of_threads = std::cmp::min(num_of_threads, nodes_to_scan_write.len());
+            // the analyses run without the write lock, the ones given the graph itself under the read lock
+            drop(nodes_to_scan_write);
+            drop(graph_write);
+
+            if let Some((interval, analysis)) = &self.analysis {
+                if curr_num_of_threads > 0 && last_analysis.elapsed() >= *interval {
+                    (analysis.lock().unwrap())(&graph.read().unwrap(), hubs.top());
+                    last_analysis = Instant::now();
+                }
+            }
+            if let Some((interval, analysis)) = &self.snapshot_analysis {
+                if curr_num_of_threads > 0 && last_snapshot.elapsed() >= *interval {
+                    let snapshot = GraphSnapshot::from_graph(&graph.read().unwrap());
+                    (analysis.lock().unwrap())(snapshot);
+                    last_snapshot = Instant::now();
+                }
+            }
+            if let (Some((interval, analysis)), Some(degrees)) = (&self.degree_analysis, &degrees) {
+                if curr_num_of_threads > 0 && last_degrees.elapsed() >= *interval {
+                    (analysis.lock().unwrap())(degrees);
+                    last_degrees = Instant::now();
+                }
+            }
         }
 
         let graph_r = graph.read().unwrap();
-        graph_r.clone()
+        if let Some((_, analysis)) = &self.analysis {
+            (analysis.lock().unwrap())(&graph_r, hubs.top());
+        }
+        if let Some((_, analysis)) = &self.snapshot_analysis {
+            (analysis.lock().unwrap())(GraphSnapshot::from_graph(&graph_r));
+        }
+        if let (Some((_, analysis)), Some(degrees)) = (&self.degree_analysis, &degrees) {
+            (analysis.lock().unwrap())(degrees);
+        }
+        *self.host_stats.lock().unwrap() = host_stats.lock().unwrap().clone();
+        *self.drop_stats.lock().unwrap() = drop_stats;
+
+        CrawlRecord {
+            graph: graph_r.clone(),
+            vertex_rounds,
+            edges,
+            content_hashes,
+            layers,
+            frontier,
+            write_errors,
+        }
+    }
+}
+
+/// Writes to the edge log of a crawl if it has one.
+/// If the write fails, the error is recorded and the crawl goes on without the log.
+fn write_edge_log(edge_log: &mut Option<EdgeLog>, write_errors: &mut Vec<String>, write: impl FnOnce(&mut EdgeLog) -> std::io::Result<()>) {
+    if let Some(log) = edge_log.as_mut() {
+        if let Err(error) = write(log) {
+            write_errors.push(format!("Unable to write the edge log: {}", error));
+            *edge_log = None;
+        }
+    }
+}
+
+/// Writes to the archive of a crawl if it has one.
+/// If the write fails, the error is recorded and the crawl goes on without the archive.
+fn write_warc(warc: &mut Option<WarcWriter>, write_errors: &mut Vec<String>, write: impl FnOnce(&mut WarcWriter) -> std::io::Result<()>) {
+    if let Some(writer) = warc.as_mut() {
+        if let Err(error) = write(writer) {
+            write_errors.push(format!("Unable to write the archive: {}", error));
+            *warc = None;
+        }
     }
 }
@@ -0,0 +1,158 @@
+use flate2::read::GzDecoder;
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader, Error, Read, Result};
+use std::path::Path;
+
+use super::graphs::{Graph, GraphOps};
+
+/// The edges of Zachary's karate club, which is small enough to be embedded.
+const KARATE_EDGES: [(usize, usize); 78] = [
+    (0, 1), (0, 2), (0, 3), (0, 4), (0, 5), (0, 6), (0, 7), (0, 8), (0, 10), (0, 11), (0, 12), (0, 13), (0, 17), (0, 19), (0, 21), (0, 31),
+    (1, 2), (1, 3), (1, 7), (1, 13), (1, 17), (1, 19), (1, 21), (1, 30),
+    (2, 3), (2, 7), (2, 8), (2, 9), (2, 13), (2, 27), (2, 28), (2, 32),
+    (3, 7), (3, 12), (3, 13), (4, 6), (4, 10), (5, 6), (5, 10), (5, 16), (6, 16),
+    (8, 30), (8, 32), (8, 33), (9, 33), (13, 33), (14, 32), (14, 33), (15, 32), (15, 33),
+    (18, 32), (18, 33), (19, 33), (20, 32), (20, 33), (22, 32), (22, 33),
+    (23, 25), (23, 27), (23, 29), (23, 32), (23, 33), (24, 25), (24, 27), (24, 31), (25, 31),
+    (26, 29), (26, 33), (27, 33), (28, 31), (28, 33), (29, 32), (29, 33), (30, 32), (30, 33), (31, 32), (31, 33), (32, 33),
+];
+
+/// A well-known public graph, used as a reproducible input of examples, benchmarks and experiments.
+/// Directed graphs are loaded as undirected ones, and vertices are named by their ids in the original data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Dataset {
+    /// Zachary's karate club: 34 members and 78 friendships, embedded in the crate.
+    Karate,
+    /// The email network of a European research institution from SNAP: 1005 vertices and 16706 edges.
+    EmailEuCore,
+    /// The collaboration network of General Relativity arXiv from SNAP: 5242 vertices and 14496 edges.
+    CaGrQc,
+    /// The web graph released by Google from SNAP: 875713 pages and 4322051 links.
+    WebGoogle,
+}
+
+impl Dataset {
+    /// Returns the name of the dataset, which is also the name of its file in the cache.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Dataset::Karate => "karate",
+            Dataset::EmailEuCore => "email-Eu-core",
+            Dataset::CaGrQc => "ca-GrQc",
+            Dataset::WebGoogle => "web-Google",
+        }
+    }
+
+    /// Returns the url of the gzipped edge list of the dataset, or None if it is embedded.
+    pub fn url(&self) -> Option<&'static str> {
+        match self {
+            Dataset::Karate => None,
+            Dataset::EmailEuCore => Some("https://snap.stanford.edu/data/email-Eu-core.txt.gz"),
+            Dataset::CaGrQc => Some("https://snap.stanford.edu/data/ca-GrQc.txt.gz"),
+            Dataset::WebGoogle => Some("https://snap.stanford.edu/data/web-Google.txt.gz"),
+        }
+    }
+}
+
+/// Reads a graph from an edge list in the SNAP format: a pair of vertex ids separated by whitespace on every line,
+/// with lines starting with '#' being comments. Vertices are indexed in order of their first appearance,
+/// loops and repeated edges are ignored.
+/// # Examples
+/// ```
+/// use labisu::datasets::read_edge_list;
+/// let graph = read_edge_list("# comment\n1\t2\n2\t1\n2 3\n3 3\n".as_bytes()).unwrap();
+/// assert_eq!(3, graph.get_num_of_vertices());
+/// assert_eq!(2, graph.get_num_of_edges());
+/// assert_eq!(Some(0), graph.name_to_idx("1"));
+/// assert!(read_edge_list("1\n".as_bytes()).is_err());
+/// ```
+pub fn read_edge_list<R: Read>(reader: R) -> Result<Graph> {
+    let mut graph = Graph::empty();
+    for line in BufReader::new(reader).lines() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue
+        }
+        let mut ids = line.split_whitespace();
+        match (ids.next(), ids.next()) {
+            (Some(from), Some(to)) => {
+                graph.add_vertex(from);
+                graph.add_vertex(to);
+                graph.add_edge(from, to);
+            },
+            _ => return Err(Error::other(format!("the line {} is not an edge", line))),
+        }
+    }
+    Ok(graph)
+}
+
+/// Loads the dataset, downloading it on first use and caching it in the directory in the local json format (see Graph::write_to_json_edge_list).
+/// The directory is created if it does not exist. Returns an error if the dataset cannot be downloaded or the cache cannot be read or written.
+/// # Examples
+/// ```
+/// use labisu::datasets::{load, Dataset};
+/// let karate = load(Dataset::Karate, "datasets").unwrap();
+/// assert_eq!(34, karate.get_num_of_vertices());
+/// assert_eq!(78, karate.get_num_of_edges());
+/// assert_eq!(17, karate.neighbours_idx(karate.name_to_idx("33").unwrap()).unwrap().len());
+/// ```
+pub fn load(dataset: Dataset, cache_dir: &str) -> Result<Graph> {
+    let cached = Path::new(cache_dir).join(format!("{}.json", dataset.name()));
+    let cached = cached.to_str().ok_or_else(|| Error::other("the cache directory is not valid UTF-8"))?;
+    if Path::new(cached).exists() {
+        return Graph::try_read_from_json(cached).map_err(Error::from)
+    }
+    let graph = match dataset.url() {
+        None => {
+            let mut graph = Graph::from_names((0..34).map(|id| id.to_string()).collect());
+            KARATE_EDGES.iter().for_each(|(from, to)| { graph.add_edge_idx(*from, *to); });
+            graph
+        },
+        Some(url) => {
+            let response = reqwest::blocking::get(url).and_then(|response| response.error_for_status()).map_err(Error::other)?;
+            read_edge_list(GzDecoder::new(response))?
+        },
+    };
+    std::fs::create_dir_all(cache_dir)?;
+    graph.write_to_json_edge_list(cached).map_err(Error::other)?;
+    Ok(graph)
+}
+
+/// Loads the dataset like load and returns the subgraph induced by the first num_of_vertices vertices reached by breadth-first search
+/// from the vertex of index 0 (continuing from the next unreached vertex if a component is exhausted), e.g. to get a reproducible part of web-Google.
+/// # Examples
+/// ```
+/// use labisu::datasets::{load_sample, Dataset};
+/// let sample = load_sample(Dataset::Karate, "datasets", 10).unwrap();
+/// assert_eq!(10, sample.get_num_of_vertices());
+/// assert_eq!(Some(0), sample.name_to_idx("0"));
+/// ```
+pub fn load_sample(dataset: Dataset, cache_dir: &str, num_of_vertices: usize) -> Result<Graph> {
+    let mut graph = load(dataset, cache_dir)?;
+    let n = graph.get_num_of_vertices();
+    let mut keep = vec![false; n];
+    let mut reached = 0;
+    let mut queue = VecDeque::new();
+    for start in 0..n {
+        if reached == num_of_vertices {
+            break
+        }
+        if keep[start] {
+            continue
+        }
+        keep[start] = true;
+        reached += 1;
+        queue.push_back(start);
+        while let Some(v) = queue.pop_front() {
+            graph.for_each_neighbour(v, &mut |u| {
+                if !keep[u] && reached < num_of_vertices {
+                    keep[u] = true;
+                    reached += 1;
+                    queue.push_back(u);
+                }
+            });
+        }
+    }
+    graph.retain_vertices(&keep);
+    Ok(graph)
+}
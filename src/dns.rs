@@ -0,0 +1,161 @@
+use hyper::client::connect::dns::Name;
+use reqwest::dns::{Addrs, Resolve, Resolving};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// The default time for which a resolved host is cached.
+pub const DEFAULT_TTL: Duration = Duration::from_secs(300);
+
+/// The default time for which a failed resolution is cached, short so that a transient failure does not lose a host for the whole crawl.
+pub const DEFAULT_FAILURE_TTL: Duration = Duration::from_secs(5);
+
+/// A failure of resolution of a host name, distinguishable from other failures of a request.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResolutionError {
+    /// The host name that could not be resolved.
+    pub host: String,
+    /// The description of the failure given by the system resolver.
+    pub message: String,
+}
+
+impl std::fmt::Display for ResolutionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unable to resolve {}: {}", self.host, self.message)
+    }
+}
+
+impl std::error::Error for ResolutionError {}
+
+/// The addresses of a host or the failure of its resolution.
+type Resolution = Result<Vec<SocketAddr>, ResolutionError>;
+
+/// A cache of host name resolutions shared by the scrapers, so that a host is not resolved for every request.
+/// Resolved hosts are cached for DEFAULT_TTL and failed resolutions only for DEFAULT_FAILURE_TTL, unless set otherwise with with_ttl.
+/// The system resolver does not report the time to live of the records, so it is the same for all hosts.
+/// Lookups are done with the system resolver and block the calling thread.
+pub struct DnsCache {
+    entries: Mutex<HashMap<String, (Instant, Resolution)>>, // with the time of expiry
+    ttl: Duration,
+    failure_ttl: Duration,
+    lookups: AtomicUsize,
+    hits: AtomicUsize,
+}
+
+impl Default for DnsCache {
+    fn default() -> DnsCache {
+        DnsCache {
+            entries: Mutex::new(HashMap::new()),
+            ttl: DEFAULT_TTL,
+            failure_ttl: DEFAULT_FAILURE_TTL,
+            lookups: AtomicUsize::new(0),
+            hits: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl DnsCache {
+    /// Creates an empty cache.
+    pub fn new() -> DnsCache {
+        DnsCache::default()
+    }
+
+    /// Sets the times for which resolved hosts and failed resolutions are cached. A zero time disables caching of either.
+    /// # Examples
+    /// ```
+    /// use labisu::dns::DnsCache;
+    /// use std::time::Duration;
+    /// let cache = DnsCache::new().with_ttl(Duration::from_secs(60), Duration::ZERO);
+    /// assert!(cache.lookup("no-such-host.invalid").is_err());
+    /// assert!(cache.lookup("no-such-host.invalid").is_err());
+    /// assert_eq!(0, cache.get_num_of_hits());
+    /// ```
+    pub fn with_ttl(mut self, ttl: Duration, failure_ttl: Duration) -> DnsCache {
+        self.ttl = ttl;
+        self.failure_ttl = failure_ttl;
+        self
+    }
+
+    /// Returns the addresses of the host (with port 0), resolving it only if it is not cached or its entry expired.
+    /// # Examples
+    /// ```
+    /// use labisu::dns::DnsCache;
+    /// let cache = DnsCache::new();
+    /// assert!(cache.lookup("localhost").is_ok());
+    /// assert!(cache.lookup("localhost").is_ok());
+    /// assert!(cache.lookup("no-such-host.invalid").is_err());
+    /// assert_eq!(3, cache.get_num_of_lookups());
+    /// assert_eq!(1, cache.get_num_of_hits());
+    /// ```
+    pub fn lookup(&self, host: &str) -> Resolution {
+        self.lookups.fetch_add(1, Ordering::Relaxed);
+        if let Some((expiry, cached)) = self.entries.lock().unwrap().get(host) {
+            if Instant::now() < *expiry {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                return cached.clone()
+            }
+        }
+
+        // the lock is not held during the resolution, so a host may be resolved twice by concurrent lookups
+        let resolved = 
+            (host, 0)
+            .to_socket_addrs()
+            .map(|addrs| addrs.collect())
+            .map_err(|e| ResolutionError { host: host.to_owned(), message: e.to_string() });
+        let ttl = if resolved.is_ok() { self.ttl } else { self.failure_ttl };
+        if !ttl.is_zero() {
+            self.entries.lock().unwrap().insert(host.to_owned(), (Instant::now() + ttl, resolved.clone()));
+        }
+        resolved
+    }
+
+    /// Returns the number of lookups.
+    pub fn get_num_of_lookups(&self) -> usize {
+        self.lookups.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of lookups answered from the cache.
+    pub fn get_num_of_hits(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+}
+
+impl Resolve for DnsCache {
+    fn resolve(&self, name: Name) -> Resolving {
+        let result = 
+            self.lookup(name.as_str())
+            .map(|addrs| Box::new(addrs.into_iter()) as Addrs)
+            .map_err(|e| e.into());
+        Box::pin(std::future::ready(result))
+    }
+}
+
+/// Reads static host name overrides from a file in the format of /etc/hosts, i.e. lines with an IPv4 or IPv6 address
+/// followed by host names, where '#' starts a comment. Lines with an invalid address are skipped.
+/// # Examples
+/// ```
+/// use labisu::dns::read_hosts_file;
+/// use std::net::IpAddr;
+/// std::fs::write("hosts", "# mirror\n10.0.0.5 mirror.local www.mirror.local\nfe80::1 v6.local # link local\nnot-an-ip host\n").unwrap();
+/// let overrides = read_hosts_file("hosts").unwrap();
+/// assert_eq!(3, overrides.len());
+/// assert_eq!(("www.mirror.local".to_string(), "10.0.0.5".parse::<IpAddr>().unwrap()), overrides[1]);
+/// assert!(overrides[2].1.is_ipv6());
+/// ```
+pub fn read_hosts_file(filename: &str) -> std::io::Result<Vec<(String, IpAddr)>> {
+    let reader = BufReader::new(File::open(filename)?);
+    let mut overrides = vec![];
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.split('#').next().unwrap_or_default();
+        let mut fields = line.split_whitespace();
+        if let Some(Ok(ip)) = fields.next().map(str::parse::<IpAddr>) {
+            overrides.extend(fields.map(|host| (host.to_owned(), ip)));
+        }
+    }
+    Ok(overrides)
+}
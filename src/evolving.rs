@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+
+use super::graphs::Graph;
+
+/// A structure representing an undirected graph, where each vertex and each edge
+/// carries the time of its insertion, e.g. the crawl round in which it was discovered.
+#[derive(Clone)]
+pub struct EvolvingGraph {
+    graph: Graph,
+    vertex_times: Vec<usize>,
+    edge_times: HashMap<(usize, usize), usize>,
+}
+
+impl EvolvingGraph {
+    /// Creates an empty evolving graph.
+    /// # Examples
+    /// ```
+    /// use labisu::evolving::EvolvingGraph;
+    /// let empty = EvolvingGraph::empty();
+    /// assert_eq!(0, empty.get_graph().get_num_of_vertices());
+    /// ```
+    pub fn empty() -> EvolvingGraph {
+        EvolvingGraph {
+            graph: Graph::empty(),
+            vertex_times: vec![],
+            edge_times: HashMap::new(),
+        }
+    }
+
+    /// Returns the graph containing all vertices and edges regardless of their insertion times.
+    pub fn get_graph(&self) -> &Graph {
+        &self.graph
+    }
+
+    /// Adds a new vertex with given name inserted at given time.
+    /// If the name already exists then it is not added and its time is not changed.
+    /// # Examples
+    /// ```
+    /// use labisu::evolving::EvolvingGraph;
+    /// let mut g = EvolvingGraph::empty();
+    /// g.add_vertex("vertex_0", 3);
+    /// g.add_vertex("vertex_0", 5);
+    /// assert_eq!(Some(3), g.vertex_time(0));
+    /// ```
+    pub fn add_vertex(&mut self, name: &str, time: usize) {
+        if !self.graph.contains_vertex(name) {
+            self.graph.add_vertex(name);
+            self.vertex_times.push(time);
+        }
+    }
+
+    /// Adds an edge inserted at given time between two vertices based on their indices.
+    /// Returns boolean value - if the adding was successful.
+    /// # Examples
+    /// ```
+    /// use labisu::evolving::EvolvingGraph;
+    /// let mut g = EvolvingGraph::empty();
+    /// g.add_vertex("vertex_0", 0);
+    /// g.add_vertex("vertex_1", 0);
+    /// assert!(g.add_edge_idx(0, 1, 2));
+    /// assert!(!g.add_edge_idx(1, 0, 4));
+    /// assert_eq!(Some(2), g.edge_time(1, 0));
+    /// ```
+    pub fn add_edge_idx(&mut self, from: usize, to: usize, time: usize) -> bool {
+        if self.graph.add_edge_idx(from, to) {
+            self.edge_times.insert(edge_key(from, to), time);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Adds an edge inserted at given time between two vertices based on their names.
+    /// Returns boolean value - if the adding was successful.
+    pub fn add_edge(&mut self, from: &str, to: &str, time: usize) -> bool {
+        match (self.graph.name_to_idx(from), self.graph.name_to_idx(to)) {
+            (Some(from_idx), Some(to_idx)) => self.add_edge_idx(from_idx, to_idx, time),
+            _ => false
+        }
+    }
+
+    /// Returns the insertion time of the vertex with given index or None if it does not exist.
+    pub fn vertex_time(&self, idx: usize) -> Option<usize> {
+        self.vertex_times.get(idx).cloned()
+    }
+
+    /// Returns the insertion time of the edge between two vertices or None if it does not exist.
+    pub fn edge_time(&self, from: usize, to: usize) -> Option<usize> {
+        self.edge_times.get(&edge_key(from, to)).cloned()
+    }
+
+    /// Returns the graph consisting of vertices and edges inserted not later than at given time.
+    /// The vertices keep their names and relative order, but may get new indices.
+    /// # Examples
+    /// ```
+    /// use labisu::evolving::EvolvingGraph;
+    /// let mut g = EvolvingGraph::empty();
+    /// g.add_vertex("root", 0);
+    /// g.add_vertex("child", 1);
+    /// g.add_vertex("grandchild", 2);
+    /// g.add_edge("root", "child", 1);
+    /// g.add_edge("child", "grandchild", 2);
+    /// g.add_edge("root", "grandchild", 3);
+    /// let snapshot = g.snapshot_at(2);
+    /// assert_eq!(3, snapshot.get_num_of_vertices());
+    /// assert_eq!(2, snapshot.get_num_of_edges());
+    /// assert_eq!(1, g.snapshot_at(1).get_num_of_edges());
+    /// ```
+    pub fn snapshot_at(&self, time: usize) -> Graph {
+        let kept = 
+            self.graph
+            .vertices()
+            .filter(|idx| self.vertex_times[*idx] <= time)
+            .collect::<Vec<usize>>();
+
+        let mut new_idx = vec![None; self.graph.get_num_of_vertices()];
+        kept.iter().enumerate().for_each(|(new, old)| new_idx[*old] = Some(new));
+
+        let mut snapshot = Graph::from_names(kept.iter().map(|idx| self.graph.idx_to_name(*idx).unwrap()).collect());
+
+        for ((from, to), edge_time) in &self.edge_times {
+            if *edge_time <= time {
+                if let (Some(from), Some(to)) = (new_idx[*from], new_idx[*to]) {
+                    snapshot.add_edge_idx(from, to);
+                }
+            }
+        }
+
+        snapshot
+    }
+}
+
+/// Returns the key under which an undirected edge is stored.
+fn edge_key(from: usize, to: usize) -> (usize, usize) {
+    (std::cmp::min(from, to), std::cmp::max(from, to))
+}
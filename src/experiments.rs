@@ -0,0 +1,301 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::io::{Result, Write};
+use std::time::{Duration, Instant};
+
+use super::atomic::AtomicFile;
+use super::bipartite::{find_bipartite, is_complete_bipartite};
+use super::graphs::Graph;
+
+/// The result of the trials of a single density in a threshold sweep.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SweepPoint {
+    /// The number of edges of the random graphs.
+    pub num_of_edges: usize,
+    /// The density of the random graphs, i.e. the fraction of pairs of vertices that are adjacent.
+    pub density: f64,
+    /// The number of trials.
+    pub trials: usize,
+    /// The number of trials in which find_bipartite found the subgraph.
+    pub successes: usize,
+}
+
+impl SweepPoint {
+    /// Returns the empirical probability that find_bipartite succeeds, or 0 if there were no trials.
+    pub fn success_rate(&self) -> f64 {
+        match self.trials {
+            0 => 0.0,
+            trials => self.successes as f64 / trials as f64,
+        }
+    }
+}
+
+/// Runs find_bipartite with the pool of s highest degree vertices and subgraphs of size t on random graphs with n vertices
+/// and step, 2 * step, ... edges (up to all pairs), trials times for each number of edges, and records how often it succeeds,
+/// i.e. how often it finds a complete bipartite subgraph with both sides of size at least t.
+/// The points show the phase transition of the algorithm and can be written for plotting with write_sweep_csv.
+/// Note that find_bipartite only searches graphs with |E| ^ 2 > 64 * |V| ^ 3, so n has to be over 256 for any trial to succeed.
+/// Panics if step is smaller than 2.
+/// # Examples
+/// ```
+/// use labisu::experiments::edge_threshold_sweep;
+/// let points = edge_threshold_sweep(300, 14950, 2, 10, 3);
+/// assert_eq!(3, points.len());
+/// assert_eq!(44850, points[2].num_of_edges);
+/// assert_eq!(1.0, points[2].density);
+/// assert_eq!(1.0, points[2].success_rate());
+/// assert_eq!(0.0, points[0].success_rate());
+/// ```
+pub fn edge_threshold_sweep(n: usize, step: usize, trials: usize, s: usize, t: usize) -> Vec<SweepPoint> {
+    assert!(step >= 2, "The step has to be at least 2.");
+    let pairs = n * n.saturating_sub(1) / 2;
+    (step..=pairs)
+    .step_by(step)
+    .map(|num_of_edges| {
+        let successes =
+            (0..trials)
+            .filter(|_| {
+                let graph = Graph::random_given_edges(n, num_of_edges);
+                let (left, right) = find_bipartite(&graph, s, t);
+                left.len() >= t && right.len() >= t && is_complete_bipartite(&graph, &left, &right)
+            })
+            .count();
+        SweepPoint { num_of_edges, density: num_of_edges as f64 / pairs as f64, trials, successes }
+    })
+    .collect()
+}
+
+/// Writes the points of a threshold sweep to a csv file with given filename, with columns num_of_edges, density, trials, successes and success_rate.
+/// # Examples
+/// ```
+/// use labisu::experiments::{edge_threshold_sweep, write_sweep_csv};
+/// let points = edge_threshold_sweep(300, 22425, 1, 10, 3);
+/// write_sweep_csv(&points, "sweep.csv").unwrap();
+/// let contents = std::fs::read_to_string("sweep.csv").unwrap();
+/// assert!(contents.starts_with("num_of_edges,density,trials,successes,success_rate\n"));
+/// assert_eq!(3, contents.lines().count());
+/// ```
+pub fn write_sweep_csv(points: &[SweepPoint], filename: &str) -> Result<()> {
+    let mut file = AtomicFile::create(filename)?;
+    writeln!(file, "num_of_edges,density,trials,successes,success_rate")?;
+    for point in points {
+        writeln!(file, "{},{},{},{},{}", point.num_of_edges, point.density, point.trials, point.successes, point.success_rate())?;
+    }
+    file.commit()
+}
+
+/// The number of resamples of a bootstrap confidence interval.
+const BOOTSTRAP_RESAMPLES: usize = 1000;
+
+/// Statistics of a sample of measurements, with the 95% confidence interval of the mean computed with the percentile bootstrap.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Estimate {
+    /// The mean of the sample.
+    pub mean: f64,
+    /// The sample standard deviation, 0 for fewer than two measurements.
+    pub std_dev: f64,
+    /// The lower end of the confidence interval of the mean.
+    pub ci_low: f64,
+    /// The upper end of the confidence interval of the mean.
+    pub ci_high: f64,
+}
+
+impl Estimate {
+    /// Computes the statistics of the sample, resampling it with a generator seeded with seed, so the interval is reproducible.
+    /// All statistics are 0 for an empty sample.
+    /// # Examples
+    /// ```
+    /// use labisu::experiments::Estimate;
+    /// let estimate = Estimate::new(&[1.0, 2.0, 3.0, 4.0, 5.0], 7);
+    /// assert_eq!(3.0, estimate.mean);
+    /// assert!((estimate.std_dev - 2.5f64.sqrt()).abs() < 1e-12);
+    /// assert!(estimate.ci_low < 3.0 && 3.0 < estimate.ci_high);
+    /// assert!(1.0 <= estimate.ci_low && estimate.ci_high <= 5.0);
+    /// assert_eq!(estimate, Estimate::new(&[1.0, 2.0, 3.0, 4.0, 5.0], 7));
+    /// ```
+    pub fn new(sample: &[f64], seed: u64) -> Estimate {
+        if sample.is_empty() {
+            return Estimate { mean: 0.0, std_dev: 0.0, ci_low: 0.0, ci_high: 0.0 }
+        }
+        let len = sample.len();
+        let mean = sample.iter().sum::<f64>() / len as f64;
+        let std_dev = 
+            if len < 2 { 0.0 } 
+            else { (sample.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (len - 1) as f64).sqrt() };
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut means = 
+            (0..BOOTSTRAP_RESAMPLES)
+            .map(|_| (0..len).map(|_| sample[rng.gen_range(0..len)]).sum::<f64>() / len as f64)
+            .collect::<Vec<f64>>();
+        means.sort_unstable_by(f64::total_cmp);
+        let percentile = |p: f64| means[((p * BOOTSTRAP_RESAMPLES as f64) as usize).min(BOOTSTRAP_RESAMPLES - 1)];
+        Estimate { mean, std_dev, ci_low: percentile(0.025), ci_high: percentile(0.975) }
+    }
+}
+
+/// The aggregated results of repeated trials of an experiment.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TrialSummary {
+    /// The number of trials.
+    pub trials: usize,
+    /// The number of successful trials.
+    pub successes: usize,
+    /// The statistics of success, with a successful trial counted as 1 and a failed one as 0.
+    pub success_rate: Estimate,
+    /// The statistics of the durations of the trials, in seconds.
+    pub duration: Estimate,
+}
+
+impl TrialSummary {
+    /// Writes the summary to a json file with given filename.
+    pub fn write_to_json(&self, filename: &str) -> serde_json::Result<()> {
+        let mut file = AtomicFile::create(filename).map_err(serde_json::Error::io)?;
+        serde_json::to_writer_pretty(&mut file, self)?;
+        file.commit().map_err(serde_json::Error::io)
+    }
+}
+
+/// Runs a trial of an experiment given number of times and aggregates whether the trials succeeded and how long they took.
+/// Every trial gets the same random generator, seeded once with seed, so that a run of trials can be reproduced,
+/// and the confidence intervals are bootstrapped with generators derived from the seed as well.
+/// # Examples
+/// ```
+/// use labisu::bipartite::find_bipartite;
+/// use labisu::experiments::repeat;
+/// use labisu::graphs::Graph;
+/// use rand::Rng;
+/// let summary = repeat(20, 42, |rng| {
+///     // dense enough for m^2 > 64n^3, so that find_bipartite searches for a K_{3,3} instead of returning an edge
+///     let graph = Graph::random(300, rng.gen_range(0.97..1.0));
+///     let (left, right) = find_bipartite(&graph, 10, 3);
+///     left.len() == 3 && right.len() == 3
+/// });
+/// assert_eq!(20, summary.trials);
+/// assert_eq!(1.0, summary.success_rate.mean);
+/// assert!(summary.duration.ci_low <= summary.duration.mean && summary.duration.mean <= summary.duration.ci_high);
+/// ```
+pub fn repeat<F: FnMut(&mut StdRng) -> bool>(trials: usize, seed: u64, mut trial: F) -> TrialSummary {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut successes = Vec::with_capacity(trials);
+    let mut durations = Vec::with_capacity(trials);
+    for _ in 0..trials {
+        let start = Instant::now();
+        let success = trial(&mut rng);
+        durations.push(start.elapsed().as_secs_f64());
+        successes.push(if success { 1.0 } else { 0.0 });
+    }
+    TrialSummary {
+        trials,
+        successes: successes.iter().filter(|success| **success == 1.0).count(),
+        success_rate: Estimate::new(&successes, seed.wrapping_add(1)),
+        duration: Estimate::new(&durations, seed.wrapping_add(2)),
+    }
+}
+
+/// An anytime algorithm run by a Schedule: it gets the instance and its deadline and reports the value of every improved solution
+/// (e.g. the number of edges of the best complete bipartite subgraph found so far) to the closure it is given.
+/// It is expected to check the deadline itself and return once it passes, reports after the deadline are ignored.
+pub type AnytimeAlgorithm = Box<dyn FnMut(&Graph, Instant, &mut dyn FnMut(usize))>;
+
+/// The best values found by an algorithm over time, recorded by a Schedule.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AnytimeCurve {
+    /// The name of the algorithm.
+    pub name: String,
+    /// The time budget of the algorithm.
+    pub budget: Duration,
+    /// The improvements as pairs of the time since the start of the algorithm and the best value so far, both increasing.
+    pub points: Vec<(Duration, usize)>,
+    /// Whether the algorithm returned within its budget, i.e. the last value is final.
+    pub finished: bool,
+}
+
+impl AnytimeCurve {
+    /// Returns the best value found within given time since the start of the algorithm, or None if there was none yet.
+    pub fn best_by(&self, time: Duration) -> Option<usize> {
+        self.points.iter().take_while(|(at, _)| *at <= time).last().map(|(_, value)| *value)
+    }
+
+    /// Returns the best value found within the budget, or None if nothing was found.
+    pub fn best(&self) -> Option<usize> {
+        self.points.last().map(|(_, value)| *value)
+    }
+}
+
+/// A batch of algorithms run one after another on the same instances, each with its own time budget,
+/// recording the best-found-by-deadline curve of every algorithm for anytime comparisons.
+/// # Examples
+/// ```
+/// use labisu::bipartite::expand_from_seed;
+/// use labisu::experiments::Schedule;
+/// use labisu::graphs::{Graph, GraphOps};
+/// use std::collections::HashSet;
+/// use std::time::{Duration, Instant};
+/// let mut schedule =
+///     Schedule::new()
+///     .with_algorithm("greedy", Duration::from_secs(10), Box::new(|graph, _, report| {
+///         if let Ok((left, right)) = expand_from_seed(graph, &HashSet::from([0]), &HashSet::from([40])) {
+///             report(left.len() * right.len());
+///         }
+///     }))
+///     .with_algorithm("restarts", Duration::from_millis(1), Box::new(|graph, deadline, report| {
+///         for v in graph.vertices().cycle() {
+///             if Instant::now() >= deadline {
+///                 break
+///             }
+///             let u = graph.neighbours_idx(v).unwrap().into_iter().min().unwrap();
+///             let (left, right) = expand_from_seed(graph, &HashSet::from([v]), &HashSet::from([u])).unwrap();
+///             report(left.len() * right.len());
+///         }
+///     }));
+/// let curves = schedule.run(&Graph::complete_multipartite(&[40, 40]));
+/// assert_eq!(2, curves.len());
+/// assert_eq!("greedy", curves[0].name);
+/// assert_eq!(Some(1600), curves[0].best_by(Duration::from_secs(10)));
+/// assert!(curves[0].finished);
+/// assert!(curves[1].points.len() <= 1);
+/// ```
+#[derive(Default)]
+pub struct Schedule {
+    algorithms: Vec<(String, Duration, AnytimeAlgorithm)>,
+}
+
+impl Schedule {
+    /// Creates an empty schedule.
+    pub fn new() -> Schedule {
+        Schedule::default()
+    }
+
+    /// Adds an algorithm with given name and time budget.
+    pub fn with_algorithm(mut self, name: &str, budget: Duration, algorithm: AnytimeAlgorithm) -> Schedule {
+        self.algorithms.push((name.to_owned(), budget, algorithm));
+        self
+    }
+
+    /// Runs every algorithm on the instance with its budget and returns their curves in the order the algorithms were added.
+    /// A report is recorded only if it improves the best value of the algorithm and comes before its deadline.
+    pub fn run(&mut self, graph: &Graph) -> Vec<AnytimeCurve> {
+        self.algorithms
+        .iter_mut()
+        .map(|(name, budget, algorithm)| {
+            let start = Instant::now();
+            let deadline = start + *budget;
+            let mut points: Vec<(Duration, usize)> = vec![];
+            algorithm(graph, deadline, &mut |value| {
+                let at = start.elapsed();
+                if at <= *budget && points.last().is_none_or(|(_, best)| value > *best) {
+                    points.push((at, value));
+                }
+            });
+            AnytimeCurve { name: name.clone(), budget: *budget, points, finished: start.elapsed() <= *budget }
+        })
+        .collect()
+    }
+
+    /// Runs the schedule on every instance, returning the curves of the algorithms for each instance in turn.
+    pub fn run_batch(&mut self, graphs: &[Graph]) -> Vec<Vec<AnytimeCurve>> {
+        graphs.iter().map(|graph| self.run(graph)).collect()
+    }
+}
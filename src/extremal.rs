@@ -0,0 +1,208 @@
+use super::graphs::Graph;
+
+/// Checks if a number is a prime.
+fn is_prime(q: u64) -> bool {
+    q >= 2 && (2..).take_while(|d| d * d <= q).all(|d| !q.is_multiple_of(d))
+}
+
+/// The field GF(p^k) for a prime p, with elements represented as polynomials over GF(p)
+/// of degree less than k modulo a fixed irreducible polynomial.
+/// Elements are identified with numbers 0..p^k whose base p digits are the coefficients.
+struct FiniteField {
+    p: u64,
+    k: usize,
+    modulus: Vec<u64>, // monic irreducible polynomial of degree k, lowest coefficient first
+}
+
+impl FiniteField {
+    fn new(p: u64, k: usize) -> FiniteField {
+        let modulus = 
+            (0..p.pow(k as u32))
+            .map(|low| {
+                let mut poly = FiniteField::digits(low, p, k);
+                poly.push(1);
+                poly
+            })
+            .find(|poly| FiniteField::is_irreducible(poly, p))
+            .unwrap();
+        FiniteField { p, k, modulus }
+    }
+
+    fn digits(x: u64, p: u64, k: usize) -> Vec<u64> {
+        (0..k).map(|i| (x / p.pow(i as u32)) % p).collect()
+    }
+
+    fn order(&self) -> u64 {
+        self.p.pow(self.k as u32)
+    }
+
+    /// Checks irreducibility by trying to divide by all monic polynomials of degree at most half of the degree.
+    fn is_irreducible(poly: &[u64], p: u64) -> bool {
+        let degree = poly.len() - 1;
+        for d in 1..=degree / 2 {
+            for low in 0..p.pow(d as u32) {
+                let mut divisor = FiniteField::digits(low, p, d);
+                divisor.push(1);
+                if FiniteField::remainder(poly, &divisor, p).iter().all(|c| *c == 0) {
+                    return false
+                }
+            }
+        }
+        true
+    }
+
+    /// Returns the remainder of dividing a polynomial by a monic one.
+    fn remainder(poly: &[u64], divisor: &[u64], p: u64) -> Vec<u64> {
+        let mut rem = poly.to_vec();
+        let d = divisor.len() - 1;
+        while rem.len() > d {
+            let lead = rem.pop().unwrap();
+            let shift = rem.len() - d;
+            for (i, c) in divisor.iter().take(d).enumerate() {
+                rem[shift + i] = (rem[shift + i] + p - (lead * c) % p) % p;
+            }
+        }
+        rem
+    }
+
+    fn add(&self, x: u64, y: u64) -> u64 {
+        let (xs, ys) = (FiniteField::digits(x, self.p, self.k), FiniteField::digits(y, self.p, self.k));
+        (0..self.k).rev().fold(0, |acc, i| acc * self.p + (xs[i] + ys[i]) % self.p)
+    }
+
+    fn mul(&self, x: u64, y: u64) -> u64 {
+        let (xs, ys) = (FiniteField::digits(x, self.p, self.k), FiniteField::digits(y, self.p, self.k));
+        let mut product = vec![0; 2 * self.k - 1];
+        for i in 0..self.k {
+            for j in 0..self.k {
+                product[i + j] = (product[i + j] + xs[i] * ys[j]) % self.p;
+            }
+        }
+        let rem = FiniteField::remainder(&product, &self.modulus, self.p);
+        (0..self.k).rev().fold(0, |acc, i| acc * self.p + rem.get(i).cloned().unwrap_or(0))
+    }
+
+    fn pow(&self, x: u64, e: u64) -> u64 {
+        let (mut base, mut e, mut result) = (x, e, 1);
+        while e > 0 {
+            if e % 2 == 1 {
+                result = self.mul(result, base);
+            }
+            base = self.mul(base, base);
+            e /= 2;
+        }
+        result
+    }
+
+    /// Returns the norm of x over GF(p), i.e. x^(1 + p + ... + p^(k-1)), which is an element of GF(p).
+    fn norm(&self, x: u64) -> u64 {
+        self.pow(x, (self.order() - 1) / (self.p - 1))
+    }
+}
+
+/// Creates the complete bipartite graph K_{a,b}.
+/// Vertices of the sides are named "left_i" and "right_j" and have indices 0..a and a..a+b respectively.
+/// # Examples
+/// ```
+/// use labisu::extremal::complete_bipartite;
+/// let k23 = complete_bipartite(2, 3);
+/// assert_eq!(5, k23.get_num_of_vertices());
+/// assert_eq!(6, k23.get_num_of_edges());
+/// assert!(k23.is_bipartite());
+/// ```
+pub fn complete_bipartite(a: usize, b: usize) -> Graph {
+    let names = 
+        (0..a).map(|i| format!("left_{}", i))
+        .chain((0..b).map(|j| format!("right_{}", j)))
+        .collect();
+    let mut graph = Graph::from_names(names);
+    for i in 0..a {
+        for j in a..a + b {
+            graph.add_edge_idx(i, j);
+        }
+    }
+    graph
+}
+
+/// Creates the incidence graph of the projective plane PG(2, q) for a prime q.
+/// It is a (q + 1)-regular bipartite graph with q^2 + q + 1 points ("point_i") and as many lines ("line_j"),
+/// and it contains no K_{2,2}, as two points lie on exactly one common line.
+/// Returns an Err value if q is not a prime.
+/// # Examples
+/// ```
+/// use labisu::extremal::projective_plane_incidence;
+/// let fano = projective_plane_incidence(2).unwrap();
+/// assert_eq!(14, fano.get_num_of_vertices());
+/// assert_eq!(21, fano.get_num_of_edges());
+/// assert!(fano.is_bipartite());
+/// assert!(projective_plane_incidence(4).is_err());
+/// ```
+pub fn projective_plane_incidence(q: u64) -> Result<Graph, &'static str> {
+    if !is_prime(q) {
+        return Err("The order of the plane has to be a prime.")
+    }
+
+    // representatives of projective points: the first non-zero coordinate equals 1
+    let mut points = vec![];
+    for x in 0..q {
+        for y in 0..q {
+            points.push([1, x, y]);
+        }
+    }
+    for y in 0..q {
+        points.push([0, 1, y]);
+    }
+    points.push([0, 0, 1]);
+
+    let n = points.len();
+    let names = 
+        (0..n).map(|i| format!("point_{}", i))
+        .chain((0..n).map(|j| format!("line_{}", j)))
+        .collect();
+    let mut graph = Graph::from_names(names);
+    // lines are represented by the same vectors, a point lies on a line if their dot product is 0
+    for (i, point) in points.iter().enumerate() {
+        for (j, line) in points.iter().enumerate() {
+            if (0..3).map(|c| point[c] * line[c]).sum::<u64>() % q == 0 {
+                graph.add_edge_idx(i, n + j);
+            }
+        }
+    }
+    Ok(graph)
+}
+
+/// Creates the projective norm graph of Alon, Ronyai and Szabo for a prime q and t >= 2.
+/// Its vertices are pairs (A, a) with A in GF(q^(t-1)) and non-zero a in GF(q), named "(A,a)" with A given by its number,
+/// and (A, a) is adjacent to (B, b) if N(A + B) = ab, where N is the norm from GF(q^(t-1)) to GF(q).
+/// The graph has q^(t-1) * (q - 1) vertices, about half of them times q^(t-1) edges and contains no K_{t,(t-1)!+1}.
+/// Returns an Err value if q is not a prime or t < 2.
+/// # Examples
+/// ```
+/// use labisu::extremal::norm_graph;
+/// let g = norm_graph(3, 3).unwrap();
+/// assert_eq!(18, g.get_num_of_vertices());
+/// assert!(norm_graph(4, 2).is_err());
+/// ```
+pub fn norm_graph(q: u64, t: usize) -> Result<Graph, &'static str> {
+    if !is_prime(q) {
+        return Err("The size of the field has to be a prime.")
+    }
+    if t < 2 {
+        return Err("The parameter t has to be at least 2.")
+    }
+
+    let field = FiniteField::new(q, t - 1);
+    let vertices = 
+        (0..field.order())
+        .flat_map(|big| (1..q).map(move |small| (big, small)))
+        .collect::<Vec<(u64, u64)>>();
+    let mut graph = Graph::from_names(vertices.iter().map(|(big, small)| format!("({},{})", big, small)).collect());
+    for (i, (big_a, small_a)) in vertices.iter().enumerate() {
+        for (j, (big_b, small_b)) in vertices.iter().enumerate().skip(i + 1) {
+            if field.norm(field.add(*big_a, *big_b)) == (small_a * small_b) % q {
+                graph.add_edge_idx(i, j);
+            }
+        }
+    }
+    Ok(graph)
+}
@@ -0,0 +1,188 @@
+use regex::Regex;
+use url::Url;
+
+use super::scraper::DropReason;
+
+/// A policy deciding which links found in pages are followed, shared by a crawler and its scrapers.
+/// Filters are combined with And, Or and Not (or the methods and, or and not), and any closure taking a link is a filter too.
+/// Links are checked before normalization, but after relative links are resolved against the url of the page.
+/// # Examples
+/// ```
+/// use labisu::filter::{Contains, Domain, Extension, LinkFilter};
+/// let filter = Domain::new("pwr.edu.pl").and(Extension::new(&["pdf", "jpg"]).not()).or(Contains::new("/news/"));
+/// assert!(filter.accepts("https://cs.pwr.edu.pl/index.html"));
+/// assert!(!filter.accepts("https://pwr.edu.pl/plan.pdf"));
+/// assert!(!filter.accepts("https://example.com/"));
+/// assert!(filter.accepts("https://example.com/news/1"));
+/// ```
+pub trait LinkFilter: Send + Sync {
+    /// Returns Ok if the link is accepted, otherwise the reason of the rejection, which is tallied in the drop statistics.
+    fn check(&self, link: &str) -> Result<(), DropReason>;
+
+    /// Returns true if the link is accepted.
+    fn accepts(&self, link: &str) -> bool {
+        self.check(link).is_ok()
+    }
+
+    /// Returns a filter accepting the links accepted by both filters.
+    fn and<F: LinkFilter + 'static>(self, other: F) -> And where Self: Sized + 'static {
+        And(vec![Box::new(self), Box::new(other)])
+    }
+
+    /// Returns a filter accepting the links accepted by any of the filters.
+    fn or<F: LinkFilter + 'static>(self, other: F) -> Or where Self: Sized + 'static {
+        Or(vec![Box::new(self), Box::new(other)])
+    }
+
+    /// Returns a filter accepting the links rejected by the filter.
+    fn not(self) -> Not where Self: Sized + 'static {
+        Not(Box::new(self))
+    }
+}
+
+impl<F: Fn(&str) -> bool + Send + Sync> LinkFilter for F {
+    fn check(&self, link: &str) -> Result<(), DropReason> {
+        if self(link) { Ok(()) } else { Err(DropReason::Filtered) }
+    }
+}
+
+/// Accepts the links accepted by all filters, rejecting with the reason of the first filter rejecting the link.
+pub struct And(pub Vec<Box<dyn LinkFilter>>);
+
+impl LinkFilter for And {
+    fn check(&self, link: &str) -> Result<(), DropReason> {
+        self.0.iter().try_for_each(|filter| filter.check(link))
+    }
+}
+
+/// Accepts the links accepted by any filter, rejecting with the reason of the last filter.
+pub struct Or(pub Vec<Box<dyn LinkFilter>>);
+
+impl LinkFilter for Or {
+    fn check(&self, link: &str) -> Result<(), DropReason> {
+        let mut result = Err(DropReason::Filtered);
+        for filter in &self.0 {
+            result = filter.check(link);
+            if result.is_ok() {
+                break
+            }
+        }
+        result
+    }
+}
+
+/// Accepts the links rejected by the filter.
+pub struct Not(pub Box<dyn LinkFilter>);
+
+impl LinkFilter for Not {
+    fn check(&self, link: &str) -> Result<(), DropReason> {
+        match self.0.check(link) {
+            Ok(()) => Err(DropReason::Filtered),
+            Err(_) => Ok(()),
+        }
+    }
+}
+
+/// Accepts the links containing the word, e.g. one of the words required by a crawler.
+pub struct Contains(pub String);
+
+impl Contains {
+    /// Creates a filter requiring given word.
+    pub fn new(word: &str) -> Contains {
+        Contains(word.to_owned())
+    }
+}
+
+impl LinkFilter for Contains {
+    fn check(&self, link: &str) -> Result<(), DropReason> {
+        if link.contains(&self.0) { Ok(()) } else { Err(DropReason::MissingWord) }
+    }
+}
+
+/// Accepts the links not containing the stop word.
+pub struct Excludes(pub String);
+
+impl Excludes {
+    /// Creates a filter rejecting given stop word.
+    pub fn new(word: &str) -> Excludes {
+        Excludes(word.to_owned())
+    }
+}
+
+impl LinkFilter for Excludes {
+    fn check(&self, link: &str) -> Result<(), DropReason> {
+        if link.contains(&self.0) { Err(DropReason::StopWord) } else { Ok(()) }
+    }
+}
+
+/// Accepts the links matching the regular expression anywhere.
+pub struct Matches(pub Regex);
+
+impl Matches {
+    /// Creates a filter with given regular expression, returns an Err value if it is invalid.
+    /// # Examples
+    /// ```
+    /// use labisu::filter::{LinkFilter, Matches};
+    /// let filter = Matches::new(r"/20\d\d/").unwrap();
+    /// assert!(filter.accepts("https://pwr.edu.pl/2023/news"));
+    /// assert!(!filter.accepts("https://pwr.edu.pl/news"));
+    /// assert!(Matches::new("(").is_err());
+    /// ```
+    pub fn new(pattern: &str) -> Result<Matches, regex::Error> {
+        Ok(Matches(Regex::new(pattern)?))
+    }
+}
+
+impl LinkFilter for Matches {
+    fn check(&self, link: &str) -> Result<(), DropReason> {
+        if self.0.is_match(link) { Ok(()) } else { Err(DropReason::Filtered) }
+    }
+}
+
+/// Accepts the links to the domain or any of its subdomains.
+pub struct Domain(pub String);
+
+impl Domain {
+    /// Creates a filter accepting given domain, e.g. "pwr.edu.pl".
+    pub fn new(domain: &str) -> Domain {
+        Domain(domain.trim_start_matches('.').to_lowercase())
+    }
+}
+
+impl LinkFilter for Domain {
+    fn check(&self, link: &str) -> Result<(), DropReason> {
+        let host = Url::parse(link).ok().and_then(|url| url.host_str().map(str::to_owned)).unwrap_or_default();
+        let accepted = host == self.0 || host.strip_suffix(&self.0).is_some_and(|prefix| prefix.ends_with('.'));
+        if accepted { Ok(()) } else { Err(DropReason::Filtered) }
+    }
+}
+
+/// Accepts the links whose path ends with one of the extensions, compared case-insensitively.
+/// Usually negated, e.g. to skip images.
+pub struct Extension(pub Vec<String>);
+
+impl Extension {
+    /// Creates a filter for given extensions, without the leading dots.
+    pub fn new(extensions: &[&str]) -> Extension {
+        Extension(extensions.iter().map(|extension| extension.trim_start_matches('.').to_lowercase()).collect())
+    }
+}
+
+impl LinkFilter for Extension {
+    fn check(&self, link: &str) -> Result<(), DropReason> {
+        let path = Url::parse(link).map(|url| url.path().to_lowercase()).unwrap_or_default();
+        let extension = path.rsplit('/').next().and_then(|file| file.rsplit_once('.')).map(|(_, extension)| extension);
+        match extension {
+            Some(extension) if self.0.iter().any(|accepted| accepted == extension) => Ok(()),
+            _ => Err(DropReason::Filtered),
+        }
+    }
+}
+
+/// Returns the filter of the words required in links and the stop words, used by the constructors of the scraper and the crawler.
+/// An empty filter accepts all links.
+pub fn words(must_contain: &[String], stop_words: &[String]) -> And {
+    let required = must_contain.iter().map(|word| Box::new(Contains::new(word)) as Box<dyn LinkFilter>);
+    let stopped = stop_words.iter().map(|word| Box::new(Excludes::new(word)) as Box<dyn LinkFilter>);
+    And(required.chain(stopped).collect())
+}
@@ -0,0 +1,195 @@
+use std::collections::VecDeque;
+
+use super::graphs::GraphOps;
+
+/// A directed network with integer capacities of edges, on which maximum flows and minimum cuts are computed with Dinic's algorithm.
+/// Nodes are numbered from 0, and every edge is stored together with its reverse edge of the residual network.
+/// # Examples
+/// ```
+/// use labisu::flow::FlowNetwork;
+/// let mut network = FlowNetwork::new(4);
+/// let top = network.add_edge(0, 1, 3);
+/// network.add_edge(0, 2, 2);
+/// network.add_edge(1, 2, 5);
+/// network.add_edge(1, 3, 2);
+/// network.add_edge(2, 3, 3);
+/// assert_eq!(Ok(5), network.max_flow(0, 3));
+/// assert_eq!(3, network.flow(top));
+/// assert_eq!(vec![true, false, false, false], network.source_side(0));
+/// assert!(network.max_flow(0, 4).is_err());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct FlowNetwork {
+    edges: Vec<Vec<usize>>, // ids of the edges leaving every node
+    heads: Vec<usize>,
+    capacities: Vec<u64>, // residual, the reverse of the edge with id e has id e ^ 1
+    original: Vec<u64>,
+}
+
+impl FlowNetwork {
+    /// Creates a network with given number of nodes and no edges.
+    pub fn new(num_of_nodes: usize) -> FlowNetwork {
+        FlowNetwork { edges: vec![vec![]; num_of_nodes], ..FlowNetwork::default() }
+    }
+
+    /// Creates a network from an undirected graph, where every edge becomes a pair of opposite edges of given capacity.
+    /// # Examples
+    /// ```
+    /// use labisu::flow::FlowNetwork;
+    /// use labisu::graphs::Graph;
+    /// // the edge connectivity of the Petersen graph is 3
+    /// let mut network = FlowNetwork::from_graph(&Graph::petersen(), 1);
+    /// assert_eq!(Ok(3), network.max_flow(0, 7));
+    /// ```
+    pub fn from_graph<G: GraphOps>(graph: &G, capacity: u64) -> FlowNetwork {
+        let mut network = FlowNetwork::new(graph.get_num_of_vertices());
+        for v in graph.vertices() {
+            graph.for_each_neighbour(v, &mut |u| {
+                if v < u {
+                    network.add_undirected_edge(v, u, capacity);
+                }
+            });
+        }
+        network
+    }
+
+    /// Returns the number of nodes.
+    pub fn get_num_of_nodes(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Adds a node and returns its number.
+    pub fn add_node(&mut self) -> usize {
+        self.edges.push(vec![]);
+        self.edges.len() - 1
+    }
+
+    /// Adds an edge from one node to another with given capacity and returns its id. Panics if a node does not exist.
+    pub fn add_edge(&mut self, from: usize, to: usize, capacity: u64) -> usize {
+        self.add_edge_pair(from, to, capacity, 0)
+    }
+
+    /// Adds a pair of opposite edges between two nodes, both with given capacity, and returns the id of the one from u to v.
+    /// Panics if a node does not exist.
+    pub fn add_undirected_edge(&mut self, u: usize, v: usize, capacity: u64) -> usize {
+        self.add_edge_pair(u, v, capacity, capacity)
+    }
+
+    /// Adds an edge with its reverse edge and returns the id of the edge.
+    fn add_edge_pair(&mut self, from: usize, to: usize, capacity: u64, reverse_capacity: u64) -> usize {
+        assert!(from < self.edges.len() && to < self.edges.len(), "Node does not exist in the network.");
+        let id = self.heads.len();
+        self.edges[from].push(id);
+        self.heads.push(to);
+        self.edges[to].push(id + 1);
+        self.heads.push(from);
+        self.capacities.extend([capacity, reverse_capacity]);
+        self.original.extend([capacity, reverse_capacity]);
+        id
+    }
+
+    /// Returns the flow through the edge with given id, after max_flow.
+    pub fn flow(&self, edge: usize) -> u64 {
+        self.original[edge].saturating_sub(self.capacities[edge])
+    }
+
+    /// Returns the distances from the source in the residual network, usize::MAX for unreachable nodes.
+    fn levels(&self, source: usize) -> Vec<usize> {
+        let mut levels = vec![usize::MAX; self.edges.len()];
+        levels[source] = 0;
+        let mut queue = VecDeque::from([source]);
+        while let Some(u) = queue.pop_front() {
+            for e in &self.edges[u] {
+                let v = self.heads[*e];
+                if self.capacities[*e] > 0 && levels[v] == usize::MAX {
+                    levels[v] = levels[u] + 1;
+                    queue.push_back(v);
+                }
+            }
+        }
+        levels
+    }
+
+    /// Pushes a blocking flow through the level graph, searching it without recursion, and returns its value.
+    fn blocking_flow(&mut self, source: usize, sink: usize, levels: &[usize]) -> u64 {
+        let mut total = 0;
+        let mut next = vec![0; self.edges.len()];
+        let mut path: Vec<usize> = vec![];
+        let mut u = source;
+        loop {
+            if u == sink {
+                let pushed = path.iter().map(|e| self.capacities[*e]).min().unwrap();
+                for e in &path {
+                    self.capacities[*e] -= pushed;
+                    self.capacities[*e ^ 1] += pushed;
+                }
+                total += pushed;
+                let saturated = path.iter().position(|e| self.capacities[*e] == 0).unwrap();
+                path.truncate(saturated);
+                u = path.last().map_or(source, |e| self.heads[*e]);
+                continue
+            }
+            let advance = self.edges[u][next[u]..].iter().position(|e| self.capacities[*e] > 0 && levels[self.heads[*e]] == levels[u] + 1);
+            match advance {
+                Some(offset) => {
+                    next[u] += offset;
+                    let e = self.edges[u][next[u]];
+                    path.push(e);
+                    u = self.heads[e];
+                },
+                None if u == source => return total,
+                None => {
+                    next[u] = self.edges[u].len();
+                    path.pop();
+                    u = path.last().map_or(source, |e| self.heads[*e]);
+                    next[u] += 1;
+                },
+            }
+        }
+    }
+
+    /// Computes a maximum flow from the source to the sink with Dinic's algorithm and returns its value.
+    /// The flow is added to the flow already in the network, so calling it again returns 0.
+    /// If a node does not exist or the source is the sink, then it returns an Err value.
+    pub fn max_flow(&mut self, source: usize, sink: usize) -> Result<u64, &'static str> {
+        if source >= self.edges.len() || sink >= self.edges.len() {
+            return Err("Node does not exist in the network.")
+        }
+        if source == sink {
+            return Err("The source and the sink have to be different.")
+        }
+        let mut total = 0;
+        loop {
+            let levels = self.levels(source);
+            if levels[sink] == usize::MAX {
+                return Ok(total)
+            }
+            total += self.blocking_flow(source, sink, &levels);
+        }
+    }
+
+    /// Returns the nodes reachable from the source in the residual network. After max_flow they form the source side of a minimum cut,
+    /// the smallest one. Panics if the source does not exist.
+    pub fn source_side(&self, source: usize) -> Vec<bool> {
+        self.levels(source).iter().map(|level| *level != usize::MAX).collect()
+    }
+
+    /// Computes a maximum flow like max_flow and returns the value of the minimum cut together with its source side.
+    /// # Examples
+    /// ```
+    /// use labisu::flow::FlowNetwork;
+    /// use labisu::graphs::Graph;
+    /// // two triangles joined by an edge are separated by cutting it
+    /// let mut g = Graph::from_names((0..6).map(|i| i.to_string()).collect());
+    /// for (u, v) in [(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3), (2, 3)] {
+    ///     g.add_edge_idx(u, v);
+    /// }
+    /// let (cut, side) = FlowNetwork::from_graph(&g, 1).min_cut(0, 5).unwrap();
+    /// assert_eq!(1, cut);
+    /// assert_eq!(vec![true, true, true, false, false, false], side);
+    /// ```
+    pub fn min_cut(&mut self, source: usize, sink: usize) -> Result<(u64, Vec<bool>), &'static str> {
+        let cut = self.max_flow(source, sink)?;
+        Ok((cut, self.source_side(source)))
+    }
+}
@@ -1,9 +1,65 @@
 use rand::Rng;
-use serde::{Deserialize, Serialize};
-use serde_json::json;
+use serde::{Deserialize, Serialize, Serializer};
 use std::collections::HashMap;
 use std::collections::HashSet;
-use std::fs::File;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::sync::Arc;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use super::atomic::AtomicFile;
+use super::combinatorics::is_graphical;
+use chunked::Chunked;
+
+mod bipartite_graph;
+#[cfg(feature = "formats")]
+mod canonical;
+mod chunked;
+#[cfg(feature = "parquet")]
+mod columnar;
+mod concurrent;
+mod cow;
+#[cfg(feature = "formats")]
+mod csv;
+mod degree_sketch;
+mod densest;
+mod diff;
+#[cfg(feature = "viz")]
+mod drawing;
+mod fitting;
+mod import;
+mod lfr;
+#[cfg(feature = "formats")]
+mod nauty;
+mod relabeling;
+mod sampling;
+#[cfg(feature = "formats")]
+mod sql;
+mod top_degrees;
+mod transaction;
+/// Eigenvalues of the adjacency and Laplacian matrices of a graph.
+pub mod spectral;
+/// Statistics describing the structure of a graph.
+pub mod stats;
+pub use bipartite_graph::{BipartiteGraph, Side};
+#[cfg(feature = "formats")]
+pub use canonical::MAX_CANONICAL_VERTICES;
+pub use concurrent::ConcurrentGraph;
+pub use cow::{CowGraph, GraphSnapshot};
+pub use degree_sketch::DegreeSketch;
+pub use densest::{densest_subgraph, densest_subgraph_exact, DensestSubgraph};
+pub use diff::{diff, GraphDiff};
+#[cfg(feature = "viz")]
+pub use drawing::{Rgb, Shape, VertexStyle};
+pub use fitting::{fit_gnp, fit_power_law, GnpFit, PowerLawFit};
+pub use import::{ImportError, ImportPolicy};
+pub use lfr::{lfr_benchmark, LfrParameters};
+pub use relabeling::Relabeling;
+pub use sampling::sample_by_degree;
+pub use top_degrees::TopDegrees;
+pub use transaction::Transaction;
 
 /// Enum used to color graph's vertices.
 #[derive(Clone, PartialEq)]
@@ -24,24 +80,290 @@ impl Color {
     }
 }
 
+/// Hasher of the internal sets and maps, the fast non-cryptographic FxHash if the fxhash feature is enabled.
+#[cfg(feature = "fxhash")]
+type BuildHasher = rustc_hash::FxBuildHasher;
+#[cfg(not(feature = "fxhash"))]
+type BuildHasher = std::collections::hash_map::RandomState;
+
+type NeighbourSet = HashSet<usize, BuildHasher>;
+type NameMap = HashMap<Arc<str>, usize, BuildHasher>;
+
 /// A structure representing an undirected graph, where vertices are named by strings.
+/// Each name is allocated once and shared by both directions of the name mapping.
+/// Neighbourhoods and names are stored in chunks shared by the clones and snapshots of the graph.
 #[derive(Clone)]
 pub struct Graph {
     num_of_vertices: usize,
     num_of_edges: usize,
-    neighbours: Vec<HashSet<usize>>,
-    idx_to_name_map: Vec<String>,
-    name_to_idx_map: HashMap<String, usize>,
+    neighbours: Chunked<NeighbourSet>,
+    idx_to_name_map: Chunked<Arc<str>>,
+    name_to_idx_map: NameMap,
+    degree_hint: usize, // initial capacity of the neighbour sets of added vertices, until the graph has hinted_vertices vertices
+    hinted_vertices: usize, // the end of the space reserved with a number of edges
+}
+
+/// Interns the names, i.e. creates both directions of the name mapping sharing one allocation per name.
+fn intern_names(names: Vec<String>) -> (Chunked<Arc<str>>, NameMap) {
+    let idx_to_name_map: Chunked<Arc<str>> = names.into_iter().map(Arc::from).collect();
+    let name_to_idx_map = 
+        idx_to_name_map
+        .iter()
+        .enumerate()
+        .map(|(idx, name)| (Arc::clone(name), idx))
+        .collect();
+    (idx_to_name_map, name_to_idx_map)
+}
+
+/// Read-only operations on a graph required by the algorithms of the crate.
+/// It is implemented for Graph, references to implementors and Arc-wrapped implementors,
+/// so that a shared immutable graph can be analyzed without cloning it.
+/// The trait is object safe, so algorithms can also be selected at runtime and run on a &dyn GraphOps (see the algorithms module).
+/// # Examples
+/// ```
+/// use labisu::graphs::{Graph, GraphOps};
+/// use std::sync::Arc;
+/// let shared = Arc::new(Graph::complete(4));
+/// let reference = &shared;
+/// assert_eq!(6, reference.get_num_of_edges());
+/// assert_eq!(3, GraphOps::degree(&shared, 0));
+/// ```
+pub trait GraphOps {
+    /// Returns number of vertices.
+    fn get_num_of_vertices(&self) -> usize;
+
+    /// Returns number of edges.
+    fn get_num_of_edges(&self) -> usize;
+
+    /// Lists all neighbours of a given vertex based on its index.
+    /// If a given index does not exist in the graph it returns an Err value.
+    fn neighbours_idx(&self, idx: usize) -> Result<HashSet<usize>, &str>;
+
+    /// Returns the degree of the vertex with given index.
+    /// Panics if the index does not exist in the graph.
+    fn degree(&self, idx: usize) -> usize;
+
+    /// Calls f on each neighbour of the vertex with given index without copying the neighbourhood.
+    /// Panics if the index does not exist in the graph.
+    fn for_each_neighbour(&self, idx: usize, f: &mut dyn FnMut(usize)) {
+        self.neighbours_idx(idx).unwrap().into_iter().for_each(f)
+    }
+
+    /// Returns an iterator on all vertices indices.
+    fn vertices(&self) -> std::ops::Range<usize> {
+        0..self.get_num_of_vertices()
+    }
+
+    /// Returns sorted list of s vertices with highest degree.
+    fn highest_degree_vertices(&self, s: usize) -> Vec<usize> {
+        let mut vertices = self.vertices().collect::<Vec<usize>>();
+        vertices.sort_by_key(|v| std::cmp::Reverse(self.degree(*v)));
+        vertices[0..s].to_vec()
+    }
 }
 
-/// A structure representing a graph that can be easily
-/// tranformed into a json file.
+impl<G: GraphOps + ?Sized> GraphOps for &G {
+    fn get_num_of_vertices(&self) -> usize {
+        (**self).get_num_of_vertices()
+    }
+
+    fn get_num_of_edges(&self) -> usize {
+        (**self).get_num_of_edges()
+    }
+
+    fn neighbours_idx(&self, idx: usize) -> Result<HashSet<usize>, &str> {
+        (**self).neighbours_idx(idx)
+    }
+
+    fn degree(&self, idx: usize) -> usize {
+        (**self).degree(idx)
+    }
+
+    fn for_each_neighbour(&self, idx: usize, f: &mut dyn FnMut(usize)) {
+        (**self).for_each_neighbour(idx, f)
+    }
+
+    fn highest_degree_vertices(&self, s: usize) -> Vec<usize> {
+        (**self).highest_degree_vertices(s)
+    }
+}
+
+impl<G: GraphOps + ?Sized> GraphOps for Arc<G> {
+    fn get_num_of_vertices(&self) -> usize {
+        (**self).get_num_of_vertices()
+    }
+
+    fn get_num_of_edges(&self) -> usize {
+        (**self).get_num_of_edges()
+    }
+
+    fn neighbours_idx(&self, idx: usize) -> Result<HashSet<usize>, &str> {
+        (**self).neighbours_idx(idx)
+    }
+
+    fn degree(&self, idx: usize) -> usize {
+        (**self).degree(idx)
+    }
+
+    fn for_each_neighbour(&self, idx: usize, f: &mut dyn FnMut(usize)) {
+        (**self).for_each_neighbour(idx, f)
+    }
+
+    fn highest_degree_vertices(&self, s: usize) -> Vec<usize> {
+        (**self).highest_degree_vertices(s)
+    }
+}
+
+impl GraphOps for Graph {
+    fn get_num_of_vertices(&self) -> usize {
+        self.num_of_vertices
+    }
+
+    fn get_num_of_edges(&self) -> usize {
+        self.num_of_edges
+    }
+
+    fn neighbours_idx(&self, idx: usize) -> Result<HashSet<usize>, &str> {
+        Graph::neighbours_idx(self, idx)
+    }
+
+    fn degree(&self, idx: usize) -> usize {
+        self.neighbours[idx].len()
+    }
+
+    fn for_each_neighbour(&self, idx: usize, f: &mut dyn FnMut(usize)) {
+        self.neighbours[idx].iter().for_each(|neighbour| f(*neighbour))
+    }
+
+    fn highest_degree_vertices(&self, s: usize) -> Vec<usize> {
+        Graph::highest_degree_vertices(self, s)
+    }
+}
+
+/// A vertex, given by its name or index, which does not exist in the graph.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UnknownVertex {
+    /// There is no vertex with the name.
+    Name(String),
+    /// There is no vertex with the index.
+    Index(usize),
+}
+
+impl std::fmt::Display for UnknownVertex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UnknownVertex::Name(name) => write!(f, "no vertex is named {}", name),
+            UnknownVertex::Index(idx) => write!(f, "no vertex has index {}", idx),
+        }
+    }
+}
+
+impl std::error::Error for UnknownVertex {}
+
+/// The reason an edge was not added to the graph.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EdgeError {
+    /// The edge is already in the graph.
+    AlreadyExists,
+    /// Both ends of the edge are the same vertex.
+    SelfLoop,
+    /// There is no vertex with the name.
+    UnknownVertex(String),
+    /// There is no vertex with the index.
+    IndexOutOfRange(usize),
+}
+
+impl std::fmt::Display for EdgeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EdgeError::AlreadyExists => write!(f, "the edge already exists"),
+            EdgeError::SelfLoop => write!(f, "the ends of the edge are the same vertex"),
+            EdgeError::UnknownVertex(name) => write!(f, "no vertex is named {}", name),
+            EdgeError::IndexOutOfRange(idx) => write!(f, "no vertex has index {}", idx),
+        }
+    }
+}
+
+impl std::error::Error for EdgeError {}
+
+/// Serializes the neighbourhoods as comma separated json arrays.
+fn neighbourhoods_to_json(neighbourhoods: &[NeighbourSet]) -> serde_json::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    for (idx, set) in neighbourhoods.iter().enumerate() {
+        if idx > 0 {
+            bytes.push(b',');
+        }
+        serde_json::to_writer(&mut bytes, set)?;
+    }
+    Ok(bytes)
+}
+
+/// A single line of the edge log.
+/// Vertices are referred to by names, so that a log can be folded without any other state.
 #[derive(Serialize, Deserialize)]
-struct GraphJson {
-    num_of_vertices: usize,
-    num_of_edges: usize,
-    neighbours: Vec<Vec<usize>>,
-    names: Vec<String>,
+#[serde(rename_all = "snake_case")]
+enum EdgeEvent {
+    Vertex(String),
+    Edge(String, String),
+}
+
+/// An append-only log of vertex and edge insertions stored as newline delimited json.
+/// Each insertion is written as a separate line, so the log can be extended incrementally
+/// instead of rewriting the whole graph, and read back with Graph::read_from_edge_log.
+/// # Examples
+/// ```
+/// use labisu::graphs::{EdgeLog, Graph};
+/// let mut log = EdgeLog::create("log.ndjson").unwrap();
+/// log.log_vertex("vertex_0").unwrap();
+/// log.log_vertex("vertex_1").unwrap();
+/// log.flush().unwrap();
+/// let mut log = EdgeLog::append("log.ndjson").unwrap();
+/// log.log_edge("vertex_0", "vertex_1").unwrap();
+/// log.flush().unwrap();
+/// let read = Graph::read_from_edge_log("log.ndjson").unwrap();
+/// assert_eq!(2, read.get_num_of_vertices());
+/// assert_eq!(1, read.get_num_of_edges());
+/// ```
+pub struct EdgeLog {
+    writer: BufWriter<File>,
+}
+
+impl EdgeLog {
+    /// Creates a new empty log with given filename, truncating an existing file.
+    pub fn create(filename: &str) -> std::io::Result<EdgeLog> {
+        Ok(EdgeLog { writer: BufWriter::new(File::create(filename)?) })
+    }
+
+    /// Opens a log with given filename for appending, creating it if it does not exist.
+    pub fn append(filename: &str) -> std::io::Result<EdgeLog> {
+        let file = OpenOptions::new().create(true).append(true).open(filename)?;
+        Ok(EdgeLog { writer: BufWriter::new(file) })
+    }
+
+    /// Appends an insertion of the vertex with given name.
+    pub fn log_vertex(&mut self, name: &str) -> std::io::Result<()> {
+        self.log(&EdgeEvent::Vertex(name.to_owned()))
+    }
+
+    /// Appends an insertion of the edge between vertices with given names.
+    pub fn log_edge(&mut self, from: &str, to: &str) -> std::io::Result<()> {
+        self.log(&EdgeEvent::Edge(from.to_owned(), to.to_owned()))
+    }
+
+    /// Writes all buffered events to the file.
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+
+    fn log(&mut self, event: &EdgeEvent) -> std::io::Result<()> {
+        write_event(&mut self.writer, event)
+    }
+}
+
+/// Writes an event of the edge log as a single line.
+fn write_event<W: Write>(writer: &mut W, event: &EdgeEvent) -> std::io::Result<()> {
+    serde_json::to_writer(&mut *writer, event)?;
+    writer.write_all(b"\n")
 }
 
 impl Graph {    
@@ -78,7 +400,20 @@ impl Graph {
     /// assert_eq!(None, e1.idx_to_name(1));
     /// ```
     pub fn idx_to_name(&self, idx: usize) -> Option<String> {
-        self.idx_to_name_map.get(idx).cloned()
+        self.idx_to_name_map.get(idx).map(|name| name.to_string())
+    }
+
+    /// Returns the name of the vertex with given index borrowed from the graph, so no allocation is made.
+    /// The return type is Option which is Some if the index exists in the graph and None otherwise.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let e1 = Graph::from_names(vec!["vertex_0".to_string()]);
+    /// assert_eq!(Some("vertex_0"), e1.idx_to_name_ref(0));
+    /// assert_eq!(None, e1.idx_to_name_ref(1));
+    /// ```
+    pub fn idx_to_name_ref(&self, idx: usize) -> Option<&str> {
+        self.idx_to_name_map.get(idx).map(|name| name.as_ref())
     }
 
     /// Returns the index of the vertex with given name.
@@ -94,6 +429,42 @@ impl Graph {
         self.name_to_idx_map.get(name).cloned()
     }
 
+    /// Returns the names of a set of vertices given by indices, e.g. a side of a bipartite subgraph found by find_bipartite.
+    /// If one of the indices does not exist in the graph, then it is returned as an Err value.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::{Graph, UnknownVertex};
+    /// use std::collections::HashSet;
+    /// let k3 = Graph::complete(3);
+    /// assert_eq!(Ok(HashSet::from(["vertex_0".to_string(), "vertex_2".to_string()])), k3.names_of(&HashSet::from([0, 2])));
+    /// assert_eq!(Err(UnknownVertex::Index(3)), k3.names_of(&HashSet::from([3])));
+    /// ```
+    pub fn names_of(&self, indices: &HashSet<usize>) -> Result<HashSet<String>, UnknownVertex> {
+        indices
+        .iter()
+        .map(|idx| self.idx_to_name(*idx).ok_or(UnknownVertex::Index(*idx)))
+        .collect()
+    }
+
+    /// Returns the indices of a set of vertices given by names.
+    /// If one of the names does not exist in the graph, then it is returned as an Err value.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::{Graph, UnknownVertex};
+    /// use std::collections::HashSet;
+    /// let k3 = Graph::complete(3);
+    /// assert_eq!(Ok(HashSet::from([1])), k3.indices_of(&HashSet::from(["vertex_1".to_string()])));
+    /// let unknown = k3.indices_of(&HashSet::from(["vertex_1".to_string(), "vertex_7".to_string()]));
+    /// assert_eq!(Err(UnknownVertex::Name("vertex_7".to_string())), unknown);
+    /// assert_eq!("no vertex is named vertex_7", unknown.unwrap_err().to_string());
+    /// ```
+    pub fn indices_of(&self, names: &HashSet<String>) -> Result<HashSet<usize>, UnknownVertex> {
+        names
+        .iter()
+        .map(|name| self.name_to_idx(name).ok_or_else(|| UnknownVertex::Name(name.to_owned())))
+        .collect()
+    }
+
     /// Creates an empty graph. The graph has 0 vertices, empty adjacency list and empty mapping.
     /// # Examples
     /// ```
@@ -105,12 +476,71 @@ impl Graph {
         Graph {
             num_of_vertices: 0,
             num_of_edges: 0,
-            neighbours: vec![],
-            idx_to_name_map: vec![],
-            name_to_idx_map: HashMap::new(),
+            neighbours: Chunked::default(),
+            idx_to_name_map: Chunked::default(),
+            name_to_idx_map: NameMap::default(),
+            degree_hint: 0,
+            hinted_vertices: 0,
         }
     }
 
+    /// Creates an empty graph with space reserved for given numbers of vertices and edges (see reserve).
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let mut graph = Graph::with_capacity(1000, 5000);
+    /// graph.add_vertex("vertex_0");
+    /// assert_eq!(1, graph.get_num_of_vertices());
+    /// assert!(graph.vertex_capacity() >= 1000);
+    /// ```
+    pub fn with_capacity(num_of_vertices: usize, num_of_edges: usize) -> Graph {
+        let mut graph = Graph::empty();
+        graph.reserve(num_of_vertices, num_of_edges);
+        graph
+    }
+
+    /// Reserves space for at least additional_vertices more vertices.
+    /// The neighbour sets of the next additional_vertices vertices are created with the space for their average degree,
+    /// i.e. 2 * additional_edges / additional_vertices, and the neighbour sets of the vertices added beyond them start empty,
+    /// so additional_edges should be 0 if most of the new vertices are going to be leaves, like the pages found by a crawler.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let mut k3 = Graph::complete(3);
+    /// k3.reserve(100, 200);
+    /// assert!(k3.vertex_capacity() >= 103);
+    /// ```
+    pub fn reserve(&mut self, additional_vertices: usize, additional_edges: usize) {
+        self.neighbours.reserve(additional_vertices);
+        self.idx_to_name_map.reserve(additional_vertices);
+        self.name_to_idx_map.reserve(additional_vertices);
+        self.degree_hint = (2 * additional_edges).checked_div(additional_vertices).unwrap_or(0);
+        self.hinted_vertices = self.num_of_vertices + additional_vertices;
+    }
+
+    /// Shrinks the capacity of all internal structures as much as possible,
+    /// e.g. to reclaim memory after reduce_to_dense removed most of the vertices.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let mut graph = Graph::with_capacity(1000, 5000);
+    /// graph.add_vertex("vertex_0");
+    /// graph.shrink_to_fit();
+    /// assert!(graph.vertex_capacity() < 1000);
+    /// ```
+    pub fn shrink_to_fit(&mut self) {
+        self.neighbours.iter_mut().for_each(|neighbours| neighbours.shrink_to_fit());
+        self.neighbours.shrink_to_fit();
+        self.idx_to_name_map.shrink_to_fit();
+        self.name_to_idx_map.shrink_to_fit();
+        self.hinted_vertices = 0;
+    }
+
+    /// Returns the number of vertices the graph can hold without reallocating.
+    pub fn vertex_capacity(&self) -> usize {
+        self.neighbours.capacity()
+    }
+
     /// Creates a random graph with given number of vertices.
     /// Each edge has a probability of ppb to be present.
     /// i-th vertex is named "vertex_i".
@@ -173,6 +603,45 @@ impl Graph {
         graph
     }
 
+    /// Creates a graph with given degrees with the Havel–Hakimi algorithm, which repeatedly connects a vertex
+    /// of the largest remaining degree to the vertices of the next largest remaining degrees.
+    /// i-th vertex is named "vertex_i" and has degree degrees[i].
+    /// Returns an Err value if the sequence is not graphical (see combinatorics::is_graphical).
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::{Graph, GraphOps};
+    /// let graph = Graph::from_degree_sequence(&[3, 1, 2, 2, 1, 1]).unwrap();
+    /// assert_eq!(vec![3, 1, 2, 2, 1, 1], graph.vertices().map(|v| graph.degree(v)).collect::<Vec<usize>>());
+    /// assert_eq!(vec![3, 2, 2, 1, 1, 1], graph.degree_sequence());
+    /// assert!(Graph::from_degree_sequence(&[3, 3, 1, 1]).is_err());
+    /// assert_eq!(0, Graph::from_degree_sequence(&[]).unwrap().get_num_of_vertices());
+    /// ```
+    pub fn from_degree_sequence(degrees: &[usize]) -> Result<Graph, &'static str> {
+        if degrees.is_empty() {
+            return Ok(Graph::empty())
+        }
+        if !is_graphical(degrees) {
+            return Err("The degree sequence is not graphical.")
+        }
+        let mut graph = Graph::from_names((0..degrees.len()).map(|i| format!("vertex_{}", i)).collect());
+        let mut remaining = degrees.to_vec();
+        let mut order = (0..degrees.len()).collect::<Vec<usize>>();
+        loop {
+            order.sort_unstable_by(|a, b| remaining[*b].cmp(&remaining[*a]));
+            let v = order[0];
+            let degree = remaining[v];
+            if degree == 0 {
+                break
+            }
+            remaining[v] = 0;
+            for u in &order[1..=degree] {
+                remaining[*u] -= 1;
+                graph.add_edge_idx(v, *u);
+            }
+        }
+        Ok(graph)
+    }
+
     /// Creates a complete graph with given number of vertices.
     /// # Examples
     /// ```
@@ -186,11 +655,10 @@ impl Graph {
         let neighbours =
             (0..num_of_vertices)
             .into_iter()
-            .map(|i|  (0..num_of_vertices).filter(|j| i != *j).collect::<HashSet<usize>>())
+            .map(|i|  (0..num_of_vertices).filter(|j| i != *j).collect::<NeighbourSet>())
             .collect();
 
-        let idx_to_name_map = (0..num_of_vertices).into_iter().map(|i| format!("vertex_{}", i)).collect();
-        let name_to_idx_map = (0..num_of_vertices).into_iter().map(|i| (format!("vertex_{}", i), i)).collect();
+        let (idx_to_name_map, name_to_idx_map) = intern_names((0..num_of_vertices).map(|i| format!("vertex_{}", i)).collect());
 
         Graph {
             num_of_vertices,
@@ -198,10 +666,161 @@ impl Graph {
             neighbours,
             idx_to_name_map,
             name_to_idx_map,
+            degree_hint: 0,
+            hinted_vertices: 0,
         }
     }
 
 
+    /// Creates a path with given number of vertices, in which vertex i is adjacent to vertex i + 1.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let p4 = Graph::path(4);
+    /// assert_eq!(3, p4.get_num_of_edges());
+    /// assert!(p4.is_bipartite());
+    /// ```
+    pub fn path(num_of_vertices: usize) -> Graph {
+        let mut graph = Graph::from_names((0..num_of_vertices).map(|i| format!("vertex_{}", i)).collect());
+        for i in 1..num_of_vertices {
+            graph.add_edge_idx(i - 1, i);
+        }
+        graph
+    }
+
+    /// Creates a cycle with given number of vertices.
+    /// For less than 3 vertices it is the same as a path.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// assert_eq!(5, Graph::cycle(5).get_num_of_edges());
+    /// assert!(Graph::cycle(6).is_bipartite());
+    /// assert!(!Graph::cycle(5).is_bipartite());
+    /// ```
+    pub fn cycle(num_of_vertices: usize) -> Graph {
+        let mut graph = Graph::path(num_of_vertices);
+        if num_of_vertices > 2 {
+            graph.add_edge_idx(num_of_vertices - 1, 0);
+        }
+        graph
+    }
+
+    /// Creates a grid with given number of rows and columns.
+    /// Vertex in row i and column j is named "vertex_i_j" and has index i * columns + j.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let grid = Graph::grid(3, 4);
+    /// assert_eq!(12, grid.get_num_of_vertices());
+    /// assert_eq!(17, grid.get_num_of_edges());
+    /// assert_eq!(Some(5), grid.name_to_idx("vertex_1_1"));
+    /// assert!(grid.is_bipartite());
+    /// ```
+    pub fn grid(rows: usize, columns: usize) -> Graph {
+        let names = 
+            (0..rows)
+            .flat_map(|i| (0..columns).map(move |j| format!("vertex_{}_{}", i, j)))
+            .collect();
+        let mut graph = Graph::from_names(names);
+        for i in 0..rows {
+            for j in 0..columns {
+                let idx = i * columns + j;
+                if j + 1 < columns { graph.add_edge_idx(idx, idx + 1); }
+                if i + 1 < rows    { graph.add_edge_idx(idx, idx + columns); }
+            }
+        }
+        graph
+    }
+
+    /// Creates a hypercube of given dimension.
+    /// Vertices are named by their binary representation and two of them are adjacent if they differ on one bit.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let q3 = Graph::hypercube(3);
+    /// assert_eq!(8, q3.get_num_of_vertices());
+    /// assert_eq!(12, q3.get_num_of_edges());
+    /// assert_eq!(Some(5), q3.name_to_idx("101"));
+    /// assert!(q3.is_bipartite());
+    /// ```
+    pub fn hypercube(dimension: usize) -> Graph {
+        let num_of_vertices = 1 << dimension;
+        let names = 
+            (0..num_of_vertices)
+            .map(|i| format!("{:0width$b}", i, width = dimension))
+            .collect();
+        let mut graph = Graph::from_names(names);
+        for i in 0..num_of_vertices {
+            for bit in 0..dimension {
+                graph.add_edge_idx(i, i ^ (1 << bit));
+            }
+        }
+        graph
+    }
+
+    /// Creates the Petersen graph.
+    /// Vertices 0..5 form the outer cycle, vertices 5..10 the inner pentagram and vertex i is adjacent to vertex i + 5.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// use labisu::graphs::GraphOps;
+    /// let petersen = Graph::petersen();
+    /// assert_eq!(10, petersen.get_num_of_vertices());
+    /// assert_eq!(15, petersen.get_num_of_edges());
+    /// assert!(petersen.vertices().all(|v| petersen.degree(v) == 3));
+    /// assert!(!petersen.is_bipartite());
+    /// ```
+    pub fn petersen() -> Graph {
+        let mut graph = Graph::from_names((0..10).map(|i| format!("vertex_{}", i)).collect());
+        for i in 0..5 {
+            graph.add_edge_idx(i, (i + 1) % 5);
+            graph.add_edge_idx(5 + i, 5 + (i + 2) % 5);
+            graph.add_edge_idx(i, i + 5);
+        }
+        graph
+    }
+
+    /// Creates a complete multipartite graph with parts of given sizes.
+    /// The parts consist of consecutive vertices and two vertices are adjacent if they belong to different parts.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let k233 = Graph::complete_multipartite(&[2, 3, 3]);
+    /// assert_eq!(8, k233.get_num_of_vertices());
+    /// assert_eq!(21, k233.get_num_of_edges());
+    /// assert!(!k233.neighbours_idx(0).unwrap().contains(&1));
+    /// assert!(Graph::complete_multipartite(&[3, 4]).is_bipartite());
+    /// ```
+    pub fn complete_multipartite(sizes: &[usize]) -> Graph {
+        let num_of_vertices = sizes.iter().sum();
+        let part = sizes.iter().enumerate().flat_map(|(i, size)| std::iter::repeat_n(i, *size)).collect::<Vec<usize>>();
+        let mut graph = Graph::from_names((0..num_of_vertices).map(|i| format!("vertex_{}", i)).collect());
+        for i in 0..num_of_vertices {
+            for j in (i + 1)..num_of_vertices {
+                if part[i] != part[j] {
+                    graph.add_edge_idx(i, j);
+                }
+            }
+        }
+        graph
+    }
+
+    /// Creates the Turan graph T(n, r), i.e. the complete r-partite graph on n vertices with parts as equal as possible,
+    /// which is the largest graph on n vertices without a clique on r + 1 vertices.
+    /// The larger parts come first. Panics if r is zero.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let t = Graph::turan(10, 3);
+    /// assert_eq!(10, t.get_num_of_vertices());
+    /// assert_eq!(33, t.get_num_of_edges());
+    /// assert_eq!(Graph::complete(5).get_num_of_edges(), Graph::turan(5, 7).get_num_of_edges());
+    /// ```
+    pub fn turan(num_of_vertices: usize, r: usize) -> Graph {
+        let sizes = (0..r).map(|i| num_of_vertices / r + usize::from(i < num_of_vertices % r)).collect::<Vec<usize>>();
+        Graph::complete_multipartite(&sizes)
+    }
+
     /// Creates a graph with no edges based on a vector of vertices names.
     /// # Examples
     /// ```
@@ -212,16 +831,16 @@ impl Graph {
     /// assert_eq!("vertex_1", e2.idx_to_name(1).unwrap());
     /// ```
     pub fn from_names(names: Vec<String>) -> Graph {
-        let mut name_to_idx_map = HashMap::new();
-        names.iter().enumerate().for_each(|(idx, name)| {
-            name_to_idx_map.insert(String::from(name), idx);
-        });
+        let num_of_vertices = names.len();
+        let (idx_to_name_map, name_to_idx_map) = intern_names(names);
         Graph {
-            num_of_vertices: names.len(),
+            num_of_vertices,
             num_of_edges: 0,
-            neighbours: vec![HashSet::new(); names.len()],
-            idx_to_name_map: names,
+            neighbours: std::iter::repeat_with(NeighbourSet::default).take(num_of_vertices).collect(),
+            idx_to_name_map,
             name_to_idx_map,
+            degree_hint: 0,
+            hinted_vertices: 0,
         }
     }
 
@@ -239,10 +858,12 @@ impl Graph {
     /// ```
     pub fn add_vertex(&mut self, name: &str) {
         if !self.name_to_idx_map.contains_key(name) {
-            self.neighbours.push(HashSet::new());
+            let name: Arc<str> = Arc::from(name);
+            let capacity = if self.num_of_vertices < self.hinted_vertices { self.degree_hint } else { 0 };
+            self.neighbours.push(NeighbourSet::with_capacity_and_hasher(capacity, BuildHasher::default()));
             self.name_to_idx_map
-                .insert(String::from(name), self.num_of_vertices);
-            self.idx_to_name_map.push(String::from(name));
+                .insert(Arc::clone(&name), self.num_of_vertices);
+            self.idx_to_name_map.push(name);
             self.num_of_vertices += 1;
         }
     }
@@ -262,34 +883,289 @@ impl Graph {
     /// assert_eq!(k5.neighbours_idx(0).unwrap(), HashSet::from([1, 2, 3]));
     /// ```
     pub fn remove_vertex(&mut self, name: &str) -> bool {
-        if let Some(idx) = self.name_to_idx(name) {
-            self.num_of_vertices -= 1;
-            self.num_of_edges -= self.neighbours[idx].len();
-
-            self.neighbours.remove(idx);
-            self.neighbours =
-                self.neighbours
-                .clone()
-                .into_iter()
-                .map(|mut neighbours| { 
-                    neighbours.remove(&idx); 
-                    neighbours.into_iter().map(|neighbour| if neighbour > idx { neighbour - 1 } else { neighbour }).collect() 
-                })
-                .collect();
-
-            self.idx_to_name_map.remove(idx);
-            self.name_to_idx_map.remove(name);
-
-            self.name_to_idx_map =
-                self.name_to_idx_map
-                .clone()
-                .into_iter()
-                .map(|(s, i)| if i > idx { (s, i - 1) } else { (s, i) })
-                .collect();
-            
-            return true 
-        } 
-        false
+        self.remove_vertex_relabeled(name).is_some()
+    }
+
+    /// Removes the vertex with given name like remove_vertex, in time linear in the size of the graph.
+    /// Returns the relabeling of the remaining vertices, or None if there is no such vertex.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let mut k5 = Graph::complete(5);
+    /// let relabeling = k5.remove_vertex_relabeled("vertex_2").unwrap();
+    /// assert_eq!(Some(2), relabeling.new_index(3));
+    /// assert!(k5.remove_vertex_relabeled("vertex_2").is_none());
+    /// ```
+    pub fn remove_vertex_relabeled(&mut self, name: &str) -> Option<Relabeling> {
+        let idx = self.name_to_idx(name)?;
+        let keep = self.vertices().map(|v| v != idx).collect::<Vec<bool>>();
+        Some(self.retain_vertices_relabeled(&keep))
+    }
+
+    /// Keeps only the vertices marked in keep, renumbering them in the original order, in time linear in the size of the graph.
+    /// Returns the relabeling of the kept vertices.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let mut c4 = Graph::cycle(4);
+    /// let relabeling = c4.retain_vertices_relabeled(&[true, false, true, true]);
+    /// assert_eq!(vec![0, 2, 3], relabeling.old_indices());
+    /// assert_eq!(2, c4.get_num_of_edges());
+    /// ```
+    pub fn retain_vertices_relabeled(&mut self, keep: &[bool]) -> Relabeling {
+        let num_of_vertices = self.num_of_vertices;
+        Relabeling::from_order(num_of_vertices, self.retain_vertices(keep))
+    }
+
+    /// Keeps only the vertices marked in keep, renumbering them in the original order, in time linear in the size of the graph.
+    /// Returns the original indices of the kept vertices.
+    pub(crate) fn retain_vertices(&mut self, keep: &[bool]) -> Vec<usize> {
+        let kept = self.vertices().filter(|v| keep[*v]).collect::<Vec<usize>>();
+        if kept.len() == self.num_of_vertices {
+            return kept
+        }
+        let mut new_idx = vec![0; self.num_of_vertices];
+        kept.iter().enumerate().for_each(|(new, old)| new_idx[*old] = new);
+
+        let neighbours = std::mem::take(&mut self.neighbours);
+        self.neighbours = 
+            kept
+            .iter()
+            .map(|old| neighbours[*old].iter().filter(|u| keep[**u]).map(|u| new_idx[*u]).collect())
+            .collect();
+        let names = std::mem::take(&mut self.idx_to_name_map);
+        self.idx_to_name_map = kept.iter().map(|old| Arc::clone(&names[*old])).collect();
+        self.name_to_idx_map = 
+            self.idx_to_name_map
+            .iter()
+            .enumerate()
+            .map(|(idx, name)| (Arc::clone(name), idx))
+            .collect();
+        self.num_of_vertices = kept.len();
+        self.num_of_edges = self.neighbours.iter().map(|neighbours| neighbours.len()).sum::<usize>() / 2;
+        kept
+    }
+
+    /// Marks the vertices of the min_degree-core, i.e. the vertices left after repeatedly removing vertices of degree lower than min_degree.
+    pub(crate) fn core(&self, min_degree: usize) -> Vec<bool> {
+        let mut degrees = self.neighbours.iter().map(|neighbours| neighbours.len()).collect::<Vec<usize>>();
+        let mut keep = degrees.iter().map(|degree| *degree >= min_degree).collect::<Vec<bool>>();
+        let mut to_remove = self.vertices().filter(|v| !keep[*v]).collect::<Vec<usize>>();
+        while let Some(v) = to_remove.pop() {
+            for u in &self.neighbours[v] {
+                degrees[*u] -= 1;
+                if keep[*u] && degrees[*u] < min_degree {
+                    keep[*u] = false;
+                    to_remove.push(*u);
+                }
+            }
+        }
+        keep
+    }
+
+    /// Returns the ego network of a vertex, i.e. the subgraph induced by the vertices at distance at most radius from the center.
+    /// Vertices keep their names and their relative order, so the center is found by name in the subgraph.
+    /// If the center does not exist in the graph it returns an Err value.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let grid = Graph::grid(5, 5);
+    /// let ego = grid.ego_network(grid.name_to_idx("vertex_2_2").unwrap(), 1).unwrap();
+    /// assert_eq!(5, ego.get_num_of_vertices());
+    /// assert_eq!(4, ego.get_num_of_edges());
+    /// assert!(ego.contains_vertex("vertex_1_2"));
+    /// assert_eq!(13, grid.ego_network(12, 2).unwrap().get_num_of_vertices());
+    /// assert!(grid.ego_network(25, 1).is_err());
+    /// ```
+    pub fn ego_network(&self, center: usize, radius: usize) -> Result<Graph, &'static str> {
+        if center >= self.num_of_vertices {
+            return Err("Index does not exist in the graph.")
+        }
+        let mut in_ball = vec![false; self.num_of_vertices];
+        in_ball[center] = true;
+        let mut frontier = vec![center];
+        for _ in 0..radius {
+            let mut next = vec![];
+            for v in frontier {
+                for u in &self.neighbours[v] {
+                    if !in_ball[*u] {
+                        in_ball[*u] = true;
+                        next.push(*u);
+                    }
+                }
+            }
+            if next.is_empty() {
+                break
+            }
+            frontier = next;
+        }
+        // only the ball is copied, so that the ego network of a vertex of a large graph is cheap
+        let ball = self.vertices().filter(|v| in_ball[*v]).collect::<Vec<usize>>();
+        self.induced_subgraph(&ball)
+    }
+
+    /// Returns the subgraph induced by given vertices, where the vertex vertices[i] gets index i and keeps its name.
+    /// Only the subgraph is copied, so it is cheap for a small part of a large graph.
+    /// If a vertex does not exist in the graph or is repeated it returns an Err value.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let c5 = Graph::cycle(5);
+    /// let p3 = c5.induced_subgraph(&[2, 0, 1]).unwrap();
+    /// assert_eq!(2, p3.get_num_of_edges());
+    /// assert_eq!(Some(2), p3.name_to_idx("vertex_1"));
+    /// assert!(c5.induced_subgraph(&[0, 0]).is_err());
+    /// assert!(c5.induced_subgraph(&[5]).is_err());
+    /// ```
+    pub fn induced_subgraph(&self, vertices: &[usize]) -> Result<Graph, &'static str> {
+        let mut new_idx = HashMap::with_capacity(vertices.len());
+        for (new, old) in vertices.iter().enumerate() {
+            if *old >= self.num_of_vertices {
+                return Err("Index does not exist in the graph.")
+            }
+            if new_idx.insert(*old, new).is_some() {
+                return Err("A vertex is repeated.")
+            }
+        }
+        let mut subgraph = Graph::from_names(vertices.iter().map(|old| self.idx_to_name_map[*old].to_string()).collect());
+        for (new, old) in vertices.iter().enumerate() {
+            for u in &self.neighbours[*old] {
+                if let Some(u) = new_idx.get(u) {
+                    subgraph.add_edge_idx(new, *u);
+                }
+            }
+        }
+        Ok(subgraph)
+    }
+
+    /// Removes all vertices without neighbours.
+    /// Returns the number of removed vertices.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let mut g = Graph::from_names(vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+    /// g.add_edge("a", "c");
+    /// assert_eq!(1, g.remove_isolated());
+    /// assert_eq!(Some(1), g.name_to_idx("c"));
+    /// assert_eq!(1, g.get_num_of_edges());
+    /// ```
+    pub fn remove_isolated(&mut self) -> usize {
+        let relabeling = self.remove_isolated_relabeled();
+        relabeling.old_len() - relabeling.new_len()
+    }
+
+    /// Removes all vertices without neighbours like remove_isolated and returns the relabeling of the remaining vertices.
+    pub fn remove_isolated_relabeled(&mut self) -> Relabeling {
+        let keep = self.neighbours.iter().map(|neighbours| !neighbours.is_empty()).collect::<Vec<bool>>();
+        self.retain_vertices_relabeled(&keep)
+    }
+
+    /// Removes all vertices of degree one. If recursive, then the vertices which become leaves are removed as well, 
+    /// until no leaves are left, i.e. trees hanging off the graph and components which are trees are removed entirely
+    /// (vertices isolated in the first place are not removed).
+    /// Returns the number of removed vertices.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let mut g = Graph::cycle(4);
+    /// g.add_vertex("tail_0");
+    /// g.add_vertex("tail_1");
+    /// g.add_edge("vertex_0", "tail_0");
+    /// g.add_edge("tail_0", "tail_1");
+    /// assert_eq!(1, g.clone().remove_leaves(false));
+    /// assert_eq!(2, g.remove_leaves(true));
+    /// assert_eq!(4, g.get_num_of_edges());
+    /// ```
+    pub fn remove_leaves(&mut self, recursive: bool) -> usize {
+        let relabeling = self.remove_leaves_relabeled(recursive);
+        relabeling.old_len() - relabeling.new_len()
+    }
+
+    /// Removes the vertices of degree one like remove_leaves and returns the relabeling of the remaining vertices.
+    pub fn remove_leaves_relabeled(&mut self, recursive: bool) -> Relabeling {
+        let keep = 
+            if recursive {
+                let core = self.core(2);
+                self.vertices().map(|v| core[v] || self.neighbours[v].is_empty()).collect::<Vec<bool>>()
+            } else {
+                self.neighbours.iter().map(|neighbours| neighbours.len() != 1).collect::<Vec<bool>>()
+            };
+        self.retain_vertices_relabeled(&keep)
+    }
+
+    /// Removes vertices of degree lower than min_degree until there are none left, leaving the min_degree-core of the graph.
+    /// Returns the number of removed vertices.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let mut g = Graph::complete(4);
+    /// g.add_vertex("tail_0");
+    /// g.add_vertex("tail_1");
+    /// g.add_edge("vertex_0", "tail_0");
+    /// g.add_edge("tail_0", "tail_1");
+    /// assert_eq!(2, g.prune_below_degree(3));
+    /// assert_eq!(4, g.get_num_of_vertices());
+    /// assert_eq!(6, g.get_num_of_edges());
+    /// ```
+    pub fn prune_below_degree(&mut self, min_degree: usize) -> usize {
+        let relabeling = self.prune_below_degree_relabeled(min_degree);
+        relabeling.old_len() - relabeling.new_len()
+    }
+
+    /// Leaves the min_degree-core of the graph like prune_below_degree and returns the relabeling of the remaining vertices.
+    pub fn prune_below_degree_relabeled(&mut self, min_degree: usize) -> Relabeling {
+        let keep = self.core(min_degree);
+        self.retain_vertices_relabeled(&keep)
+    }
+
+    /// Contracts the edge between two vertices given by indices: the vertices are merged into one, adjacent to all neighbours of both,
+    /// named "u+v" after the names u and v of the vertices. The vertices after the larger index are renumbered one lower.
+    /// Returns the index of the merged vertex, i.e. the smaller of the indices, or an Err value if there is no such edge.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::{Graph, GraphOps};
+    /// let mut c4 = Graph::cycle(4);
+    /// assert_eq!(Ok(0), c4.contract_edge(1, 0));
+    /// assert_eq!(3, c4.get_num_of_vertices());
+    /// assert_eq!(3, c4.get_num_of_edges());
+    /// assert_eq!(Some(0), c4.name_to_idx("vertex_0+vertex_1"));
+    /// assert_eq!(Some(1), c4.name_to_idx("vertex_2"));
+    /// assert!(c4.contract_edge(0, 0).is_err());
+    /// ```
+    pub fn contract_edge(&mut self, u: usize, v: usize) -> Result<usize, &'static str> {
+        let (u, v) = (u.min(v), u.max(v));
+        self.contract_edge_with(u, v, |u, v| format!("{}+{}", u, v))
+    }
+
+    /// Contracts the edge between two vertices given by indices like contract_edge, but the merged vertex is named join(name of u, name of v),
+    /// and it gets the smaller of the indices. Returns an Err value if there is no such edge or the new name is already taken by another vertex.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let mut p3 = Graph::path(3);
+    /// assert_eq!(Ok(1), p3.contract_edge_with(2, 1, |_, v| v.to_owned()));
+    /// assert_eq!(Some(1), p3.name_to_idx("vertex_1"));
+    /// assert!(p3.contract_edge_with(0, 1, |_, _| "vertex_1".to_owned()).is_ok());
+    /// let mut p3 = Graph::path(3);
+    /// assert!(p3.contract_edge_with(0, 1, |_, _| "vertex_2".to_owned()).is_err());
+    /// ```
+    pub fn contract_edge_with<F: Fn(&str, &str) -> String>(&mut self, u: usize, v: usize, join: F) -> Result<usize, &'static str> {
+        if u >= self.num_of_vertices || v >= self.num_of_vertices || !self.neighbours[u].contains(&v) {
+            return Err("There is no edge between the vertices.")
+        }
+        let name = join(&self.idx_to_name_map[u], &self.idx_to_name_map[v]);
+        if self.name_to_idx_map.get(name.as_str()).is_some_and(|idx| *idx != u && *idx != v) {
+            return Err("The name of the merged vertex is already taken.")
+        }
+        let (kept, removed) = (u.min(v), u.max(v));
+        let moved = self.neighbours[removed].iter().copied().filter(|w| *w != kept).collect::<Vec<usize>>();
+        for w in moved {
+            self.add_edge_idx(kept, w);
+        }
+        self.idx_to_name_map[kept] = Arc::from(name);
+        let keep = self.vertices().map(|w| w != removed).collect::<Vec<bool>>();
+        self.retain_vertices(&keep);
+        Ok(kept)
     }
 
     /// Returns an iterator on all vertices indices.
@@ -326,17 +1202,32 @@ impl Graph {
     /// assert!(k2.neighbours_idx(1).unwrap().contains(&0));
     /// ```
     pub fn add_edge_idx(&mut self, from: usize, to: usize) -> bool {
-        if from < self.num_of_vertices 
-        && to < self.num_of_vertices 
-        && from != to
-        && !self.neighbours[from].contains(&to) {
-            self.neighbours[to].insert(from);
-            self.neighbours[from].insert(to);
-            self.num_of_edges += 1;
-            true
-        } else {
-            false
+        self.add_edge_idx_checked(from, to).is_ok()
+    }
+
+    /// Adds an edge between two vertices based on their indices like add_edge_idx, but returns the reason as an Err value if it is not added.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::{EdgeError, Graph};
+    /// let mut k2 = Graph::complete(2);
+    /// assert_eq!(Err(EdgeError::AlreadyExists), k2.add_edge_idx_checked(0, 1));
+    /// assert_eq!(Err(EdgeError::SelfLoop), k2.add_edge_idx_checked(1, 1));
+    /// assert_eq!(Err(EdgeError::IndexOutOfRange(2)), k2.add_edge_idx_checked(0, 2));
+    /// ```
+    pub fn add_edge_idx_checked(&mut self, from: usize, to: usize) -> Result<(), EdgeError> {
+        if let Some(idx) = [from, to].into_iter().find(|idx| *idx >= self.num_of_vertices) {
+            return Err(EdgeError::IndexOutOfRange(idx))
         }
+        if from == to {
+            return Err(EdgeError::SelfLoop)
+        }
+        if self.neighbours[from].contains(&to) {
+            return Err(EdgeError::AlreadyExists)
+        }
+        self.neighbours[to].insert(from);
+        self.neighbours[from].insert(to);
+        self.num_of_edges += 1;
+        Ok(())
     }
 
     /// Adds an edge between two vertices based on their names.
@@ -350,15 +1241,51 @@ impl Graph {
     /// assert!(k2.neighbours_idx(1).unwrap().contains(&0));
     /// ```
     pub fn add_edge(&mut self, from: &str, to: &str) -> bool {
-        if let (Some (from_idx), Some (to_idx)) = (self.name_to_idx(from), self.name_to_idx(to)) {
-            if from != to && !self.neighbours[from_idx].contains(&to_idx) {
-                self.neighbours[to_idx].insert(from_idx);
-                self.neighbours[from_idx].insert(to_idx);
-                self.num_of_edges += 1;
-                return true
-            }
-        } 
-        false
+        self.add_edge_checked(from, to).is_ok()
+    }
+
+    /// Adds an edge between two vertices based on their names like add_edge, but returns the reason as an Err value if it is not added.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::{EdgeError, Graph};
+    /// let mut k2 = Graph::complete(2);
+    /// assert_eq!(Err(EdgeError::AlreadyExists), k2.add_edge_checked("vertex_0", "vertex_1"));
+    /// assert_eq!(Err(EdgeError::UnknownVertex("vertex_2".to_string())), k2.add_edge_checked("vertex_0", "vertex_2"));
+    /// ```
+    pub fn add_edge_checked(&mut self, from: &str, to: &str) -> Result<(), EdgeError> {
+        let from_idx = self.name_to_idx(from).ok_or_else(|| EdgeError::UnknownVertex(from.to_owned()))?;
+        let to_idx = self.name_to_idx(to).ok_or_else(|| EdgeError::UnknownVertex(to.to_owned()))?;
+        self.add_edge_idx_checked(from_idx, to_idx)
+    }
+
+    /// Checks whether there is an edge between two vertices based on their indices.
+    /// Returns false if any of the indices does not exist in the graph.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let p3 = Graph::path(3);
+    /// assert!(p3.contains_edge_idx(1, 0));
+    /// assert!(!p3.contains_edge_idx(0, 2));
+    /// assert!(!p3.contains_edge_idx(0, 3));
+    /// ```
+    pub fn contains_edge_idx(&self, from: usize, to: usize) -> bool {
+        from < self.num_of_vertices && self.neighbours[from].contains(&to)
+    }
+
+    /// Checks whether there is an edge between two vertices based on their names.
+    /// Returns false if any of the vertices does not exist in the graph.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let k2 = Graph::complete(2);
+    /// assert!(k2.contains_edge("vertex_1", "vertex_0"));
+    /// assert!(!k2.contains_edge("vertex_0", "vertex_2"));
+    /// ```
+    pub fn contains_edge(&self, from: &str, to: &str) -> bool {
+        match (self.name_to_idx(from), self.name_to_idx(to)) {
+            (Some(from), Some(to)) => self.contains_edge_idx(from, to),
+            _ => false,
+        }
     }
 
     /// Lists all neighbours of a given vertex based on its index.
@@ -379,11 +1306,23 @@ impl Graph {
         if idx >= self.num_of_vertices {
             Err("Index does not exist in the graph.")
         } else {
-            Ok(self.neighbours[idx].clone())
+            Ok(self.neighbours[idx].iter().copied().collect())
         }
     }
 
 
+    /// Returns the degrees of all vertices, sorted non-increasingly.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// assert_eq!(vec![2, 2, 1, 1], Graph::path(4).degree_sequence());
+    /// ```
+    pub fn degree_sequence(&self) -> Vec<usize> {
+        let mut degrees = self.neighbours.iter().map(|set| set.len()).collect::<Vec<usize>>();
+        degrees.sort_unstable_by(|a, b| b.cmp(a));
+        degrees
+    }
+
     /// Returns sorted list of s vertices with highest degree.
     /// ```
     /// use labisu::graphs::Graph;
@@ -416,7 +1355,49 @@ impl Graph {
         vertices[0..s].to_vec()
     }
 
+    /// Returns a 64-bit fingerprint of the structure of the graph, which can be used as a key of cached results.
+    /// It hashes the number of vertices and edges, the sorted degree sequence and the sorted neighbourhood of every vertex
+    /// with FNV-1a, so it does not depend on the iteration order of the sets and is the same across runs and builds.
+    /// Vertex names are not taken into account, as results of the algorithms refer to vertex indices.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let mut g = Graph::path(4);
+    /// assert_eq!(g.fingerprint(), Graph::path(4).fingerprint());
+    /// assert_ne!(g.fingerprint(), Graph::cycle(4).fingerprint());
+    /// g.add_edge_idx(3, 0);
+    /// assert_eq!(g.fingerprint(), Graph::cycle(4).fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> u64 {
+        const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+        const FNV_PRIME: u64 = 0x100000001b3;
+        let mut hash = FNV_OFFSET;
+        let mut feed = |value: usize| {
+            for byte in (value as u64).to_le_bytes() {
+                hash = (hash ^ byte as u64).wrapping_mul(FNV_PRIME);
+            }
+        };
+
+        feed(self.num_of_vertices);
+        feed(self.num_of_edges);
+        let mut degrees: Vec<usize> = self.neighbours.iter().map(|set| set.len()).collect();
+        degrees.sort_unstable();
+        degrees.into_iter().for_each(&mut feed);
+
+        let mut sorted = vec![];
+        for set in self.neighbours.iter() {
+            sorted.clear();
+            sorted.extend(set.iter().copied());
+            sorted.sort_unstable();
+            feed(usize::MAX); // separator of the neighbourhoods
+            sorted.iter().for_each(|v| feed(*v));
+        }
+        hash
+    }
+
     /// Writes a graph to a json file with given filename.
+    /// The graph is streamed to the file without building an intermediate json value.
+    /// The neighbourhoods are serialized in chunks, which are processed in parallel if the parallel feature is enabled.
     /// # Examples
     /// ```
     /// use labisu::graphs::Graph;
@@ -424,19 +1405,104 @@ impl Graph {
     /// k2.add_edge("vertex_0", "vertex_1");
     /// let result = k2.write_to_json("k2.json");
     /// assert!(result.is_ok());
+    ///
+    /// let g = Graph::random(5_000, 0.002);
+    /// g.write_to_json("random.json").unwrap();
+    /// let read = Graph::read_from_json("random.json");
+    /// assert_eq!(g.get_num_of_edges(), read.get_num_of_edges());
+    /// assert!(g.vertices().all(|v| g.neighbours_idx(v) == read.neighbours_idx(v)));
     /// ```
     pub fn write_to_json(&self, filename: &str) -> serde_json::Result<()> {
-        let graph = json!({
-            "num_of_vertices": self.num_of_vertices,
-            "num_of_edges": self.num_of_edges,
-            "neighbours": self.neighbours,
-            "names": self.idx_to_name_map
+        let mut writer = AtomicFile::create(filename).map_err(serde_json::Error::io)?;
+        write!(
+            writer,
+            "{{\"num_of_vertices\":{},\"num_of_edges\":{},\"neighbours\":[",
+            self.num_of_vertices, self.num_of_edges
+        ).map_err(serde_json::Error::io)?;
+
+        let chunks: Vec<&[NeighbourSet]> = self.neighbours.chunks().iter().map(|chunk| chunk.as_slice()).collect();
+        // only a batch of serialized chunks is kept in memory at a time
+        #[cfg(feature = "parallel")]
+        let batch_size = rayon::current_num_threads();
+        #[cfg(not(feature = "parallel"))]
+        let batch_size = 1;
+        for (idx, batch) in chunks.chunks(batch_size).enumerate() {
+            #[cfg(feature = "parallel")]
+            let serialized = batch
+                .par_iter()
+                .map(|chunk| neighbourhoods_to_json(chunk))
+                .collect::<serde_json::Result<Vec<Vec<u8>>>>()?;
+            #[cfg(not(feature = "parallel"))]
+            let serialized = batch
+                .iter()
+                .map(|chunk| neighbourhoods_to_json(chunk))
+                .collect::<serde_json::Result<Vec<Vec<u8>>>>()?;
+            for (chunk_idx, bytes) in serialized.iter().enumerate() {
+                if idx > 0 || chunk_idx > 0 {
+                    writer.write_all(b",").map_err(serde_json::Error::io)?;
+                }
+                writer.write_all(bytes).map_err(serde_json::Error::io)?;
+            }
+        }
+
+        writer.write_all(b"],\"names\":").map_err(serde_json::Error::io)?;
+        serde_json::Serializer::new(&mut writer)
+            .collect_seq(self.idx_to_name_map.iter().map(|name| name.as_ref()))?;
+        writer.write_all(b"}").map_err(serde_json::Error::io)?;
+        writer.commit().map_err(serde_json::Error::io)
+    }
+
+    /// Writes a graph to a json file with given filename, storing the edges as a flat list of pairs of indices sorted lexicographically,
+    /// the smaller index first, instead of the neighbourhoods. Every edge is stored once and every name and edge is on its own line,
+    /// so the file is about half the size and two snapshots of a growing graph can be compared with diff.
+    /// The file can be read with read_from_json.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let k3 = Graph::complete(3);
+    /// k3.write_to_json_edge_list("k3_edges.json").unwrap();
+    /// let contents = std::fs::read_to_string("k3_edges.json").unwrap();
+    /// assert!(contents.contains("\n[0,1],\n[0,2],\n[1,2]\n"));
+    /// let read = Graph::read_from_json("k3_edges.json");
+    /// assert_eq!(3, read.get_num_of_edges());
+    /// assert_eq!(Some(2), read.name_to_idx("vertex_2"));
+    /// ```
+    pub fn write_to_json_edge_list(&self, filename: &str) -> serde_json::Result<()> {
+        let mut writer = AtomicFile::create(filename).map_err(serde_json::Error::io)?;
+        let mut lines = vec![
+            "{".to_owned(),
+            format!("\"num_of_vertices\": {},", self.num_of_vertices),
+            format!("\"num_of_edges\": {},", self.num_of_edges),
+            "\"names\": [".to_owned(),
+        ];
+        for (idx, name) in self.idx_to_name_map.iter().enumerate() {
+            let separator = if idx + 1 < self.num_of_vertices { "," } else { "" };
+            lines.push(format!("{}{}", serde_json::to_string(name.as_ref())?, separator));
         }
-        );
-        serde_json::to_writer(&File::create(filename).unwrap(), &graph)
+        lines.push("],".to_owned());
+        lines.push("\"edges\": [".to_owned());
+        let mut edges = Vec::with_capacity(self.num_of_edges);
+        for from in self.vertices() {
+            let start = edges.len();
+            edges.extend(self.neighbours[from].iter().filter(|to| from < **to).map(|to| (from, *to)));
+            edges[start..].sort_unstable();
+        }
+        for (i, (from, to)) in edges.iter().enumerate() {
+            let separator = if i + 1 < edges.len() { "," } else { "" };
+            lines.push(format!("[{},{}]{}", from, to, separator));
+        }
+        lines.push("]".to_owned());
+        lines.push("}".to_owned());
+        for line in lines {
+            writeln!(writer, "{}", line).map_err(serde_json::Error::io)?;
+        }
+        writer.commit().map_err(serde_json::Error::io)
     }
 
     /// Reads a graph from a json file with given filename.
+    /// Both the layout written by write_to_json (neighbourhoods) and the one written by write_to_json_edge_list (a list of edges) are accepted.
+    /// The file is trusted to be consistent, files written by other tools should be read with read_from_json_with_policy.
+    /// Panics if the file cannot be read, try_read_from_json returns an Err value instead.
     /// # Examples
     /// ```
     /// use labisu::graphs::Graph;
@@ -450,47 +1516,273 @@ impl Graph {
     /// assert_eq!(1, read.name_to_idx("vertex_1").unwrap());
     /// ```
     pub fn read_from_json(filename: &str) -> Graph {
-        let data = std::fs::read_to_string(filename).expect("Unable to read file");
-        let json: serde_json::Value =
-            serde_json::from_str(&data).expect("JSON does not have correct format.");
-
-        let num_of_vertices = json["num_of_vertices"].as_u64().unwrap() as usize;
+        Graph::try_read_from_json(filename).expect("Unable to read the graph.")
+    }
 
-        let num_of_edges = json["num_of_edges"].as_u64().unwrap() as usize;
+    /// Reads a graph from a json file with given filename like read_from_json, but returns an Err value
+    /// if the file cannot be read, is not in one of the layouts or has an index of a vertex out of range.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// Graph::petersen().write_to_json_edge_list("petersen_edges.json").unwrap();
+    /// assert_eq!(15, Graph::try_read_from_json("petersen_edges.json").unwrap().get_num_of_edges());
+    /// assert!(Graph::try_read_from_json("missing.json").is_err());
+    /// std::fs::write("out_of_range.json", r#"{"num_of_vertices": 1, "num_of_edges": 1, "names": ["a"], "edges": [[0, 1]]}"#).unwrap();
+    /// assert!(Graph::try_read_from_json("out_of_range.json").is_err());
+    /// ```
+    pub fn try_read_from_json(filename: &str) -> serde_json::Result<Graph> {
+        let invalid = |message: &str| <serde_json::Error as serde::de::Error>::custom(message);
+        let data = std::fs::read_to_string(filename).map_err(serde_json::Error::io)?;
+        let layout: import::JsonLayout = serde_json::from_str(&data)?;
+        let num_of_vertices = layout.num_of_vertices;
+        if layout.names.len() != num_of_vertices {
+            return Err(invalid("The number of names differs from the number of vertices."))
+        }
 
-        let neighbours = json["neighbours"]
-            .as_array()
-            .unwrap()
-            .iter()
-            .map(|value| {
-                value
-                    .as_array()
-                    .unwrap()
-                    .iter()
-                    .map(|value| value.as_u64().unwrap() as usize)
-                    .collect::<HashSet<usize>>()
-            })
-            .collect::<Vec<HashSet<usize>>>();
-
-        let names: Vec<String> = json["names"]
-            .as_array()
-            .unwrap()
-            .iter()
-            .map(|value| String::from(value.as_str().unwrap()))
-            .collect();
+        let neighbours = match (layout.edges, layout.neighbours) {
+            (Some(edges), _) => {
+                let mut neighbours: Chunked<NeighbourSet> = std::iter::repeat_with(NeighbourSet::default).take(num_of_vertices).collect();
+                for (from, to) in edges {
+                    if from >= num_of_vertices || to >= num_of_vertices {
+                        return Err(invalid("An end of an edge is out of range."))
+                    }
+                    neighbours[from].insert(to);
+                    neighbours[to].insert(from);
+                }
+                neighbours
+            },
+            (None, Some(neighbours)) => {
+                if neighbours.len() != num_of_vertices || neighbours.iter().flatten().any(|v| *v >= num_of_vertices) {
+                    return Err(invalid("The neighbourhoods do not match the number of vertices."))
+                }
+                neighbours.into_iter().map(NeighbourSet::from_iter).collect()
+            },
+            (None, None) => return Err(invalid("Neither edges nor neighbours are given.")),
+        };
 
-        let mut name_to_idx_map = HashMap::new();
-        names.iter().enumerate().for_each(|(idx, name)| {
-            name_to_idx_map.insert(String::from(name), idx);
-        });
+        let (idx_to_name_map, name_to_idx_map) = intern_names(layout.names);
 
-        Graph {
+        Ok(Graph {
             num_of_vertices,
-            num_of_edges,
+            num_of_edges: layout.num_of_edges,
             neighbours,
-            idx_to_name_map: names,
+            idx_to_name_map,
             name_to_idx_map,
+            degree_hint: 0,
+            hinted_vertices: 0,
+        })
+    }
+
+    /// Writes the whole graph as an edge log with given filename.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let k3 = Graph::complete(3);
+    /// k3.write_to_edge_log("k3.ndjson").unwrap();
+    /// let read = Graph::read_from_edge_log("k3.ndjson").unwrap();
+    /// assert_eq!(3, read.get_num_of_edges());
+    /// assert_eq!(Some(2), read.name_to_idx("vertex_2"));
+    /// ```
+    pub fn write_to_edge_log(&self, filename: &str) -> std::io::Result<()> {
+        let mut file = AtomicFile::create(filename)?;
+        for name in self.idx_to_name_map.iter() {
+            write_event(&mut file, &EdgeEvent::Vertex(name.to_string()))?;
         }
+        for from in self.vertices() {
+            for to in self.neighbours[from].iter().filter(|to| from < **to) {
+                let (from, to) = (self.idx_to_name_map[from].to_string(), self.idx_to_name_map[*to].to_string());
+                write_event(&mut file, &EdgeEvent::Edge(from, to))?;
+            }
+        }
+        file.commit()
+    }
+
+    /// Reads a graph by folding an edge log with given filename.
+    /// Vertices are indexed in order of their first insertion, repeated events are ignored
+    /// and an edge whose endpoint was not inserted before adds that vertex.
+    /// An unparsable last line is ignored, as it is a write torn by an interrupted crawl.
+    ///
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// std::fs::write("torn.ndjson", "{\"edge\":[\"a\",\"b\"]}\n{\"edge\":[\"b\",").unwrap();
+    /// let read = Graph::read_from_edge_log("torn.ndjson").unwrap();
+    /// assert_eq!(1, read.get_num_of_edges());
+    /// ```
+    pub fn read_from_edge_log(filename: &str) -> serde_json::Result<Graph> {
+        let reader = BufReader::new(File::open(filename).map_err(serde_json::Error::io)?);
+        let mut graph = Graph::empty();
+        let mut lines = reader.lines().peekable();
+        while let Some(line) = lines.next() {
+            let line = line.map_err(serde_json::Error::io)?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let event = match serde_json::from_str(&line) {
+                Ok(event) => event,
+                Err(_) if lines.peek().is_none() => break,
+                Err(error) => return Err(error),
+            };
+            match event {
+                EdgeEvent::Vertex(name) => graph.add_vertex(&name),
+                EdgeEvent::Edge(from, to) => {
+                    graph.add_vertex(&from);
+                    graph.add_vertex(&to);
+                    graph.add_edge(&from, &to);
+                }
+            }
+        }
+        Ok(graph)
+    }
+
+    /// Returns a copy of the graph where the vertex order[i] of the original graph gets index i.
+    /// The order has to be a permutation of the vertices.
+    fn permuted(&self, order: &[usize]) -> Graph {
+        let mut new_idx = vec![0; self.num_of_vertices];
+        order.iter().enumerate().for_each(|(new, old)| new_idx[*old] = new);
+
+        let mut graph = Graph::from_names(order.iter().map(|old| self.idx_to_name_map[*old].to_string()).collect());
+        graph.neighbours = 
+            order
+            .iter()
+            .map(|old| self.neighbours[*old].iter().map(|neighbour| new_idx[*neighbour]).collect())
+            .collect();
+        graph.num_of_edges = self.num_of_edges;
+        graph
+    }
+
+    /// Returns the graph with vertices renumbered by non-increasing degree (ties broken by the original index)
+    /// together with the permutation: the vertex with new index i had index permutation[i] in the original graph.
+    /// Vertex names are preserved.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let mut g = Graph::from_names(vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+    /// g.add_edge("c", "a");
+    /// g.add_edge("c", "b");
+    /// let (reordered, permutation) = g.reorder_by_degree();
+    /// assert_eq!(vec![2, 0, 1], permutation);
+    /// assert_eq!(Some(0), reordered.name_to_idx("c"));
+    /// assert_eq!(2, reordered.neighbours_idx(0).unwrap().len());
+    /// ```
+    pub fn reorder_by_degree(&self) -> (Graph, Vec<usize>) {
+        let mut order = self.vertices().collect::<Vec<usize>>();
+        order.sort_by_key(|v| std::cmp::Reverse(self.neighbours[*v].len()));
+        (self.permuted(&order), order)
+    }
+
+    /// Returns the graph with vertices renumbered in breadth-first search order from start
+    /// together with the permutation: the vertex with new index i had index permutation[i] in the original graph.
+    /// Neighbours are visited in increasing order of indices and vertices unreachable from start
+    /// follow in the order of searches from the smallest unvisited vertex. Vertex names are preserved.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let mut g = Graph::from_names((0..5).map(|i| format!("v_{}", i)).collect());
+    /// g.add_edge_idx(3, 1);
+    /// g.add_edge_idx(1, 0);
+    /// g.add_edge_idx(2, 4);
+    /// let (reordered, permutation) = g.reorder_by_bfs(3);
+    /// assert_eq!(vec![3, 1, 0, 2, 4], permutation);
+    /// assert_eq!(Some("v_3".to_string()), reordered.idx_to_name(0));
+    /// assert!(reordered.neighbours_idx(3).unwrap().contains(&4));
+    /// ```
+    pub fn reorder_by_bfs(&self, start: usize) -> (Graph, Vec<usize>) {
+        let mut visited = vec![false; self.num_of_vertices];
+        let mut order = Vec::with_capacity(self.num_of_vertices);
+        let starts = std::iter::once(start).chain(self.vertices()).filter(|v| *v < self.num_of_vertices);
+        for root in starts {
+            if visited[root] {
+                continue;
+            }
+            visited[root] = true;
+            let mut head = order.len();
+            order.push(root);
+            while head < order.len() {
+                let current = order[head];
+                head += 1;
+                let mut neighbours = self.neighbours[current].iter().copied().collect::<Vec<usize>>();
+                neighbours.sort_unstable();
+                for neighbour in neighbours {
+                    if !visited[neighbour] {
+                        visited[neighbour] = true;
+                        order.push(neighbour);
+                    }
+                }
+            }
+        }
+        (self.permuted(&order), order)
+    }
+
+    /// Creates a product of two graphs, in which vertex (a, b) has index a * m + b, where m is the number of vertices of the second graph,
+    /// and is named "(name_a,name_b)". Two pairs are adjacent if the predicate holds,
+    /// given whether their first coordinates are equal or adjacent and the same for the second coordinates.
+    /// Only pairs with equal or adjacent coordinates are checked, by iterating the neighbourhoods of both graphs.
+    fn product<F>(&self, other: &Graph, is_edge: F) -> Graph 
+    where F: Fn(bool, bool, bool, bool) -> bool {
+        let m = other.num_of_vertices;
+        let names = 
+            self.idx_to_name_map.iter()
+            .flat_map(|a| other.idx_to_name_map.iter().map(move |b| format!("({},{})", a, b)))
+            .collect();
+        let mut graph = Graph::from_names(names);
+        for a0 in 0..self.num_of_vertices {
+            for a1 in std::iter::once(a0).chain(self.neighbours[a0].iter().copied().filter(|a1| a0 < *a1)) {
+                let (equal_a, adjacent_a) = (a0 == a1, a0 != a1);
+                for b0 in 0..m {
+                    for b1 in std::iter::once(b0).chain(other.neighbours[b0].iter().copied()) {
+                        let (equal_b, adjacent_b) = (b0 == b1, b0 != b1);
+                        if is_edge(equal_a, adjacent_a, equal_b, adjacent_b) {
+                            graph.add_edge_idx(a0 * m + b0, a1 * m + b1);
+                        }
+                    }
+                }
+            }
+        }
+        graph
+    }
+
+    /// Creates the cartesian product of two graphs.
+    /// Pairs (a0, b0) and (a1, b1) are adjacent if a0 = a1 and b0 ~ b1, or a0 ~ a1 and b0 = b1.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let grid = Graph::path(3).cartesian_product(&Graph::path(4));
+    /// assert_eq!(12, grid.get_num_of_vertices());
+    /// assert_eq!(17, grid.get_num_of_edges());
+    /// assert_eq!(Some(5), grid.name_to_idx("(vertex_1,vertex_1)"));
+    /// ```
+    pub fn cartesian_product(&self, other: &Graph) -> Graph {
+        self.product(other, |equal_a, adjacent_a, equal_b, adjacent_b| 
+            (equal_a && adjacent_b) || (adjacent_a && equal_b)
+        )
+    }
+
+    /// Creates the tensor (categorical) product of two graphs.
+    /// Pairs (a0, b0) and (a1, b1) are adjacent if a0 ~ a1 and b0 ~ b1.
+    /// The product with K2 is the bipartite double cover of a graph.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let double_cover = Graph::cycle(5).tensor_product(&Graph::complete(2));
+    /// assert_eq!(10, double_cover.get_num_of_edges());
+    /// assert!(double_cover.is_bipartite());
+    /// ```
+    pub fn tensor_product(&self, other: &Graph) -> Graph {
+        self.product(other, |_, adjacent_a, _, adjacent_b| adjacent_a && adjacent_b)
+    }
+
+    /// Creates the strong product of two graphs,
+    /// which is the union of the edges of the cartesian and the tensor product.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let k6 = Graph::complete(2).strong_product(&Graph::complete(3));
+    /// assert_eq!(15, k6.get_num_of_edges());
+    /// ```
+    pub fn strong_product(&self, other: &Graph) -> Graph {
+        self.product(other, |equal_a, adjacent_a, equal_b, adjacent_b| 
+            (equal_a || adjacent_a) && (equal_b || adjacent_b) && !(equal_a && equal_b)
+        )
     }
 
     /// Checks if a graph is bipartite.
@@ -537,23 +1829,35 @@ impl Graph {
     /// assert!(rand1024_with_leaf.reduce_to_dense());
     /// assert!(!k256.reduce_to_dense());
     pub fn reduce_to_dense(&mut self) -> bool {
-        let lowest_degree_vertices: Vec<String> = 
-            self.lowest_degree_vertices(self.num_of_vertices)
-            .into_iter()
-            .map(|idx| self.idx_to_name(idx).unwrap())
-            .collect();
-
-        for name in lowest_degree_vertices {
-            let n = self.get_num_of_vertices();
-            let m = self.get_num_of_edges();
-
-            println!("n: {}, m: {}", n, m);
+        self.reduce_to_dense_relabeled().0
+    }
 
+    /// Removes vertices in order of non-decreasing degree like reduce_to_dense, but all of them at once, in time linear in the size of the graph.
+    /// Returns whether the reduction was successful together with the relabeling of the remaining vertices.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let mut k17_with_leaf = Graph::complete(17);
+    /// k17_with_leaf.add_vertex("leaf");
+    /// k17_with_leaf.add_edge_idx(17, 0);
+    /// let (dense, relabeling) = k17_with_leaf.reduce_to_dense_relabeled();
+    /// assert!(!dense);
+    /// assert_eq!(0, relabeling.new_len());
+    /// ```
+    pub fn reduce_to_dense_relabeled(&mut self) -> (bool, Relabeling) {
+        let mut keep = vec![true; self.num_of_vertices];
+        let mut n = self.num_of_vertices;
+        let mut m = self.num_of_edges;
+        let mut dense = false;
+        for v in self.lowest_degree_vertices(self.num_of_vertices) {
             if m.pow(2) > 64 * n.pow(3) {
-                return true
+                dense = true;
+                break
             }
-            self.remove_vertex(&name);
+            keep[v] = false;
+            n -= 1;
+            m -= self.neighbours[v].iter().filter(|u| keep[**u]).count();
         }
-        false
+        (dense, self.retain_vertices_relabeled(&keep))
     }
 }
\ No newline at end of file
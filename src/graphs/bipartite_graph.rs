@@ -0,0 +1,308 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashSet, VecDeque};
+
+use super::{Graph, GraphOps};
+
+/// One of the two classes of vertices of a bipartite graph.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Side {
+    /// The left class, e.g. pages.
+    Left,
+    /// The right class, e.g. domains linked from the pages.
+    Right,
+}
+
+impl Side {
+    /// Returns the other class.
+    pub fn other(self) -> Side {
+        match self {
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+        }
+    }
+}
+
+/// A graph whose vertices are split into two classes, with edges only between the classes.
+/// The classes are stored explicitly, so that inherently bipartite data (e.g. pages and the domains they link to)
+/// keeps its structure, and every insertion of an edge is checked against them.
+/// It implements GraphOps, so all algorithms on graphs run on it directly.
+/// # Examples
+/// ```
+/// use labisu::graphs::{BipartiteGraph, GraphOps, Side};
+/// let mut graph = BipartiteGraph::new();
+/// let page = graph.add_vertex("https://pwr.edu.pl/", Side::Left).unwrap();
+/// let domain = graph.add_vertex("pwr.edu.pl", Side::Right).unwrap();
+/// let other = graph.add_vertex("https://pwr.edu.pl/a", Side::Left).unwrap();
+/// assert_eq!(Ok(true), graph.add_edge_idx(page, domain));
+/// assert_eq!(Ok(false), graph.add_edge_idx(domain, page));
+/// assert!(graph.add_edge_idx(page, other).is_err());
+/// assert!(graph.add_vertex("pwr.edu.pl", Side::Left).is_err());
+/// assert_eq!(1, graph.get_num_of_edges());
+/// assert_eq!(vec![page, other], graph.class(Side::Left));
+/// ```
+#[derive(Clone)]
+pub struct BipartiteGraph {
+    graph: Graph,
+    sides: Vec<Side>,
+}
+
+impl Default for BipartiteGraph {
+    fn default() -> BipartiteGraph {
+        BipartiteGraph::new()
+    }
+}
+
+impl BipartiteGraph {
+    /// Creates an empty bipartite graph.
+    pub fn new() -> BipartiteGraph {
+        BipartiteGraph { graph: Graph::empty(), sides: vec![] }
+    }
+
+    /// Creates a bipartite graph from a graph, where the vertices in left form the left class and all others the right class.
+    /// Returns an Err value if an edge joins two vertices of the same class.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::{BipartiteGraph, Graph, Side};
+    /// use std::collections::HashSet;
+    /// let c4 = Graph::cycle(4);
+    /// let bipartite = BipartiteGraph::from_graph(&c4, &HashSet::from([0, 2])).unwrap();
+    /// assert_eq!(Some(Side::Right), bipartite.side(1));
+    /// assert!(BipartiteGraph::from_graph(&c4, &HashSet::from([0, 1])).is_err());
+    /// ```
+    pub fn from_graph(graph: &Graph, left: &HashSet<usize>) -> Result<BipartiteGraph, &'static str> {
+        let sides = graph.vertices().map(|v| if left.contains(&v) { Side::Left } else { Side::Right }).collect::<Vec<Side>>();
+        for v in graph.vertices() {
+            if graph.neighbours[v].iter().any(|u| sides[*u] == sides[v]) {
+                return Err("An edge joins two vertices of the same class.")
+            }
+        }
+        Ok(BipartiteGraph { graph: graph.clone(), sides })
+    }
+
+    /// Creates a bipartite graph from a graph by 2-colouring its connected components,
+    /// the vertex of the smallest index of each component in the left class.
+    /// Returns None if the graph is not bipartite.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::{BipartiteGraph, Graph, Side};
+    /// let bipartite = BipartiteGraph::try_from_graph(&Graph::path(3)).unwrap();
+    /// assert_eq!(vec![0, 2], bipartite.class(Side::Left));
+    /// assert!(BipartiteGraph::try_from_graph(&Graph::cycle(5)).is_none());
+    /// ```
+    pub fn try_from_graph(graph: &Graph) -> Option<BipartiteGraph> {
+        let mut sides: Vec<Option<Side>> = vec![None; graph.get_num_of_vertices()];
+        let mut queue = VecDeque::new();
+        for start in graph.vertices() {
+            if sides[start].is_some() {
+                continue
+            }
+            sides[start] = Some(Side::Left);
+            queue.push_back(start);
+            while let Some(v) = queue.pop_front() {
+                let side = sides[v].unwrap();
+                for u in &graph.neighbours[v] {
+                    match sides[*u] {
+                        None => {
+                            sides[*u] = Some(side.other());
+                            queue.push_back(*u);
+                        },
+                        Some(other) if other == side => return None,
+                        Some(_) => {},
+                    }
+                }
+            }
+        }
+        Some(BipartiteGraph { graph: graph.clone(), sides: sides.into_iter().map(Option::unwrap).collect() })
+    }
+
+    /// Returns the underlying graph.
+    pub fn graph(&self) -> &Graph {
+        &self.graph
+    }
+
+    /// Returns the underlying graph, dropping the classes.
+    pub fn into_graph(self) -> Graph {
+        self.graph
+    }
+
+    /// Returns the class of the vertex with given index, or None if it does not exist.
+    pub fn side(&self, idx: usize) -> Option<Side> {
+        self.sides.get(idx).copied()
+    }
+
+    /// Returns the indices of the vertices of the class, sorted.
+    pub fn class(&self, side: Side) -> Vec<usize> {
+        (0..self.sides.len()).filter(|v| self.sides[*v] == side).collect()
+    }
+
+    /// Returns the index of the vertex with given name, or None if it does not exist.
+    pub fn name_to_idx(&self, name: &str) -> Option<usize> {
+        self.graph.name_to_idx(name)
+    }
+
+    /// Returns the name of the vertex with given index, or None if it does not exist.
+    pub fn idx_to_name(&self, idx: usize) -> Option<String> {
+        self.graph.idx_to_name(idx)
+    }
+
+    /// Adds a vertex with given name to the class if it does not exist yet. Returns the index of the vertex,
+    /// or an Err value if it already exists in the other class.
+    pub fn add_vertex(&mut self, name: &str, side: Side) -> Result<usize, &'static str> {
+        if let Some(idx) = self.graph.name_to_idx(name) {
+            if self.sides[idx] != side {
+                return Err("The vertex already exists in the other class.")
+            }
+            return Ok(idx)
+        }
+        self.graph.add_vertex(name);
+        self.sides.push(side);
+        Ok(self.sides.len() - 1)
+    }
+
+    /// Adds an edge between two vertices based on their indices, given in any order.
+    /// Returns Ok(true) if the edge was added, Ok(false) if it already existed,
+    /// or an Err value if a vertex does not exist or both are in the same class.
+    pub fn add_edge_idx(&mut self, from: usize, to: usize) -> Result<bool, &'static str> {
+        match (self.side(from), self.side(to)) {
+            (Some(from_side), Some(to_side)) if from_side == to_side => Err("Both ends of the edge are in the same class."),
+            (Some(_), Some(_)) => Ok(self.graph.add_edge_idx(from, to)),
+            _ => Err("Index does not exist in the graph."),
+        }
+    }
+
+    /// Adds an edge between two vertices based on their names like add_edge_idx.
+    pub fn add_edge(&mut self, from: &str, to: &str) -> Result<bool, &'static str> {
+        match (self.name_to_idx(from), self.name_to_idx(to)) {
+            (Some(from), Some(to)) => self.add_edge_idx(from, to),
+            _ => Err("Name does not exist in the graph."),
+        }
+    }
+
+    /// Returns the projection onto the class: the graph on its vertices (with the same names, in the order of class),
+    /// in which two vertices are adjacent if they have a common neighbour.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::{BipartiteGraph, GraphOps, Side};
+    /// let mut graph = BipartiteGraph::new();
+    /// for page in ["a", "b", "c"] {
+    ///     graph.add_vertex(page, Side::Left).unwrap();
+    /// }
+    /// graph.add_vertex("x.com", Side::Right).unwrap();
+    /// graph.add_vertex("y.com", Side::Right).unwrap();
+    /// graph.add_edge("a", "x.com").unwrap();
+    /// graph.add_edge("b", "x.com").unwrap();
+    /// graph.add_edge("c", "y.com").unwrap();
+    /// let pages = graph.projection(Side::Left);
+    /// assert_eq!(3, pages.get_num_of_vertices());
+    /// assert_eq!(1, pages.get_num_of_edges());
+    /// assert!(pages.contains_edge("a", "b"));
+    /// assert_eq!(0, graph.projection(Side::Right).get_num_of_edges());
+    /// ```
+    pub fn projection(&self, side: Side) -> Graph {
+        let class = self.class(side);
+        let mut new_idx = vec![usize::MAX; self.sides.len()];
+        class.iter().enumerate().for_each(|(new, old)| new_idx[*old] = new);
+        let mut projection = Graph::from_names(class.iter().map(|v| self.graph.idx_to_name_map[*v].to_string()).collect());
+        for v in self.class(side.other()) {
+            let neighbours = self.graph.neighbours[v].iter().map(|u| new_idx[*u]).collect::<Vec<usize>>();
+            for (i, a) in neighbours.iter().enumerate() {
+                for b in &neighbours[i + 1..] {
+                    projection.add_edge_idx(*a, *b);
+                }
+            }
+        }
+        projection
+    }
+
+    /// Returns a maximum matching, found with the Hopcroft-Karp algorithm, as pairs (left, right) sorted by the left vertex.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::{BipartiteGraph, Graph};
+    /// use std::collections::HashSet;
+    /// let k23 = Graph::complete_multipartite(&[2, 3]);
+    /// let bipartite = BipartiteGraph::from_graph(&k23, &HashSet::from([0, 1])).unwrap();
+    /// assert_eq!(2, bipartite.maximum_matching().len());
+    /// let p4 = BipartiteGraph::try_from_graph(&Graph::path(4)).unwrap();
+    /// assert_eq!(vec![(0, 1), (2, 3)], p4.maximum_matching());
+    /// ```
+    pub fn maximum_matching(&self) -> Vec<(usize, usize)> {
+        let left = self.class(Side::Left);
+        let n = self.sides.len();
+        let mut mate: Vec<Option<usize>> = vec![None; n];
+        loop {
+            // layers of the alternating paths from free left vertices
+            let mut layer = vec![usize::MAX; n];
+            let mut queue = VecDeque::new();
+            for v in left.iter().filter(|v| mate[**v].is_none()) {
+                layer[*v] = 0;
+                queue.push_back(*v);
+            }
+            let mut found = false;
+            while let Some(v) = queue.pop_front() {
+                for u in &self.graph.neighbours[v] {
+                    match mate[*u] {
+                        None => found = true,
+                        Some(w) if layer[w] == usize::MAX => {
+                            layer[w] = layer[v] + 1;
+                            queue.push_back(w);
+                        },
+                        Some(_) => {},
+                    }
+                }
+            }
+            if !found {
+                break
+            }
+            for v in left.iter().copied() {
+                if mate[v].is_none() {
+                    self.augment(v, &mut layer, &mut mate);
+                }
+            }
+        }
+        left.into_iter().filter_map(|v| mate[v].map(|u| (v, u))).collect()
+    }
+
+    /// Looks for an augmenting path from the left vertex along the layers and flips it. Returns true if it was found.
+    fn augment(&self, v: usize, layer: &mut [usize], mate: &mut [Option<usize>]) -> bool {
+        let neighbours = self.graph.neighbours[v].iter().copied().collect::<Vec<usize>>();
+        for u in neighbours {
+            let free = match mate[u] {
+                None => true,
+                Some(w) => layer[w] == layer[v] + 1 && self.augment(w, layer, mate),
+            };
+            if free {
+                mate[u] = Some(v);
+                mate[v] = Some(u);
+                return true
+            }
+        }
+        layer[v] = usize::MAX; // dead end in this phase
+        false
+    }
+}
+
+impl GraphOps for BipartiteGraph {
+    fn get_num_of_vertices(&self) -> usize {
+        self.graph.get_num_of_vertices()
+    }
+
+    fn get_num_of_edges(&self) -> usize {
+        self.graph.get_num_of_edges()
+    }
+
+    fn neighbours_idx(&self, idx: usize) -> Result<HashSet<usize>, &str> {
+        self.graph.neighbours_idx(idx)
+    }
+
+    fn degree(&self, idx: usize) -> usize {
+        self.graph.degree(idx)
+    }
+
+    fn for_each_neighbour(&self, idx: usize, f: &mut dyn FnMut(usize)) {
+        self.graph.for_each_neighbour(idx, f)
+    }
+
+    fn highest_degree_vertices(&self, s: usize) -> Vec<usize> {
+        self.graph.highest_degree_vertices(s)
+    }
+}
@@ -0,0 +1,109 @@
+use super::Graph;
+
+/// The largest number of vertices of a graph whose canonical form is computed, as the search is exponential.
+pub const MAX_CANONICAL_VERTICES: usize = 10;
+
+/// The state of the search for the canonical order of vertices.
+/// Orders are compared by the upper triangles of the adjacency matrices, column by column as in graph6, and the largest one wins.
+struct CanonicalSearch {
+    adjacency: Vec<Vec<bool>>,
+    degrees: Vec<usize>,
+    sorted_degrees: Vec<usize>, // non-increasing, the degree of the vertex at each position
+    order: Vec<usize>,
+    used: Vec<bool>,
+    bits: Vec<bool>,
+    best_bits: Vec<bool>,
+    best_order: Vec<usize>,
+}
+
+impl CanonicalSearch {
+    fn extend(&mut self) {
+        let k = self.order.len();
+        if k == self.adjacency.len() {
+            if self.best_order.is_empty() || self.bits > self.best_bits {
+                self.best_bits = self.bits.clone();
+                self.best_order = self.order.clone();
+            }
+            return
+        }
+        for v in 0..self.adjacency.len() {
+            if self.used[v] || self.degrees[v] != self.sorted_degrees[k] {
+                continue
+            }
+            let start = self.bits.len();
+            for i in 0..k {
+                self.bits.push(self.adjacency[self.order[i]][v]);
+            }
+            // a prefix smaller than the prefix of the best order cannot lead to a larger order
+            if self.best_order.is_empty() || self.bits[..] >= self.best_bits[..self.bits.len()] {
+                self.order.push(v);
+                self.used[v] = true;
+                self.extend();
+                self.used[v] = false;
+                self.order.pop();
+            }
+            self.bits.truncate(start);
+        }
+    }
+}
+
+impl Graph {
+    /// Returns a canonical form of the graph: the graph6 encoding (see to_graph6) of the relabeling of the graph
+    /// with the lexicographically largest adjacency matrix among the orders of vertices by non-increasing degree.
+    /// Two graphs are isomorphic if and only if their canonical forms are equal, so the forms can be stored as certificates in regression tests.
+    /// The form is found by brute force with pruning, so it is only computed for graphs with up to MAX_CANONICAL_VERTICES vertices,
+    /// otherwise an Err value is returned.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let petersen = Graph::petersen().canonical_form().unwrap();
+    /// assert_eq!(petersen, Graph::from_graph6("IheA@GUAo").unwrap().canonical_form().unwrap());
+    /// let mut two_triangles = Graph::from_names((0..6).map(|i| i.to_string()).collect());
+    /// for (from, to) in [(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3)] {
+    ///     two_triangles.add_edge_idx(from, to);
+    /// }
+    /// assert_ne!(Graph::cycle(6).canonical_form(), two_triangles.canonical_form());
+    /// assert_eq!(Graph::complete(4).to_graph6(), Graph::complete(4).canonical_form().unwrap());
+    /// assert!(Graph::complete(11).canonical_form().is_err());
+    /// ```
+    pub fn canonical_form(&self) -> Result<String, &'static str> {
+        let n = self.num_of_vertices;
+        if n > MAX_CANONICAL_VERTICES {
+            return Err("The graph has too many vertices to compute its canonical form.")
+        }
+        let degrees = self.neighbours.iter().map(|neighbours| neighbours.len()).collect::<Vec<usize>>();
+        let mut sorted_degrees = degrees.clone();
+        sorted_degrees.sort_unstable_by(|a, b| b.cmp(a));
+        let mut search = CanonicalSearch {
+            adjacency: (0..n).map(|v| (0..n).map(|u| self.neighbours[v].contains(&u)).collect()).collect(),
+            degrees,
+            sorted_degrees,
+            order: Vec::with_capacity(n),
+            used: vec![false; n],
+            bits: vec![],
+            best_bits: vec![],
+            best_order: vec![],
+        };
+        search.extend();
+        Ok(self.permuted(&search.best_order).to_graph6())
+    }
+
+    /// Checks whether two graphs are isomorphic by comparing their canonical forms.
+    /// Returns an Err value if any of the graphs has more than MAX_CANONICAL_VERTICES vertices.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let mut path = Graph::from_names((0..4).map(|i| i.to_string()).collect());
+    /// for (from, to) in [(0, 2), (2, 3), (3, 1)] {
+    ///     path.add_edge_idx(from, to);
+    /// }
+    /// assert_eq!(Ok(true), Graph::path(4).is_isomorphic(&path));
+    /// assert_eq!(Ok(false), Graph::path(4).is_isomorphic(&Graph::cycle(4)));
+    /// ```
+    pub fn is_isomorphic(&self, other: &Graph) -> Result<bool, &'static str> {
+        if self.num_of_vertices != other.num_of_vertices || self.num_of_edges != other.num_of_edges {
+            return Ok(false)
+        }
+        Ok(self.canonical_form()? == other.canonical_form()?)
+    }
+}
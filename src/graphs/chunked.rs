@@ -0,0 +1,110 @@
+use std::ops::{Index, IndexMut};
+use std::sync::Arc;
+
+/// The number of elements in a chunk, i.e. the unit of copying after a snapshot.
+const CHUNK_SIZE: usize = 1024;
+
+/// A chunk of elements, shared by the graph and its snapshots until one of them modifies it.
+type Chunk<T> = Arc<Vec<T>>;
+
+/// A vector stored in chunks of CHUNK_SIZE elements (only the last one may be shorter), which are shared by its clones.
+/// A clone costs one reference per chunk, and a chunk is copied only when it is modified while it is shared,
+/// so a graph growing under its snapshots only copies the chunks touched since the last snapshot.
+#[derive(Clone)]
+pub(super) struct Chunked<T> {
+    chunks: Vec<Chunk<T>>,
+}
+
+impl<T> Default for Chunked<T> {
+    fn default() -> Chunked<T> {
+        Chunked { chunks: vec![] }
+    }
+}
+
+impl<T: Clone> Chunked<T> {
+    pub(super) fn get(&self, idx: usize) -> Option<&T> {
+        self.chunks.get(idx / CHUNK_SIZE).and_then(|chunk| chunk.get(idx % CHUNK_SIZE))
+    }
+
+    pub(super) fn iter(&self) -> impl Iterator<Item = &T> {
+        self.chunks.iter().flat_map(|chunk| chunk.iter())
+    }
+
+    /// Iterates over mutable references, copying every shared chunk.
+    pub(super) fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.chunks.iter_mut().flat_map(|chunk| Arc::make_mut(chunk).iter_mut())
+    }
+
+    pub(super) fn chunks(&self) -> &[Chunk<T>] {
+        &self.chunks
+    }
+
+    pub(super) fn push(&mut self, value: T) {
+        match self.chunks.last_mut() {
+            Some(last) if last.len() < CHUNK_SIZE => Arc::make_mut(last).push(value),
+            // only a vector with full chunks gets a chunk of full capacity, so that small graphs stay small
+            _ => {
+                let capacity = if self.chunks.is_empty() { 0 } else { CHUNK_SIZE };
+                let mut chunk = Vec::with_capacity(capacity);
+                chunk.push(value);
+                self.chunks.push(Arc::new(chunk));
+            },
+        }
+    }
+
+    /// Reserves space for at least additional more elements in the last chunk and for the references to the next chunks.
+    pub(super) fn reserve(&mut self, additional: usize) {
+        if additional == 0 {
+            return
+        }
+        if self.chunks.is_empty() {
+            self.chunks.push(Arc::new(vec![]));
+        }
+        let last = self.chunks.last_mut().unwrap();
+        let spare = additional.min(CHUNK_SIZE - last.len());
+        Arc::make_mut(last).reserve(spare);
+        self.chunks.reserve((additional - spare).div_ceil(CHUNK_SIZE));
+    }
+
+    /// Returns the number of elements the vector can hold without moving the elements it already has.
+    pub(super) fn capacity(&self) -> usize {
+        let allocated = self.chunks.iter().map(|chunk| chunk.capacity()).sum::<usize>();
+        allocated + (self.chunks.capacity() - self.chunks.len()) * CHUNK_SIZE
+    }
+
+    pub(super) fn shrink_to_fit(&mut self) {
+        if let Some(last) = self.chunks.last_mut() {
+            Arc::make_mut(last).shrink_to_fit();
+        }
+        self.chunks.shrink_to_fit();
+    }
+}
+
+impl<T> Index<usize> for Chunked<T> {
+    type Output = T;
+
+    fn index(&self, idx: usize) -> &T {
+        &self.chunks[idx / CHUNK_SIZE][idx % CHUNK_SIZE]
+    }
+}
+
+impl<T: Clone> IndexMut<usize> for Chunked<T> {
+    /// Returns a mutable reference to the element, copying its chunk if it is shared.
+    fn index_mut(&mut self, idx: usize) -> &mut T {
+        &mut Arc::make_mut(&mut self.chunks[idx / CHUNK_SIZE])[idx % CHUNK_SIZE]
+    }
+}
+
+impl<T: Clone> FromIterator<T> for Chunked<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Chunked<T> {
+        let mut chunked = Chunked::default();
+        iter.into_iter().for_each(|value| chunked.push(value));
+        chunked
+    }
+}
+
+impl<T: Clone> From<Vec<T>> for Chunked<T> {
+    fn from(values: Vec<T>) -> Chunked<T> {
+        values.into_iter().collect()
+    }
+}
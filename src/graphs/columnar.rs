@@ -0,0 +1,71 @@
+use arrow_array::{ArrayRef, RecordBatch, StringArray, UInt64Array};
+use arrow_schema::{DataType, Field, Schema};
+use parquet::arrow::ArrowWriter;
+use parquet::errors::Result;
+use std::sync::Arc;
+
+use crate::atomic::AtomicFile;
+use super::Graph;
+
+/// The number of rows written to a parquet file at once, so that giant graphs are not copied into memory as a whole.
+const BATCH_SIZE: usize = 1 << 16;
+
+/// Writes the rows produced by next_batch to a parquet file, until it returns an empty batch.
+fn write_table<F: FnMut() -> Vec<ArrayRef>>(filename: &str, schema: Schema, mut next_batch: F) -> Result<()> {
+    let schema = Arc::new(schema);
+    let mut writer = ArrowWriter::try_new(AtomicFile::create(filename)?, Arc::clone(&schema), None)?;
+    loop {
+        let columns = next_batch();
+        if columns[0].is_empty() {
+            break
+        }
+        writer.write(&RecordBatch::try_new(Arc::clone(&schema), columns)?)?;
+    }
+    writer.into_inner()?.commit()?;
+    Ok(())
+}
+
+impl Graph {
+    /// Writes the vertex and edge tables of the graph to parquet files with given filenames.
+    /// The vertex table has columns idx, name and degree, the edge table has columns from and to with from < to,
+    /// where the endpoints are indices of the vertex table.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// use parquet::file::reader::{FileReader, SerializedFileReader};
+    /// use std::fs::File;
+    /// let petersen = Graph::petersen();
+    /// petersen.write_to_parquet("petersen_vertices.parquet", "petersen_edges.parquet").unwrap();
+    /// let vertices = SerializedFileReader::new(File::open("petersen_vertices.parquet").unwrap()).unwrap();
+    /// let edges = SerializedFileReader::new(File::open("petersen_edges.parquet").unwrap()).unwrap();
+    /// assert_eq!(10, vertices.metadata().file_metadata().num_rows());
+    /// assert_eq!(15, edges.metadata().file_metadata().num_rows());
+    /// ```
+    pub fn write_to_parquet(&self, vertices_filename: &str, edges_filename: &str) -> Result<()> {
+        let vertex_schema = Schema::new(vec![
+            Field::new("idx", DataType::UInt64, false),
+            Field::new("name", DataType::Utf8, false),
+            Field::new("degree", DataType::UInt64, false),
+        ]);
+        let mut next_vertex = 0;
+        write_table(vertices_filename, vertex_schema, || {
+            let batch = next_vertex..(next_vertex + BATCH_SIZE).min(self.num_of_vertices);
+            next_vertex = batch.end;
+            vec![
+                Arc::new(UInt64Array::from_iter_values(batch.clone().map(|v| v as u64))) as ArrayRef,
+                Arc::new(StringArray::from_iter_values(batch.clone().map(|v| self.idx_to_name_map[v].as_ref()))),
+                Arc::new(UInt64Array::from_iter_values(batch.map(|v| self.neighbours[v].len() as u64))),
+            ]
+        })?;
+
+        let edge_schema = Schema::new(vec![
+            Field::new("from", DataType::UInt64, false),
+            Field::new("to", DataType::UInt64, false),
+        ]);
+        let mut edges = self.vertices().flat_map(|from| self.neighbours[from].iter().filter(move |to| from < **to).map(move |to| (from, *to)));
+        write_table(edges_filename, edge_schema, || {
+            let (from, to): (Vec<u64>, Vec<u64>) = edges.by_ref().take(BATCH_SIZE).map(|(from, to)| (from as u64, to as u64)).unzip();
+            vec![Arc::new(UInt64Array::from(from)) as ArrayRef, Arc::new(UInt64Array::from(to))]
+        })
+    }
+}
@@ -0,0 +1,176 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::BuildHasher as _;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+
+use super::{BuildHasher, Graph, GraphOps, NeighbourSet};
+
+/// The default number of shards of a concurrent graph.
+const DEFAULT_NUM_OF_SHARDS: usize = 64;
+
+/// An undirected graph with named vertices, which many threads can extend at once,
+/// e.g. workers of a custom crawl inserting the links they find without waiting for a global lock.
+/// The Crawler does not use it, as it merges the links of a round in one thread after its workers finish.
+/// Vertices and the name mapping are split into shards with separate locks and indices are drawn from an atomic counter,
+/// so that concurrent insertions of vertices and edges only contend when they touch the same shard.
+/// An edge is first added to the neighbourhood of its smaller end, which decides whether it is new,
+/// so while insertions are in progress the other end may not list it yet.
+/// # Examples
+/// ```
+/// use labisu::graphs::{ConcurrentGraph, GraphOps};
+/// use std::sync::Arc;
+/// let graph = Arc::new(ConcurrentGraph::new());
+/// let workers = (0..8).map(|worker| {
+///     let graph = Arc::clone(&graph);
+///     std::thread::spawn(move || {
+///         for i in 0..100 {
+///             graph.add_edge("root", &format!("page_{}", (worker * 50 + i) % 400));
+///         }
+///     })
+/// }).collect::<Vec<_>>();
+/// workers.into_iter().for_each(|worker| worker.join().unwrap());
+/// assert_eq!(401, graph.get_num_of_vertices());
+/// assert_eq!(400, graph.get_num_of_edges());
+/// let snapshot = graph.to_graph();
+/// assert_eq!(400, snapshot.neighbours_idx(snapshot.name_to_idx("root").unwrap()).unwrap().len());
+/// ```
+pub struct ConcurrentGraph {
+    num_of_vertices: AtomicUsize,
+    name_shards: Vec<Mutex<HashMap<Arc<str>, usize, BuildHasher>>>,
+    vertex_shards: Vec<RwLock<HashMap<usize, Vertex, BuildHasher>>>,
+    num_of_edges: AtomicUsize,
+    hasher: BuildHasher,
+}
+
+/// A vertex of a concurrent graph, stored in the shard of its index.
+struct Vertex {
+    name: Arc<str>,
+    neighbours: NeighbourSet,
+}
+
+impl Default for ConcurrentGraph {
+    fn default() -> ConcurrentGraph {
+        ConcurrentGraph::new()
+    }
+}
+
+impl ConcurrentGraph {
+    /// Creates an empty concurrent graph with the default number of shards.
+    pub fn new() -> ConcurrentGraph {
+        ConcurrentGraph::with_shards(DEFAULT_NUM_OF_SHARDS)
+    }
+
+    /// Creates an empty concurrent graph with given number of shards (at least one),
+    /// which should be a few times larger than the number of writing threads.
+    pub fn with_shards(num_of_shards: usize) -> ConcurrentGraph {
+        let num_of_shards = num_of_shards.max(1);
+        ConcurrentGraph {
+            num_of_vertices: AtomicUsize::new(0),
+            name_shards: (0..num_of_shards).map(|_| Mutex::new(HashMap::default())).collect(),
+            vertex_shards: (0..num_of_shards).map(|_| RwLock::new(HashMap::default())).collect(),
+            num_of_edges: AtomicUsize::new(0),
+            hasher: BuildHasher::default(),
+        }
+    }
+
+    fn vertex_shard(&self, idx: usize) -> &RwLock<HashMap<usize, Vertex, BuildHasher>> {
+        &self.vertex_shards[idx % self.vertex_shards.len()]
+    }
+
+    /// Adds a vertex with given name if it does not exist yet. Returns the index of the vertex.
+    /// Only the shard of the name and the shard of the new index are locked.
+    pub fn add_vertex(&self, name: &str) -> usize {
+        let shard = self.hasher.hash_one(name) as usize % self.name_shards.len();
+        let mut name_shard = self.name_shards[shard].lock().unwrap();
+        if let Some(idx) = name_shard.get(name) {
+            return *idx
+        }
+        let name: Arc<str> = Arc::from(name);
+        let idx = self.num_of_vertices.fetch_add(1, Ordering::Relaxed);
+        self.vertex_shard(idx).write().unwrap().insert(idx, Vertex { name: Arc::clone(&name), neighbours: NeighbourSet::default() });
+        name_shard.insert(name, idx);
+        idx
+    }
+
+    /// Returns the index of the vertex with given name, or None if it does not exist.
+    pub fn name_to_idx(&self, name: &str) -> Option<usize> {
+        let shard = self.hasher.hash_one(name) as usize % self.name_shards.len();
+        self.name_shards[shard].lock().unwrap().get(name).copied()
+    }
+
+    /// Returns the name of the vertex with given index, or None if it does not exist.
+    pub fn idx_to_name(&self, idx: usize) -> Option<String> {
+        self.vertex_shard(idx).read().unwrap().get(&idx).map(|vertex| vertex.name.to_string())
+    }
+
+    /// Adds an edge between two vertices given by indices.
+    /// Returns true if the edge was added, i.e. both vertices exist, they are different and the edge did not exist before.
+    pub fn add_edge_idx(&self, from: usize, to: usize) -> bool {
+        let (smaller, larger) = (from.min(to), from.max(to));
+        if smaller == larger {
+            return false
+        }
+        if !self.vertex_shard(larger).read().unwrap().contains_key(&larger) {
+            return false
+        }
+        let is_new = match self.vertex_shard(smaller).write().unwrap().get_mut(&smaller) {
+            Some(vertex) => vertex.neighbours.insert(larger),
+            None => return false,
+        };
+        if is_new {
+            self.vertex_shard(larger).write().unwrap().get_mut(&larger).unwrap().neighbours.insert(smaller);
+            self.num_of_edges.fetch_add(1, Ordering::Relaxed);
+        }
+        is_new
+    }
+
+    /// Adds an edge between two vertices given by names, adding the vertices if they do not exist.
+    /// Returns true if the edge was added.
+    pub fn add_edge(&self, from: &str, to: &str) -> bool {
+        let from = self.add_vertex(from);
+        let to = self.add_vertex(to);
+        self.add_edge_idx(from, to)
+    }
+
+    /// Copies the current state into a Graph, which should be done when no insertions are in progress.
+    pub fn to_graph(&self) -> Graph {
+        let num_of_vertices = self.get_num_of_vertices();
+        let names = (0..num_of_vertices).map(|idx| self.idx_to_name(idx).unwrap_or_default()).collect::<Vec<String>>();
+        let mut graph = Graph::from_names(names);
+        for v in 0..num_of_vertices {
+            for u in self.neighbours_idx(v).unwrap_or_default() {
+                if v < u && u < num_of_vertices {
+                    graph.add_edge_idx(v, u);
+                }
+            }
+        }
+        graph
+    }
+}
+
+impl GraphOps for ConcurrentGraph {
+    fn get_num_of_vertices(&self) -> usize {
+        self.num_of_vertices.load(Ordering::Relaxed)
+    }
+
+    fn get_num_of_edges(&self) -> usize {
+        self.num_of_edges.load(Ordering::Relaxed)
+    }
+
+    fn neighbours_idx(&self, idx: usize) -> Result<HashSet<usize>, &str> {
+        match self.vertex_shard(idx).read().unwrap().get(&idx) {
+            Some(vertex) => Ok(vertex.neighbours.iter().copied().collect()),
+            None => Err("Index does not exist in the graph."),
+        }
+    }
+
+    fn degree(&self, idx: usize) -> usize {
+        self.vertex_shard(idx).read().unwrap()[&idx].neighbours.len()
+    }
+
+    fn for_each_neighbour(&self, idx: usize, f: &mut dyn FnMut(usize)) {
+        // the neighbourhood is copied, so that f may access the graph without waiting for the lock of the shard
+        let neighbours = self.vertex_shard(idx).read().unwrap()[&idx].neighbours.iter().copied().collect::<Vec<usize>>();
+        neighbours.into_iter().for_each(f)
+    }
+}
@@ -0,0 +1,199 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use super::chunked::Chunked;
+use super::{BuildHasher, Graph, GraphOps, NeighbourSet};
+
+/// A graph with named vertices whose immutable snapshots are cheap.
+/// Neighbourhoods and names are stored in chunks shared with the snapshots, and a chunk is copied
+/// only when it is modified for the first time after a snapshot, so taking a snapshot costs one reference per chunk
+/// and a growing graph only copies the chunks touched since the last snapshot.
+/// # Examples
+/// ```
+/// use labisu::graphs::{CowGraph, GraphOps};
+/// let mut graph = CowGraph::new();
+/// graph.add_edge("a", "b");
+/// let snapshot = graph.snapshot();
+/// graph.add_edge("b", "c");
+/// assert_eq!(2, snapshot.get_num_of_vertices());
+/// assert_eq!(1, snapshot.get_num_of_edges());
+/// assert_eq!(3, graph.get_num_of_vertices());
+/// assert_eq!(1, snapshot.degree(1));
+/// assert_eq!(2, graph.degree(1));
+/// ```
+#[derive(Clone, Default)]
+pub struct CowGraph {
+    snapshot: GraphSnapshot,
+    name_to_idx_map: HashMap<Arc<str>, usize, BuildHasher>,
+}
+
+/// An immutable snapshot of a CowGraph or a Graph, which can be sent to another thread and analyzed while the graph grows.
+#[derive(Clone, Default)]
+pub struct GraphSnapshot {
+    num_of_vertices: usize,
+    num_of_edges: usize,
+    neighbours: Chunked<NeighbourSet>,
+    names: Chunked<Arc<str>>,
+}
+
+impl GraphSnapshot {
+    /// Takes a snapshot of a graph, sharing its chunks of neighbourhoods and names, so no neighbourhood is copied
+    /// until the graph modifies it.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::{Graph, GraphOps, GraphSnapshot};
+    /// let snapshot = GraphSnapshot::from_graph(&Graph::petersen());
+    /// assert_eq!(15, snapshot.get_num_of_edges());
+    /// assert_eq!(Some("vertex_3".to_string()), snapshot.idx_to_name(3));
+    /// ```
+    pub fn from_graph(graph: &Graph) -> GraphSnapshot {
+        GraphSnapshot {
+            num_of_vertices: graph.num_of_vertices,
+            num_of_edges: graph.num_of_edges,
+            neighbours: graph.neighbours.clone(),
+            names: graph.idx_to_name_map.clone(),
+        }
+    }
+
+    fn neighbours_of(&self, idx: usize) -> Option<&NeighbourSet> {
+        self.neighbours.get(idx)
+    }
+
+    /// Returns the name of the vertex with given index, or None if it does not exist.
+    pub fn idx_to_name(&self, idx: usize) -> Option<String> {
+        self.names.get(idx).map(|name| name.to_string())
+    }
+
+    /// Turns the snapshot into a Graph sharing its chunks, so only the mapping from names to indices is built.
+    pub fn to_graph(&self) -> Graph {
+        Graph {
+            num_of_vertices: self.num_of_vertices,
+            num_of_edges: self.num_of_edges,
+            neighbours: self.neighbours.clone(),
+            idx_to_name_map: self.names.clone(),
+            name_to_idx_map: self.names.iter().enumerate().map(|(idx, name)| (Arc::clone(name), idx)).collect(),
+            degree_hint: 0,
+            hinted_vertices: 0,
+        }
+    }
+}
+
+impl GraphOps for GraphSnapshot {
+    fn get_num_of_vertices(&self) -> usize {
+        self.num_of_vertices
+    }
+
+    fn get_num_of_edges(&self) -> usize {
+        self.num_of_edges
+    }
+
+    fn neighbours_idx(&self, idx: usize) -> Result<HashSet<usize>, &str> {
+        match self.neighbours_of(idx) {
+            Some(neighbours) => Ok(neighbours.iter().copied().collect()),
+            None => Err("Index does not exist in the graph."),
+        }
+    }
+
+    fn degree(&self, idx: usize) -> usize {
+        self.neighbours_of(idx).unwrap().len()
+    }
+
+    fn for_each_neighbour(&self, idx: usize, f: &mut dyn FnMut(usize)) {
+        self.neighbours_of(idx).unwrap().iter().for_each(|neighbour| f(*neighbour))
+    }
+}
+
+impl CowGraph {
+    /// Creates an empty graph.
+    pub fn new() -> CowGraph {
+        CowGraph::default()
+    }
+
+    /// Creates a graph sharing the chunks of given graph.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::{CowGraph, Graph, GraphOps};
+    /// let cow = CowGraph::from_graph(&Graph::petersen());
+    /// assert_eq!(15, cow.get_num_of_edges());
+    /// assert_eq!(15, cow.snapshot().to_graph().get_num_of_edges());
+    /// ```
+    pub fn from_graph(graph: &Graph) -> CowGraph {
+        CowGraph {
+            snapshot: GraphSnapshot::from_graph(graph),
+            name_to_idx_map: graph.name_to_idx_map.clone(),
+        }
+    }
+
+    /// Returns an immutable snapshot of the current state of the graph.
+    pub fn snapshot(&self) -> GraphSnapshot {
+        self.snapshot.clone()
+    }
+
+    /// Returns the index of the vertex with given name, or None if it does not exist.
+    pub fn name_to_idx(&self, name: &str) -> Option<usize> {
+        self.name_to_idx_map.get(name).copied()
+    }
+
+    /// Returns the name of the vertex with given index, or None if it does not exist.
+    pub fn idx_to_name(&self, idx: usize) -> Option<String> {
+        self.snapshot.idx_to_name(idx)
+    }
+
+    /// Adds a vertex with given name if it does not exist yet. Returns the index of the vertex.
+    pub fn add_vertex(&mut self, name: &str) -> usize {
+        if let Some(idx) = self.name_to_idx_map.get(name) {
+            return *idx
+        }
+        let graph = &mut self.snapshot;
+        let idx = graph.num_of_vertices;
+        let name: Arc<str> = Arc::from(name);
+        graph.neighbours.push(NeighbourSet::default());
+        graph.names.push(Arc::clone(&name));
+        graph.num_of_vertices += 1;
+        self.name_to_idx_map.insert(name, idx);
+        idx
+    }
+
+    /// Adds an edge between two vertices given by indices, copying their chunks if they are shared with a snapshot.
+    /// Returns true if the edge was added, i.e. both vertices exist, they are different and the edge did not exist before.
+    pub fn add_edge_idx(&mut self, from: usize, to: usize) -> bool {
+        let graph = &mut self.snapshot;
+        if from == to || from >= graph.num_of_vertices || to >= graph.num_of_vertices || graph.neighbours_of(from).unwrap().contains(&to) {
+            return false
+        }
+        graph.neighbours[from].insert(to);
+        graph.neighbours[to].insert(from);
+        graph.num_of_edges += 1;
+        true
+    }
+
+    /// Adds an edge between two vertices given by names, adding the vertices if they do not exist.
+    /// Returns true if the edge was added.
+    pub fn add_edge(&mut self, from: &str, to: &str) -> bool {
+        let from = self.add_vertex(from);
+        let to = self.add_vertex(to);
+        self.add_edge_idx(from, to)
+    }
+}
+
+impl GraphOps for CowGraph {
+    fn get_num_of_vertices(&self) -> usize {
+        self.snapshot.num_of_vertices
+    }
+
+    fn get_num_of_edges(&self) -> usize {
+        self.snapshot.num_of_edges
+    }
+
+    fn neighbours_idx(&self, idx: usize) -> Result<HashSet<usize>, &str> {
+        self.snapshot.neighbours_idx(idx)
+    }
+
+    fn degree(&self, idx: usize) -> usize {
+        self.snapshot.degree(idx)
+    }
+
+    fn for_each_neighbour(&self, idx: usize, f: &mut dyn FnMut(usize)) {
+        self.snapshot.for_each_neighbour(idx, f)
+    }
+}
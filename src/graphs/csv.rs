@@ -0,0 +1,38 @@
+use std::io::{Result, Write};
+
+use crate::atomic::AtomicFile;
+use super::Graph;
+
+/// Returns a field of a csv file, quoted if it contains a separator, a quote or a line break.
+pub(super) fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_owned()
+    }
+}
+
+impl Graph {
+    /// Writes the edges of the graph to a csv file with given filename as pairs of names (source, target),
+    /// with the smaller index first, in the order of the indices. Isolated vertices are not written.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let p3 = Graph::path(3);
+    /// p3.write_to_csv_edge_list("p3.csv").unwrap();
+    /// let contents = std::fs::read_to_string("p3.csv").unwrap();
+    /// assert_eq!("source,target\nvertex_0,vertex_1\nvertex_1,vertex_2\n", contents);
+    /// ```
+    pub fn write_to_csv_edge_list(&self, filename: &str) -> Result<()> {
+        let mut file = AtomicFile::create(filename)?;
+        writeln!(file, "source,target")?;
+        for from in self.vertices() {
+            let mut neighbours = self.neighbours[from].iter().filter(|to| from < **to).collect::<Vec<&usize>>();
+            neighbours.sort_unstable();
+            for to in neighbours {
+                writeln!(file, "{},{}", csv_field(&self.idx_to_name_map[from]), csv_field(&self.idx_to_name_map[*to]))?;
+            }
+        }
+        file.commit()
+    }
+}
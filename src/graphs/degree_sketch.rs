@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+
+/// The number of buckets per power of two above 2 * SUB_BUCKETS, which bounds the relative error of quantiles by 1 / SUB_BUCKETS.
+const SUB_BUCKETS: usize = 32;
+const SUB_BUCKETS_LOG: u32 = SUB_BUCKETS.ilog2();
+
+/// A sketch of the degree distribution of a growing graph, answering quantile queries approximately.
+/// Degrees are counted in log-linear buckets: degrees below 64 exactly, larger ones in 32 buckets per power of two,
+/// so a quantile is off by at most about 3% of its value and the sketch takes a few kilobytes for any graph.
+/// Unlike t-digest and similar sketches, a vertex can be moved to the next degree, so the sketch follows the graph while edges are added.
+/// # Examples
+/// ```
+/// use labisu::graphs::DegreeSketch;
+/// // a star with 1000 leaves, built edge by edge
+/// let mut sketch = DegreeSketch::new();
+/// (0..=1000).for_each(|_| sketch.insert(0));
+/// for center_degree in 0..1000 {
+///     sketch.increment(center_degree);
+///     sketch.increment(0);
+/// }
+/// assert_eq!(1001, sketch.count());
+/// assert_eq!(1, sketch.quantile(0.5));
+/// let max = sketch.quantile(1.0);
+/// assert!(970 <= max && max <= 1030);
+/// assert!((sketch.mean() - 2000.0 / 1001.0).abs() < 1e-9);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DegreeSketch {
+    buckets: Vec<u64>,
+    count: u64,
+    total: u64, // sum of all degrees
+}
+
+/// Returns the bucket of given degree.
+fn bucket(degree: usize) -> usize {
+    if degree < 2 * SUB_BUCKETS {
+        return degree
+    }
+    let shift = (degree.ilog2() - SUB_BUCKETS_LOG) as usize;
+    SUB_BUCKETS * (shift + 1) + (degree >> shift) - SUB_BUCKETS
+}
+
+/// Returns the smallest degree and the number of degrees in given bucket.
+fn bucket_range(bucket: usize) -> (usize, usize) {
+    if bucket < 2 * SUB_BUCKETS {
+        return (bucket, 1)
+    }
+    let shift = bucket / SUB_BUCKETS - 1;
+    ((bucket % SUB_BUCKETS + SUB_BUCKETS) << shift, 1 << shift)
+}
+
+impl DegreeSketch {
+    /// Creates an empty sketch.
+    pub fn new() -> DegreeSketch {
+        DegreeSketch::default()
+    }
+
+    /// Returns the number of vertices in the sketch.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the exact average degree of the vertices in the sketch, or 0 if it is empty.
+    pub fn mean(&self) -> f64 {
+        if self.count == 0 {
+            return 0.0
+        }
+        self.total as f64 / self.count as f64
+    }
+
+    /// Adds a vertex of given degree.
+    pub fn insert(&mut self, degree: usize) {
+        let bucket = bucket(degree);
+        if bucket >= self.buckets.len() {
+            self.buckets.resize(bucket + 1, 0);
+        }
+        self.buckets[bucket] += 1;
+        self.count += 1;
+        self.total += degree as u64;
+    }
+
+    /// Moves a vertex of given degree to the next degree, e.g. after adding an edge to it.
+    /// If there is no vertex in the bucket of the degree, a vertex of degree + 1 is inserted instead.
+    pub fn increment(&mut self, degree: usize) {
+        if let Some(count) = self.buckets.get_mut(bucket(degree)).filter(|count| **count > 0) {
+            *count -= 1;
+            self.count -= 1;
+            self.total -= degree as u64;
+        }
+        self.insert(degree + 1);
+    }
+
+    /// Returns approximately the degree d such that a fraction q of the vertices have degree at most d.
+    /// The result is the middle of the bucket containing the quantile, so it is exact for degrees below 64.
+    /// Returns 0 for an empty sketch.
+    pub fn quantile(&self, q: f64) -> usize {
+        let rank = (q.clamp(0.0, 1.0) * self.count as f64).ceil().max(1.0) as u64;
+        let mut seen = 0;
+        for (bucket, count) in self.buckets.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                let (low, width) = bucket_range(bucket);
+                return low + (width - 1) / 2
+            }
+        }
+        0
+    }
+
+    /// Returns approximate quantiles for the fractions given, e.g. [0.5, 0.9, 0.99].
+    pub fn quantiles(&self, qs: &[f64]) -> Vec<usize> {
+        qs.iter().map(|q| self.quantile(*q)).collect()
+    }
+
+    /// Adds all vertices of another sketch, e.g. of a different part of the graph.
+    pub fn merge(&mut self, other: &DegreeSketch) {
+        if other.buckets.len() > self.buckets.len() {
+            self.buckets.resize(other.buckets.len(), 0);
+        }
+        self.buckets.iter_mut().zip(&other.buckets).for_each(|(count, other)| *count += other);
+        self.count += other.count;
+        self.total += other.total;
+    }
+}
@@ -0,0 +1,141 @@
+use serde::{Deserialize, Serialize};
+
+use super::GraphOps;
+use crate::flow::FlowNetwork;
+
+/// A subgraph of a graph with many edges per vertex, found by densest_subgraph or densest_subgraph_exact.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DensestSubgraph {
+    /// The sorted indices of the vertices of the subgraph.
+    pub vertices: Vec<usize>,
+    /// The number of edges of the subgraph induced by the vertices.
+    pub num_of_edges: usize,
+}
+
+impl DensestSubgraph {
+    /// Returns the density of the subgraph, i.e. the number of its edges divided by the number of its vertices, or 0 if it is empty.
+    pub fn density(&self) -> f64 {
+        match self.vertices.len() {
+            0 => 0.0,
+            n => self.num_of_edges as f64 / n as f64,
+        }
+    }
+
+    /// Checks if the subgraph is strictly denser than other, comparing the densities exactly.
+    fn is_denser_than(&self, other: &DensestSubgraph) -> bool {
+        (self.num_of_edges * other.vertices.len()) as u128 > (other.num_of_edges * self.vertices.len()) as u128
+    }
+}
+
+/// Returns the subgraph induced by the vertices marked in members.
+fn induced<G: GraphOps>(graph: &G, members: &[bool]) -> DensestSubgraph {
+    let vertices = graph.vertices().filter(|v| members[*v]).collect::<Vec<usize>>();
+    let mut num_of_edges = 0;
+    for v in &vertices {
+        graph.for_each_neighbour(*v, &mut |u| {
+            if members[u] && *v < u {
+                num_of_edges += 1;
+            }
+        });
+    }
+    DensestSubgraph { vertices, num_of_edges }
+}
+
+/// Finds a subgraph of density at least half of the largest density of a subgraph, with Charikar's peeling:
+/// vertices of the smallest degree are removed one by one and the densest of the intermediate subgraphs is returned.
+/// It runs in time linear in the size of the graph, so it works as a preprocessing filter of the pool of a biclique search,
+/// as dense subgraphs are where large complete bipartite subgraphs are.
+/// # Examples
+/// ```
+/// use labisu::graphs::{densest_subgraph, Graph};
+/// let mut g = Graph::complete(5);
+/// (0..5).for_each(|i| { g.add_vertex(&format!("tail_{}", i)); });
+/// (4..9).for_each(|i| { g.add_edge_idx(i, i + 1); });
+/// let densest = densest_subgraph(&g);
+/// assert_eq!(vec![0, 1, 2, 3, 4], densest.vertices);
+/// assert_eq!(2.0, densest.density());
+/// assert!(densest_subgraph(&Graph::empty()).vertices.is_empty());
+/// ```
+pub fn densest_subgraph<G: GraphOps>(graph: &G) -> DensestSubgraph {
+    let n = graph.get_num_of_vertices();
+    let mut degrees = graph.vertices().map(|v| graph.degree(v)).collect::<Vec<usize>>();
+    // buckets of vertices by degree, with outdated entries skipped when popped
+    let mut buckets = vec![vec![]; degrees.iter().max().map_or(0, |max| max + 1)];
+    graph.vertices().for_each(|v| buckets[degrees[v]].push(v));
+    let mut removed = vec![false; n];
+    let mut order = Vec::with_capacity(n);
+    let mut num_of_edges = graph.get_num_of_edges();
+    let (mut best_edges, mut best_size, mut best_step) = (num_of_edges, n, 0);
+    let mut min_degree = 0;
+    while order.len() < n {
+        let v = loop {
+            match buckets[min_degree].pop() {
+                Some(v) if !removed[v] && degrees[v] == min_degree => break v,
+                Some(_) => {},
+                None => min_degree += 1,
+            }
+        };
+        removed[v] = true;
+        order.push(v);
+        num_of_edges -= degrees[v];
+        graph.for_each_neighbour(v, &mut |u| {
+            if !removed[u] {
+                degrees[u] -= 1;
+                buckets[degrees[u]].push(u);
+            }
+        });
+        min_degree = min_degree.saturating_sub(1);
+        let size = n - order.len();
+        if size > 0 && num_of_edges * best_size > best_edges * size {
+            (best_edges, best_size, best_step) = (num_of_edges, size, order.len());
+        }
+    }
+    let mut members = vec![true; n];
+    order[..best_step].iter().for_each(|v| members[*v] = false);
+    induced(graph, &members)
+}
+
+/// Finds a subgraph of the largest density exactly, with Goldberg's reduction to minimum cuts (see flow::FlowNetwork).
+/// Starting from the result of densest_subgraph, a denser subgraph is looked for with a cut in a network built for the current density,
+/// until there is none. Capacities are scaled to integers, so the result is exact, but every step computes a maximum flow,
+/// so it is meant for graphs up to about a hundred thousand edges.
+/// # Examples
+/// ```
+/// use labisu::graphs::{densest_subgraph, densest_subgraph_exact, Graph};
+/// let g = Graph::random(60, 0.2);
+/// let exact = densest_subgraph_exact(&g);
+/// let approximate = densest_subgraph(&g);
+/// assert!(exact.density() >= approximate.density());
+/// assert!(2.0 * approximate.density() >= exact.density());
+/// assert_eq!(1.5, densest_subgraph_exact(&Graph::complete(4)).density());
+/// ```
+pub fn densest_subgraph_exact<G: GraphOps>(graph: &G) -> DensestSubgraph {
+    let n = graph.get_num_of_vertices();
+    let m = graph.get_num_of_edges() as u64;
+    let (source, sink) = (n, n + 1);
+    let mut best = densest_subgraph(graph);
+    loop {
+        // a cut of capacity below n * m * size has a source side of density above num_of_edges / size
+        let (edges, size) = (best.num_of_edges as u64, best.vertices.len() as u64);
+        if size == 0 {
+            return best
+        }
+        let mut network = FlowNetwork::new(n + 2);
+        for v in graph.vertices() {
+            network.add_edge(source, v, m * size);
+            network.add_edge(v, sink, m * size + 2 * edges - graph.degree(v) as u64 * size);
+            graph.for_each_neighbour(v, &mut |u| {
+                if v < u {
+                    network.add_undirected_edge(v, u, size);
+                }
+            });
+        }
+        let (_, mut members) = network.min_cut(source, sink).unwrap();
+        members.truncate(n);
+        let candidate = induced(graph, &members);
+        if candidate.vertices.is_empty() || !candidate.is_denser_than(&best) {
+            return best
+        }
+        best = candidate;
+    }
+}
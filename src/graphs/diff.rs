@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use crate::atomic::AtomicFile;
+use super::Graph;
+
+/// The differences between two graphs whose vertices are matched by names, e.g. two crawls of the same website.
+/// Every edge is given by the names of its ends, the smaller name first, and all lists are sorted.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphDiff {
+    /// The names of the vertices present only in the new graph.
+    pub added_vertices: Vec<String>,
+    /// The names of the vertices present only in the old graph.
+    pub removed_vertices: Vec<String>,
+    /// The edges present only in the new graph.
+    pub added_edges: Vec<(String, String)>,
+    /// The edges present only in the old graph.
+    pub removed_edges: Vec<(String, String)>,
+}
+
+impl GraphDiff {
+    /// Returns true if both graphs have the same vertices and the same edges.
+    pub fn is_empty(&self) -> bool {
+        self.added_vertices.is_empty()
+            && self.removed_vertices.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+    }
+
+    /// Writes the differences together with their counts to a json file with given filename.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::{diff, Graph};
+    /// let old = Graph::path(3);
+    /// let new = Graph::cycle(3);
+    /// diff(&old, &new).write_to_json("diff.json").unwrap();
+    /// let contents = std::fs::read_to_string("diff.json").unwrap();
+    /// assert!(contents.contains("\"num_of_added_edges\": 1"));
+    /// ```
+    pub fn write_to_json(&self, filename: &str) -> serde_json::Result<()> {
+        let report = serde_json::json!({
+            "num_of_added_vertices": self.added_vertices.len(),
+            "num_of_removed_vertices": self.removed_vertices.len(),
+            "num_of_added_edges": self.added_edges.len(),
+            "num_of_removed_edges": self.removed_edges.len(),
+            "added_vertices": self.added_vertices,
+            "removed_vertices": self.removed_vertices,
+            "added_edges": self.added_edges,
+            "removed_edges": self.removed_edges,
+        });
+        let mut file = AtomicFile::create(filename).map_err(serde_json::Error::io)?;
+        serde_json::to_writer_pretty(&mut file, &report)?;
+        file.commit().map_err(serde_json::Error::io)
+    }
+}
+
+/// Returns the edges of a graph by names of their ends, the smaller name first.
+fn named_edges(graph: &Graph) -> HashSet<(&str, &str)> {
+    let mut edges = HashSet::with_capacity(graph.get_num_of_edges());
+    for from in graph.vertices() {
+        let from_name = graph.idx_to_name_ref(from).unwrap();
+        for to in graph.neighbours[from].iter().filter(|to| from < **to) {
+            let to_name = graph.idx_to_name_ref(*to).unwrap();
+            edges.insert(if from_name < to_name { (from_name, to_name) } else { (to_name, from_name) });
+        }
+    }
+    edges
+}
+
+/// Returns the vertices and edges added and removed between the old and the new graph, matching the vertices by names.
+/// # Examples
+/// ```
+/// use labisu::graphs::{diff, Graph};
+/// let mut old = Graph::from_names(vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+/// old.add_edge("a", "b");
+/// old.add_edge("b", "c");
+/// let mut new = Graph::from_names(vec!["b".to_string(), "a".to_string(), "d".to_string()]);
+/// new.add_edge("b", "a");
+/// new.add_edge("a", "d");
+/// let changes = diff(&old, &new);
+/// assert_eq!(vec!["d".to_string()], changes.added_vertices);
+/// assert_eq!(vec!["c".to_string()], changes.removed_vertices);
+/// assert_eq!(vec![("a".to_string(), "d".to_string())], changes.added_edges);
+/// assert_eq!(vec![("b".to_string(), "c".to_string())], changes.removed_edges);
+/// assert!(diff(&new, &new).is_empty());
+/// ```
+pub fn diff(old: &Graph, new: &Graph) -> GraphDiff {
+    let only_in = |graph: &Graph, other: &Graph| {
+        let mut names =
+            graph
+            .vertices()
+            .map(|v| graph.idx_to_name_ref(v).unwrap())
+            .filter(|name| !other.contains_vertex(name))
+            .map(str::to_owned)
+            .collect::<Vec<String>>();
+        names.sort_unstable();
+        names
+    };
+    let (old_edges, new_edges) = (named_edges(old), named_edges(new));
+    let edges_only_in = |edges: &HashSet<(&str, &str)>, other: &HashSet<(&str, &str)>| {
+        let mut edges =
+            edges
+            .difference(other)
+            .map(|(from, to)| (from.to_string(), to.to_string()))
+            .collect::<Vec<(String, String)>>();
+        edges.sort_unstable();
+        edges
+    };
+
+    GraphDiff {
+        added_vertices: only_in(new, old),
+        removed_vertices: only_in(old, new),
+        added_edges: edges_only_in(&new_edges, &old_edges),
+        removed_edges: edges_only_in(&old_edges, &new_edges),
+    }
+}
@@ -0,0 +1,275 @@
+use std::f64::consts::PI;
+use std::fmt::Write as _;
+use std::io::{Error, ErrorKind, Result};
+
+use super::Graph;
+
+/// A color given by its red, green and blue components.
+pub type Rgb = (u8, u8, u8);
+
+/// The width and the height of a drawing in svg.
+const SVG_SIZE: f64 = 800.0;
+/// The radius of a vertex in svg.
+const SVG_VERTEX_RADIUS: f64 = 8.0;
+
+/// The shape of a vertex in a drawing, named as in Graphviz.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shape {
+    Circle,
+    Box,
+    Triangle,
+    Diamond,
+}
+
+impl Shape {
+    /// Returns the name of the shape in the dot language.
+    fn dot_name(&self) -> &'static str {
+        match self {
+            Shape::Circle => "circle",
+            Shape::Box => "box",
+            Shape::Triangle => "triangle",
+            Shape::Diamond => "diamond",
+        }
+    }
+
+    /// Returns the svg element of the shape centered at given point.
+    fn svg_element(&self, x: f64, y: f64, fill: &str) -> String {
+        let r = SVG_VERTEX_RADIUS;
+        match self {
+            Shape::Circle => format!("<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{}\" fill=\"{}\"/>", x, y, r, fill),
+            Shape::Box => format!("<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>", x - r, y - r, 2.0 * r, 2.0 * r, fill),
+            Shape::Triangle => format!(
+                "<polygon points=\"{:.1},{:.1} {:.1},{:.1} {:.1},{:.1}\" fill=\"{}\"/>",
+                x, y - r, x - r, y + r, x + r, y + r, fill
+            ),
+            Shape::Diamond => format!(
+                "<polygon points=\"{:.1},{:.1} {:.1},{:.1} {:.1},{:.1} {:.1},{:.1}\" fill=\"{}\"/>",
+                x, y - r, x + r, y, x, y + r, x - r, y, fill
+            ),
+        }
+    }
+}
+
+/// The rule coloring vertices.
+#[derive(Clone, Debug, PartialEq)]
+enum ColorRule {
+    /// Numeric values of the attribute are mapped linearly onto the gradient from the smallest to the largest value.
+    Gradient { attribute: String, from: Rgb, to: Rgb },
+    /// Values of the attribute are given colors.
+    Categories { attribute: String, colors: Vec<(String, Rgb)> },
+}
+
+/// The declarative description of how vertices of a drawing are styled by their attributes,
+/// used by write_to_dot and write_to_svg. The attributes are given to the exporters as pairs of a key and the values of all vertices,
+/// like in write_sql_with_attributes. Vertices without a matching value get the default color and shape.
+/// # Examples
+/// ```
+/// use labisu::graphs::{Shape, VertexStyle};
+/// let style =
+///     VertexStyle::new()
+///     .with_gradient("depth", (255, 255, 255), (0, 0, 255))
+///     .with_shapes("tag", &[("news", Shape::Box), ("pdf", Shape::Triangle)])
+///     .with_default_shape(Shape::Diamond);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct VertexStyle {
+    color: Option<ColorRule>,
+    shapes: Option<(String, Vec<(String, Shape)>)>,
+    default_color: Rgb,
+    default_shape: Shape,
+}
+
+impl Default for VertexStyle {
+    fn default() -> VertexStyle {
+        VertexStyle {
+            color: None,
+            shapes: None,
+            default_color: (211, 211, 211),
+            default_shape: Shape::Circle,
+        }
+    }
+}
+
+/// Returns the color in the hexadecimal notation used both by dot and svg.
+fn hex(color: Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.0, color.1, color.2)
+}
+
+/// Escapes the characters special in xml.
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+impl VertexStyle {
+    /// Creates a style drawing every vertex as a light gray circle.
+    pub fn new() -> VertexStyle {
+        VertexStyle::default()
+    }
+
+    /// Colors vertices by a numeric attribute, e.g. the crawl depth, with the smallest value colored from and the largest one colored to.
+    /// Replaces the coloring by categories. Vertices whose values are not numbers get the default color.
+    pub fn with_gradient(mut self, attribute: &str, from: Rgb, to: Rgb) -> VertexStyle {
+        self.color = Some(ColorRule::Gradient { attribute: attribute.to_owned(), from, to });
+        self
+    }
+
+    /// Colors vertices by the value of an attribute, e.g. the host. Replaces the coloring by a gradient.
+    pub fn with_colors(mut self, attribute: &str, colors: &[(&str, Rgb)]) -> VertexStyle {
+        let colors = colors.iter().map(|(value, color)| (value.to_string(), *color)).collect();
+        self.color = Some(ColorRule::Categories { attribute: attribute.to_owned(), colors });
+        self
+    }
+
+    /// Shapes vertices by the value of an attribute, e.g. a tag.
+    pub fn with_shapes(mut self, attribute: &str, shapes: &[(&str, Shape)]) -> VertexStyle {
+        self.shapes = Some((attribute.to_owned(), shapes.iter().map(|(value, shape)| (value.to_string(), *shape)).collect()));
+        self
+    }
+
+    /// Sets the color of vertices not colored by an attribute.
+    pub fn with_default_color(mut self, color: Rgb) -> VertexStyle {
+        self.default_color = color;
+        self
+    }
+
+    /// Sets the shape of vertices not shaped by an attribute.
+    pub fn with_default_shape(mut self, shape: Shape) -> VertexStyle {
+        self.default_shape = shape;
+        self
+    }
+
+    /// Returns the color and the shape of every vertex, or an error of kind InvalidInput
+    /// if an attribute used by the style is missing or does not have a value for each vertex.
+    fn resolve(&self, num_of_vertices: usize, attributes: &[(&str, &[String])]) -> Result<Vec<(Rgb, Shape)>> {
+        let values = |attribute: &str| {
+            match attributes.iter().find(|(key, _)| *key == attribute) {
+                Some((_, values)) if values.len() == num_of_vertices => Ok(*values),
+                Some(_) => Err(Error::new(ErrorKind::InvalidInput, "Every attribute has to have a value for each vertex.")),
+                None => Err(Error::new(ErrorKind::InvalidInput, format!("The attribute {} is missing.", attribute))),
+            }
+        };
+        let mut styles = vec![(self.default_color, self.default_shape); num_of_vertices];
+        match &self.color {
+            Some(ColorRule::Gradient { attribute, from, to }) => {
+                let numbers = values(attribute)?.iter().map(|value| value.trim().parse::<f64>().ok()).collect::<Vec<Option<f64>>>();
+                let min = numbers.iter().flatten().copied().fold(f64::INFINITY, f64::min);
+                let max = numbers.iter().flatten().copied().fold(f64::NEG_INFINITY, f64::max);
+                let mix = |from: u8, to: u8, t: f64| (from as f64 + (to as f64 - from as f64) * t).round() as u8;
+                for (style, number) in styles.iter_mut().zip(numbers) {
+                    if let Some(number) = number {
+                        let t = if max > min { (number - min) / (max - min) } else { 0.0 };
+                        style.0 = (mix(from.0, to.0, t), mix(from.1, to.1, t), mix(from.2, to.2, t));
+                    }
+                }
+            },
+            Some(ColorRule::Categories { attribute, colors }) => {
+                for (style, value) in styles.iter_mut().zip(values(attribute)?) {
+                    if let Some((_, color)) = colors.iter().find(|(category, _)| category == value) {
+                        style.0 = *color;
+                    }
+                }
+            },
+            None => {},
+        }
+        if let Some((attribute, shapes)) = &self.shapes {
+            for (style, value) in styles.iter_mut().zip(values(attribute)?) {
+                if let Some((_, shape)) = shapes.iter().find(|(category, _)| category == value) {
+                    style.1 = *shape;
+                }
+            }
+        }
+        Ok(styles)
+    }
+}
+
+impl Graph {
+    /// Returns the edges with the smaller index first, sorted.
+    fn sorted_edges(&self) -> Vec<(usize, usize)> {
+        let mut edges =
+            self.vertices()
+            .flat_map(|from| self.neighbours[from].iter().filter(move |to| from < **to).map(move |to| (from, *to)))
+            .collect::<Vec<(usize, usize)>>();
+        edges.sort_unstable();
+        edges
+    }
+
+    /// Returns the graph in the dot language of Graphviz, with vertices labeled by names and styled by their attributes.
+    /// Returns an error of kind InvalidInput if an attribute used by the style is missing or does not have a value for each vertex.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::{Graph, Shape, VertexStyle};
+    /// use labisu::traversal::bfs;
+    /// let path = Graph::path(3);
+    /// let depths = bfs(&path, &[0]).iter().map(|depth| depth.unwrap().to_string()).collect::<Vec<String>>();
+    /// let tags = vec!["root".to_owned(), "".to_owned(), "".to_owned()];
+    /// let style = VertexStyle::new().with_gradient("depth", (255, 255, 255), (0, 0, 255)).with_shapes("tag", &[("root", Shape::Box)]);
+    /// let dot = path.to_dot(&[("depth", &depths), ("tag", &tags)], &style).unwrap();
+    /// assert!(dot.contains("0 [label=\"vertex_0\", shape=box, style=filled, fillcolor=\"#ffffff\"];"));
+    /// assert!(dot.contains("2 [label=\"vertex_2\", shape=circle, style=filled, fillcolor=\"#0000ff\"];"));
+    /// assert!(dot.contains("0 -- 1;"));
+    /// assert!(path.to_dot(&[("depth", &depths)], &style).is_err());
+    /// ```
+    pub fn to_dot(&self, attributes: &[(&str, &[String])], style: &VertexStyle) -> Result<String> {
+        let styles = style.resolve(self.num_of_vertices, attributes)?;
+        let mut dot = String::from("graph {\n");
+        for (v, (color, shape)) in styles.iter().enumerate() {
+            let label = self.idx_to_name_map[v].replace('\\', "\\\\").replace('"', "\\\"");
+            let _ = writeln!(dot, "  {} [label=\"{}\", shape={}, style=filled, fillcolor=\"{}\"];", v, label, shape.dot_name(), hex(*color));
+        }
+        for (from, to) in self.sorted_edges() {
+            let _ = writeln!(dot, "  {} -- {};", from, to);
+        }
+        dot.push_str("}\n");
+        Ok(dot)
+    }
+
+    /// Writes the graph to a file with given filename in the dot language (see to_dot), e.g. to be laid out with `dot -Tsvg`.
+    pub fn write_to_dot(&self, filename: &str, attributes: &[(&str, &[String])], style: &VertexStyle) -> Result<()> {
+        crate::atomic::write(filename, self.to_dot(attributes, style)?)
+    }
+
+    /// Returns a drawing of the graph in svg, with vertices placed on a circle in order of their indices and styled by their attributes.
+    /// The name of a vertex is shown when the pointer is over it. For larger graphs a layout computed by Graphviz from to_dot is usually clearer.
+    /// Returns an error of kind InvalidInput if an attribute used by the style is missing or does not have a value for each vertex.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::{Graph, VertexStyle};
+    /// let k3 = Graph::complete(3);
+    /// let hosts = vec!["a.com".to_owned(), "b.com".to_owned(), "a.com".to_owned()];
+    /// let style = VertexStyle::new().with_colors("host", &[("a.com", (255, 0, 0))]);
+    /// let svg = k3.to_svg(&[("host", &hosts)], &style).unwrap();
+    /// assert!(svg.starts_with("<svg"));
+    /// assert_eq!(3, svg.matches("<line").count());
+    /// assert_eq!(2, svg.matches("fill=\"#ff0000\"").count());
+    /// assert_eq!(1, svg.matches("fill=\"#d3d3d3\"").count());
+    /// ```
+    pub fn to_svg(&self, attributes: &[(&str, &[String])], style: &VertexStyle) -> Result<String> {
+        let styles = style.resolve(self.num_of_vertices, attributes)?;
+        let center = SVG_SIZE / 2.0;
+        let radius = center - 2.0 * SVG_VERTEX_RADIUS;
+        let positions =
+            (0..self.num_of_vertices)
+            .map(|v| {
+                let angle = 2.0 * PI * v as f64 / self.num_of_vertices as f64;
+                (center + radius * angle.cos(), center + radius * angle.sin())
+            })
+            .collect::<Vec<(f64, f64)>>();
+
+        let mut svg = format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{0}\" viewBox=\"0 0 {0} {0}\">\n", SVG_SIZE);
+        for (from, to) in self.sorted_edges() {
+            let ((x1, y1), (x2, y2)) = (positions[from], positions[to]);
+            let _ = writeln!(svg, "<line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" stroke=\"#999999\"/>", x1, y1, x2, y2);
+        }
+        for (v, (color, shape)) in styles.iter().enumerate() {
+            let (x, y) = positions[v];
+            let _ = writeln!(svg, "<g><title>{}</title>{}</g>", xml_escape(&self.idx_to_name_map[v]), shape.svg_element(x, y, &hex(*color)));
+        }
+        svg.push_str("</svg>\n");
+        Ok(svg)
+    }
+
+    /// Writes the drawing of the graph in svg (see to_svg) to a file with given filename.
+    pub fn write_to_svg(&self, filename: &str, attributes: &[(&str, &[String])], style: &VertexStyle) -> Result<()> {
+        crate::atomic::write(filename, self.to_svg(attributes, style)?)
+    }
+}
@@ -0,0 +1,126 @@
+use serde::{Deserialize, Serialize};
+
+use super::GraphOps;
+
+/// Parameters of the Erdos-Renyi G(n, p) model fitted to a graph.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GnpFit {
+    /// The maximum likelihood estimate of the edge probability, i.e. the density of the graph.
+    pub p: f64,
+    /// The log-likelihood of the graph in the fitted model.
+    pub log_likelihood: f64,
+    /// The Kolmogorov-Smirnov distance between the degree distribution and Binomial(n - 1, p).
+    pub ks_statistic: f64,
+}
+
+/// Parameters of a power law degree distribution P(d) ~ d^(-alpha) for d >= x_min fitted to a graph.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PowerLawFit {
+    /// The estimated exponent.
+    pub alpha: f64,
+    /// The smallest degree following the power law.
+    pub x_min: usize,
+    /// The number of vertices with degree at least x_min.
+    pub tail_size: usize,
+    /// The Kolmogorov-Smirnov distance between the degrees in the tail and the fitted distribution.
+    pub ks_statistic: f64,
+}
+
+/// Returns the sorted degree sequence of a graph.
+fn sorted_degrees<G: GraphOps>(graph: &G) -> Vec<usize> {
+    let mut degrees: Vec<usize> = graph.vertices().map(|v| graph.degree(v)).collect();
+    degrees.sort_unstable();
+    degrees
+}
+
+/// Returns the largest distance between the empirical distribution function of sorted values and a given distribution function.
+fn ks_distance<F: Fn(usize) -> f64>(sorted: &[usize], cdf: F) -> f64 {
+    let len = sorted.len() as f64;
+    let mut distance: f64 = 0.0;
+    let mut i = 0;
+    while i < sorted.len() {
+        let x = sorted[i];
+        let below = i as f64 / len;
+        while i < sorted.len() && sorted[i] == x {
+            i += 1;
+        }
+        let up_to = i as f64 / len;
+        let model = cdf(x);
+        let model_below = if x == 0 { 0.0 } else { cdf(x - 1) };
+        distance = distance.max((up_to - model).abs()).max((below - model_below).abs());
+    }
+    distance
+}
+
+/// Fits the G(n, p) model to a graph.
+/// # Examples
+/// ```
+/// use labisu::graphs::{fit_gnp, Graph};
+/// let fit = fit_gnp(&Graph::random(400, 0.1));
+/// assert!((fit.p - 0.1).abs() < 0.01);
+/// assert!(fit.ks_statistic < 0.1);
+/// let complete = fit_gnp(&Graph::complete(10));
+/// assert_eq!(1.0, complete.p);
+/// assert_eq!(0.0, complete.ks_statistic);
+/// ```
+pub fn fit_gnp<G: GraphOps>(graph: &G) -> GnpFit {
+    let n = graph.get_num_of_vertices();
+    let m = graph.get_num_of_edges() as f64;
+    let pairs = (n * n.saturating_sub(1) / 2) as f64;
+    let p = if pairs > 0.0 { m / pairs } else { 0.0 };
+    let log_likelihood = 
+        if p == 0.0 || p == 1.0 { 0.0 } 
+        else { m * p.ln() + (pairs - m) * (1.0 - p).ln() };
+
+    // distribution function of Binomial(n - 1, p), computed in log space to avoid underflow
+    let trials = n.saturating_sub(1);
+    let mut cdf = Vec::with_capacity(trials + 1);
+    let mut acc = 0.0;
+    let mut ln_binomial = 0.0;
+    for k in 0..=trials {
+        if k > 0 {
+            ln_binomial += ((trials - k + 1) as f64).ln() - (k as f64).ln();
+        }
+        acc += 
+            if p == 0.0 { (k == 0) as usize as f64 } 
+            else if p == 1.0 { (k == trials) as usize as f64 } 
+            else { (ln_binomial + k as f64 * p.ln() + (trials - k) as f64 * (1.0 - p).ln()).exp() };
+        cdf.push(acc.min(1.0));
+    }
+    let ks_statistic = ks_distance(&sorted_degrees(graph), |d| cdf.get(d).copied().unwrap_or(1.0));
+
+    GnpFit { p, log_likelihood, ks_statistic }
+}
+
+/// Fits a power law to the degree distribution of a graph with the method of Clauset, Shalizi and Newman:
+/// for every candidate x_min the exponent is estimated by (the discrete approximation of) maximum likelihood
+/// and the x_min minimizing the Kolmogorov-Smirnov distance is chosen.
+/// Returns None if there are no vertices of positive degree or all of them have the same degree.
+/// # Examples
+/// ```
+/// use labisu::graphs::{fit_power_law, Graph};
+/// let mut star = Graph::from_names((0..50).map(|i| format!("v_{}", i)).collect());
+/// (1..50).for_each(|i| { star.add_edge_idx(0, i); });
+/// assert!(fit_power_law(&Graph::complete(5)).is_none());
+/// let fit = fit_power_law(&star).unwrap();
+/// assert_eq!(1, fit.x_min);
+/// assert!(fit.alpha > 1.0);
+/// ```
+pub fn fit_power_law<G: GraphOps>(graph: &G) -> Option<PowerLawFit> {
+    let degrees: Vec<usize> = sorted_degrees(graph).into_iter().filter(|d| *d > 0).collect();
+    let mut candidates = degrees.clone();
+    candidates.dedup();
+    candidates.pop(); // the tail of the largest degree alone has no spread
+
+    candidates
+    .into_iter()
+    .map(|x_min| {
+        let tail = &degrees[degrees.partition_point(|d| *d < x_min)..];
+        let shift = x_min as f64 - 0.5;
+        let log_sum: f64 = tail.iter().map(|d| (*d as f64 / shift).ln()).sum();
+        let alpha = 1.0 + tail.len() as f64 / log_sum;
+        let cdf = |d: usize| 1.0 - ((d as f64 + 0.5) / shift).powf(1.0 - alpha);
+        PowerLawFit { alpha, x_min, tail_size: tail.len(), ks_statistic: ks_distance(tail, cdf) }
+    })
+    .min_by(|a, b| a.ks_statistic.total_cmp(&b.ks_statistic))
+}
@@ -0,0 +1,190 @@
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+
+use super::Graph;
+
+/// The way inconsistencies of imported data, e.g. of edge lists written by other tools, are resolved.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ImportPolicy {
+    /// Every inconsistency is an error: a repeated name, a loop, an edge listed by only one of its ends or a wrong number of edges.
+    #[default]
+    Strict,
+    /// The vertices with the same name are merged into the first of them, which gets the union of their neighbourhoods.
+    /// Loops are dropped, edges listed by only one end are added and the number of edges is recomputed.
+    Merge,
+    /// A repeated name gets the smallest suffix _1, _2, ... making it unique, so the vertices stay distinct.
+    /// Loops are dropped, edges listed by only one end are added and the number of edges is recomputed.
+    RenameSuffix,
+}
+
+/// The reason imported data could not be turned into a graph.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ImportError {
+    /// The file cannot be read or it is not in the expected format.
+    Format(String),
+    /// The name is given to more than one vertex.
+    DuplicateName(String),
+    /// An edge has an end with an index out of range.
+    EndpointOutOfRange(usize),
+    /// An edge joins the vertex with given name with itself.
+    SelfLoop(String),
+    /// The edge between the vertices with given indices is listed in the neighbourhood of only one of them.
+    Asymmetric(usize, usize),
+    /// The stated number of edges differs from the number of edges found.
+    EdgeCountMismatch { stated: usize, found: usize },
+}
+
+impl std::fmt::Display for ImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImportError::Format(message) => write!(f, "invalid input: {}", message),
+            ImportError::DuplicateName(name) => write!(f, "more than one vertex is named {}", name),
+            ImportError::EndpointOutOfRange(idx) => write!(f, "no vertex has index {}", idx),
+            ImportError::SelfLoop(name) => write!(f, "the vertex {} is joined with itself", name),
+            ImportError::Asymmetric(from, to) => write!(f, "the edge {} -- {} is listed only by vertex {}", from, to, from),
+            ImportError::EdgeCountMismatch { stated, found } => write!(f, "{} edges are stated but {} are found", stated, found),
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+/// The layouts of json files written by Graph::write_to_json and Graph::write_to_json_edge_list.
+#[derive(Deserialize)]
+pub(super) struct JsonLayout {
+    pub(super) num_of_vertices: usize,
+    pub(super) num_of_edges: usize,
+    pub(super) names: Vec<String>,
+    pub(super) neighbours: Option<Vec<Vec<usize>>>,
+    pub(super) edges: Option<Vec<(usize, usize)>>,
+}
+
+/// Maps every given name to the index of its vertex in the graph according to the policy.
+/// Returns the names of the vertices of the graph and the index of the vertex of every given name.
+fn resolve_names(names: Vec<String>, policy: ImportPolicy) -> Result<(Vec<String>, Vec<usize>), ImportError> {
+    let mut indices: HashMap<String, usize> = HashMap::with_capacity(names.len());
+    let mut unique_names = Vec::with_capacity(names.len());
+    let mut targets = Vec::with_capacity(names.len());
+    for name in names {
+        let name = match indices.get(&name) {
+            None => name,
+            Some(idx) => match policy {
+                ImportPolicy::Strict => return Err(ImportError::DuplicateName(name)),
+                ImportPolicy::Merge => {
+                    targets.push(*idx);
+                    continue
+                },
+                ImportPolicy::RenameSuffix =>
+                    (1..)
+                    .map(|suffix| format!("{}_{}", name, suffix))
+                    .find(|renamed| !indices.contains_key(renamed))
+                    .unwrap(),
+            },
+        };
+        indices.insert(name.clone(), unique_names.len());
+        targets.push(unique_names.len());
+        unique_names.push(name);
+    }
+    Ok((unique_names, targets))
+}
+
+impl Graph {
+    /// Creates a graph with vertices with given names and edges given by pairs of indices into names,
+    /// resolving repeated names, loops and repeated edges according to the policy.
+    /// With ImportPolicy::Strict a repeated edge is allowed and added once.
+    /// An edge with an index out of range is an error regardless of the policy.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::{Graph, ImportError, ImportPolicy};
+    /// let names = vec!["a".to_string(), "b".to_string(), "a".to_string()];
+    /// let edges = [(0, 1), (1, 2)];
+    /// let error = Graph::from_edges_with_policy(names.clone(), &edges, ImportPolicy::Strict).err();
+    /// assert_eq!(Some(ImportError::DuplicateName("a".to_string())), error);
+    ///
+    /// let merged = Graph::from_edges_with_policy(names.clone(), &edges, ImportPolicy::Merge).unwrap();
+    /// assert_eq!(2, merged.get_num_of_vertices());
+    /// assert_eq!(1, merged.get_num_of_edges());
+    ///
+    /// let renamed = Graph::from_edges_with_policy(names.clone(), &edges, ImportPolicy::RenameSuffix).unwrap();
+    /// assert_eq!(Some(2), renamed.name_to_idx("a_1"));
+    /// assert!(renamed.contains_edge("b", "a_1"));
+    ///
+    /// assert!(Graph::from_edges_with_policy(names, &[(0, 3)], ImportPolicy::Merge).is_err());
+    /// ```
+    pub fn from_edges_with_policy(names: Vec<String>, edges: &[(usize, usize)], policy: ImportPolicy) -> Result<Graph, ImportError> {
+        let num_of_names = names.len();
+        let (unique_names, targets) = resolve_names(names, policy)?;
+        let mut graph = Graph::from_names(unique_names);
+        for (from, to) in edges {
+            if let Some(idx) = [*from, *to].into_iter().find(|idx| *idx >= num_of_names) {
+                return Err(ImportError::EndpointOutOfRange(idx))
+            }
+            let (from, to) = (targets[*from], targets[*to]);
+            if from == to {
+                if policy == ImportPolicy::Strict {
+                    return Err(ImportError::SelfLoop(graph.idx_to_name(from).unwrap()))
+                }
+                continue
+            }
+            graph.add_edge_idx(from, to);
+        }
+        Ok(graph)
+    }
+
+    /// Reads a graph from a json file with given filename in any layout accepted by read_from_json,
+    /// resolving repeated names and inconsistent edges according to the policy instead of trusting the file.
+    /// With ImportPolicy::Strict the neighbourhoods have to be symmetric and the stated numbers of vertices and edges have to be right.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::{Graph, ImportError, ImportPolicy};
+    /// let messy = r#"{"num_of_vertices": 3, "num_of_edges": 1, "names": ["a", "b", "b"], "neighbours": [[1], [], [0]]}"#;
+    /// std::fs::write("messy.json", messy).unwrap();
+    /// let error = Graph::read_from_json_with_policy("messy.json", ImportPolicy::Strict).err();
+    /// assert_eq!(Some(ImportError::DuplicateName("b".to_string())), error);
+    ///
+    /// let merged = Graph::read_from_json_with_policy("messy.json", ImportPolicy::Merge).unwrap();
+    /// assert_eq!(2, merged.get_num_of_vertices());
+    /// assert!(merged.contains_edge("a", "b"));
+    ///
+    /// let renamed = Graph::read_from_json_with_policy("messy.json", ImportPolicy::RenameSuffix).unwrap();
+    /// assert_eq!(3, renamed.get_num_of_vertices());
+    /// assert_eq!(2, renamed.get_num_of_edges());
+    ///
+    /// Graph::petersen().write_to_json("petersen.json").unwrap();
+    /// assert!(Graph::read_from_json_with_policy("petersen.json", ImportPolicy::Strict).is_ok());
+    /// ```
+    pub fn read_from_json_with_policy(filename: &str, policy: ImportPolicy) -> Result<Graph, ImportError> {
+        let data = std::fs::read_to_string(filename).map_err(|e| ImportError::Format(e.to_string()))?;
+        let layout: JsonLayout = serde_json::from_str(&data).map_err(|e| ImportError::Format(e.to_string()))?;
+        if policy == ImportPolicy::Strict && layout.names.len() != layout.num_of_vertices {
+            return Err(ImportError::Format(format!("{} vertices are stated but {} names are given", layout.num_of_vertices, layout.names.len())))
+        }
+        let (edges, symmetric) = match (layout.edges, layout.neighbours) {
+            (Some(edges), _) => (edges, true),
+            (None, Some(neighbours)) => {
+                let edges =
+                    neighbours
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(from, set)| set.iter().map(move |to| (from, *to)))
+                    .collect::<Vec<(usize, usize)>>();
+                (edges, false)
+            },
+            (None, None) => return Err(ImportError::Format("neither edges nor neighbours are given".to_owned())),
+        };
+
+        let graph = Graph::from_edges_with_policy(layout.names, &edges, policy)?;
+        if policy == ImportPolicy::Strict {
+            if !symmetric {
+                let listed = edges.iter().copied().collect::<HashSet<(usize, usize)>>();
+                if let Some((from, to)) = edges.iter().find(|(from, to)| !listed.contains(&(*to, *from))) {
+                    return Err(ImportError::Asymmetric(*from, *to))
+                }
+            }
+            if graph.get_num_of_edges() != layout.num_of_edges {
+                return Err(ImportError::EdgeCountMismatch { stated: layout.num_of_edges, found: graph.get_num_of_edges() })
+            }
+        }
+        Ok(graph)
+    }
+}
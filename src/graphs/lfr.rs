@@ -0,0 +1,202 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use super::Graph;
+use crate::combinatorics::WeightedSampler;
+
+/// Parameters of the Lancichinetti-Fortunato-Radicchi (LFR) benchmark.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LfrParameters {
+    /// The number of vertices.
+    pub num_of_vertices: usize,
+    /// The expected average degree.
+    pub average_degree: f64,
+    /// The largest degree.
+    pub max_degree: usize,
+    /// The exponent of the power law of degrees, usually between 2 and 3.
+    pub degree_exponent: f64,
+    /// The exponent of the power law of community sizes, usually between 1 and 2.
+    pub community_exponent: f64,
+    /// The mixing parameter, i.e. the fraction of the edges of each vertex leading outside its community.
+    pub mixing: f64,
+    /// The smallest size of a community.
+    pub min_community: usize,
+    /// The largest size of a community.
+    pub max_community: usize,
+}
+
+/// A power law distribution P(x) ~ x^(-exponent) over the integers from min to max.
+struct PowerLaw {
+    min: usize,
+    sampler: WeightedSampler,
+}
+
+impl PowerLaw {
+    fn new(min: usize, max: usize, exponent: f64) -> PowerLaw {
+        let weights = (min..=max).map(|x| (x as f64).powf(-exponent)).collect::<Vec<f64>>();
+        PowerLaw { min, sampler: WeightedSampler::new(&weights).expect("The weights of a power law are positive.") }
+    }
+
+    fn sample<R: Rng>(&self, rng: &mut R) -> usize {
+        self.min + self.sampler.sample(rng)
+    }
+}
+
+/// Returns the value of the inverse distribution function at u of the continuous power law x^(-exponent) on [min, max].
+fn power_law_quantile(min: f64, max: f64, exponent: f64, u: f64) -> f64 {
+    let e = 1.0 - exponent;
+    if e.abs() < 1e-9 {
+        min * (max / min).powf(u)
+    } else {
+        (min.powf(e) + u * (max.powf(e) - min.powf(e))).powf(1.0 / e)
+    }
+}
+
+/// Returns the mean of the continuous power law x^(-exponent) on [min, max], by the midpoint rule over quantiles.
+fn power_law_mean(min: f64, max: f64, exponent: f64) -> f64 {
+    const STEPS: usize = 1000;
+    (0..STEPS).map(|i| power_law_quantile(min, max, exponent, (i as f64 + 0.5) / STEPS as f64)).sum::<f64>() / STEPS as f64
+}
+
+/// Pairs the stubs of the configuration model at random, adding the edges accepted by the predicate.
+/// Rejected stubs are shuffled and paired again a few times, the ones left at the end are dropped.
+fn pair_stubs<R: Rng, F: Fn(usize, usize) -> bool>(graph: &mut Graph, mut stubs: Vec<usize>, accept: F, rng: &mut R) {
+    for _ in 0..10 {
+        stubs.shuffle(rng);
+        let mut rejected = vec![];
+        for pair in stubs.chunks_exact(2) {
+            let (v, u) = (pair[0], pair[1]);
+            if v == u || !accept(v, u) || !graph.add_edge_idx(v, u) {
+                rejected.extend_from_slice(pair);
+            }
+        }
+        if rejected.len() < 2 {
+            return
+        }
+        stubs = rejected;
+    }
+}
+
+/// Generates a graph of the LFR benchmark, in which both the degrees and the sizes of planted communities follow power laws.
+/// Returns the graph together with the community of every vertex, numbered from 0.
+/// Degrees are rounded samples of a continuous power law, whose smallest value is chosen so that its mean is the given average degree.
+/// Each vertex gets round((1 - mixing) * degree) edges within its community, wired with the configuration model like the remaining ones,
+/// so the realized degrees may be slightly lower because of rejected loops and multiple edges.
+/// The graph depends only on the parameters and the seed.
+/// # Examples
+/// ```
+/// use labisu::graphs::{lfr_benchmark, LfrParameters};
+/// let parameters = LfrParameters {
+///     num_of_vertices: 500,
+///     average_degree: 10.0,
+///     max_degree: 40,
+///     degree_exponent: 2.0,
+///     community_exponent: 1.5,
+///     mixing: 0.1,
+///     min_community: 20,
+///     max_community: 60,
+/// };
+/// let (graph, communities) = lfr_benchmark(&parameters, 7).unwrap();
+/// assert_eq!(graph.get_num_of_edges(), lfr_benchmark(&parameters, 7).unwrap().0.get_num_of_edges());
+/// assert_eq!(500, graph.get_num_of_vertices());
+/// assert_eq!(500, communities.len());
+/// let average_degree = 2.0 * graph.get_num_of_edges() as f64 / 500.0;
+/// assert!(7.0 < average_degree && average_degree < 12.0);
+/// let between = graph.vertices().map(|v| graph.neighbours_idx(v).unwrap().iter().filter(|u| communities[**u] != communities[v]).count()).sum::<usize>();
+/// assert!((between as f64) < 0.2 * 2.0 * graph.get_num_of_edges() as f64);
+/// ```
+pub fn lfr_benchmark(parameters: &LfrParameters, seed: u64) -> Result<(Graph, Vec<usize>), &'static str> {
+    let n = parameters.num_of_vertices;
+    if n == 0 || parameters.max_degree >= n {
+        return Err("The largest degree has to be lower than the number of vertices.")
+    }
+    if parameters.average_degree < 1.0 || parameters.average_degree > parameters.max_degree as f64 {
+        return Err("The average degree has to be between 1 and the largest degree.")
+    }
+    if !(0.0..=1.0).contains(&parameters.mixing) {
+        return Err("The mixing parameter has to be between 0 and 1.")
+    }
+    if parameters.min_community == 0 || parameters.min_community > parameters.max_community || parameters.max_community > n {
+        return Err("The community sizes have to satisfy 0 < min_community <= max_community <= num_of_vertices.")
+    }
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    // degrees
+    let max_degree = parameters.max_degree as f64;
+    let (mut low, mut high) = (1.0, max_degree);
+    for _ in 0..50 {
+        let mid = (low + high) / 2.0;
+        if power_law_mean(mid, max_degree, parameters.degree_exponent) < parameters.average_degree {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    let degrees = 
+        (0..n)
+        .map(|_| power_law_quantile(low, max_degree, parameters.degree_exponent, rng.gen_range(0.0..1.0)).round() as usize)
+        .collect::<Vec<usize>>();
+
+    // community sizes summing up to n
+    let size_law = PowerLaw::new(parameters.min_community, parameters.max_community, parameters.community_exponent);
+    let mut sizes = vec![];
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        if attempts > 1000 {
+            return Err("Unable to partition the vertices into communities of given sizes.")
+        }
+        sizes.clear();
+        let mut sum = 0;
+        while sum < n {
+            let size = size_law.sample(&mut rng);
+            sizes.push(size);
+            sum += size;
+        }
+        let mut excess = sum - n;
+        for size in sizes.iter_mut().rev() {
+            let cut = excess.min(*size - parameters.min_community);
+            *size -= cut;
+            excess -= cut;
+        }
+        if excess == 0 {
+            break
+        }
+    }
+
+    // assigning vertices to communities, the ones with the most internal edges first
+    let mut internal = degrees.iter().map(|degree| ((1.0 - parameters.mixing) * *degree as f64).round() as usize).collect::<Vec<usize>>();
+    let external = degrees.iter().zip(&internal).map(|(degree, internal)| degree - internal).collect::<Vec<usize>>();
+    let mut order = (0..n).collect::<Vec<usize>>();
+    order.sort_by_key(|v| std::cmp::Reverse(internal[*v]));
+    let mut capacity = sizes.clone();
+    let mut communities = vec![0; n];
+    for v in order {
+        let fitting = (0..sizes.len()).filter(|c| capacity[*c] > 0 && sizes[*c] > internal[v]).collect::<Vec<usize>>();
+        let community = match fitting.choose(&mut rng) {
+            Some(community) => *community,
+            None => {
+                let largest = (0..sizes.len()).filter(|c| capacity[*c] > 0).max_by_key(|c| sizes[*c]).unwrap();
+                internal[v] = sizes[largest] - 1;
+                largest
+            },
+        };
+        capacity[community] -= 1;
+        communities[v] = community;
+    }
+
+    // wiring
+    let mut graph = Graph::from_names((0..n).map(|i| format!("vertex_{}", i)).collect());
+    let mut members = vec![vec![]; sizes.len()];
+    (0..n).for_each(|v| members[communities[v]].push(v));
+    for community in members {
+        let stubs = community.iter().flat_map(|v| std::iter::repeat_n(*v, internal[*v])).collect();
+        pair_stubs(&mut graph, stubs, |_, _| true, &mut rng);
+    }
+    let stubs = (0..n).flat_map(|v| std::iter::repeat_n(v, external[v])).collect();
+    pair_stubs(&mut graph, stubs, |v, u| communities[v] != communities[u], &mut rng);
+
+    Ok((graph, communities))
+}
@@ -0,0 +1,251 @@
+use super::Graph;
+use std::io::BufRead;
+
+/// Encodes the number of vertices in the format shared by graph6 and sparse6.
+fn encode_size(n: usize, bytes: &mut Vec<u8>) {
+    if n < 63 {
+        bytes.push(n as u8 + 63);
+    } else if n < 258048 {
+        bytes.push(126);
+        bytes.extend((0..3).rev().map(|i| ((n >> (6 * i)) & 63) as u8 + 63));
+    } else {
+        bytes.extend([126, 126]);
+        bytes.extend((0..6).rev().map(|i| ((n >> (6 * i)) & 63) as u8 + 63));
+    }
+}
+
+/// Decodes the number of vertices and returns it together with the remaining bytes.
+fn decode_size(bytes: &[u8]) -> Result<(usize, &[u8]), &'static str> {
+    let size_len = match bytes {
+        [126, 126, ..] => 8,
+        [126, ..] => 4,
+        _ => 1,
+    };
+    if bytes.iter().take(size_len).any(|byte| !(63..=126).contains(byte)) {
+        return Err("The graph contains a character outside of the allowed range.")
+    }
+    let fold = |digits: &[u8]| digits.iter().fold(0, |acc, d| (acc << 6) + (*d - 63) as usize);
+    match bytes {
+        [126, 126, rest @ ..] if rest.len() >= 6 => Ok((fold(&rest[..6]), &rest[6..])),
+        [126, rest @ ..] if rest.len() >= 3 => Ok((fold(&rest[..3]), &rest[3..])),
+        [first, rest @ ..] if *first < 126 => Ok(((first - 63) as usize, rest)),
+        _ => Err("The size of the graph is not encoded correctly.")
+    }
+}
+
+/// The largest number of vertices encoded in the 1 or 4 bytes of the short forms of the size.
+const MAX_SHORT_SIZE: usize = 258047;
+
+/// Packs bits into bytes of 6 bits each, padding the last one with given bit.
+fn pack_bits(bits: &[bool], padding: bool, bytes: &mut Vec<u8>) {
+    for chunk in bits.chunks(6) {
+        let byte = (0..6).fold(0, |acc, i| (acc << 1) | *chunk.get(i).unwrap_or(&padding) as u8);
+        bytes.push(byte + 63);
+    }
+}
+
+/// Unpacks bytes of 6 bits each into bits.
+fn unpack_bits(bytes: &[u8]) -> Result<Vec<bool>, &'static str> {
+    if bytes.iter().any(|byte| !(63..=126).contains(byte)) {
+        return Err("The graph contains a character outside of the allowed range.")
+    }
+    Ok(bytes.iter().flat_map(|byte| (0..6).rev().map(move |i| ((byte - 63) >> i) & 1 == 1)).collect())
+}
+
+/// Returns the number of bits used by sparse6 to encode a vertex of a graph with n vertices.
+fn sparse6_width(n: usize) -> usize {
+    let mut k = 1;
+    while (1 << k) < n {
+        k += 1;
+    }
+    k
+}
+
+impl Graph {
+    /// Creates a graph with vertices named "vertex_i".
+    fn with_numbered_vertices(num_of_vertices: usize) -> Graph {
+        Graph::from_names((0..num_of_vertices).map(|i| format!("vertex_{}", i)).collect())
+    }
+
+    /// Encodes the graph in the graph6 format of nauty, vertex names are not preserved.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// assert_eq!("Bw", Graph::complete(3).to_graph6());
+    /// assert_eq!("DhC", Graph::path(5).to_graph6());
+    /// ```
+    pub fn to_graph6(&self) -> String {
+        let n = self.num_of_vertices;
+        let mut bytes = vec![];
+        encode_size(n, &mut bytes);
+        let bits: Vec<bool> = 
+            (1..n)
+            .flat_map(|j| (0..j).map(move |i| (i, j)))
+            .map(|(i, j)| self.neighbours[i].contains(&j))
+            .collect();
+        pack_bits(&bits, false, &mut bytes);
+        String::from_utf8(bytes).unwrap()
+    }
+
+    /// Decodes a graph from the graph6 format of nauty, with an optional ">>graph6<<" header.
+    /// Vertices are named "vertex_i".
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let petersen = Graph::from_graph6("IheA@GUAo").unwrap();
+    /// assert_eq!(10, petersen.get_num_of_vertices());
+    /// assert_eq!(15, petersen.get_num_of_edges());
+    /// assert_eq!(Some(3), petersen.name_to_idx("vertex_3"));
+    /// let k4 = Graph::complete(4);
+    /// assert_eq!(k4.to_graph6(), Graph::from_graph6(&k4.to_graph6()).unwrap().to_graph6());
+    /// assert!(Graph::from_graph6("C").is_err());
+    /// assert!(Graph::from_graph6("~~~~~~~~").is_err());
+    /// ```
+    pub fn from_graph6(line: &str) -> Result<Graph, &'static str> {
+        let line = line.trim_end();
+        let line = line.strip_prefix(">>graph6<<").unwrap_or(line);
+        let (n, rest) = decode_size(line.as_bytes())?;
+        let num_of_pairs = n.checked_mul(n.saturating_sub(1)).map(|doubled| doubled / 2);
+        if num_of_pairs.is_none_or(|num_of_pairs| 6 * rest.len() < num_of_pairs) {
+            return Err("The adjacency matrix is too short.")
+        }
+        let bits = unpack_bits(rest)?;
+
+        let mut graph = Graph::with_numbered_vertices(n);
+        let pairs = (1..n).flat_map(|j| (0..j).map(move |i| (i, j)));
+        for ((i, j), bit) in pairs.zip(bits) {
+            if bit {
+                graph.add_edge_idx(i, j);
+            }
+        }
+        Ok(graph)
+    }
+
+    /// Encodes the graph in the sparse6 format of nauty, vertex names are not preserved.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let c4 = Graph::cycle(4);
+    /// assert_eq!(":Cdv", Graph::path(4).to_sparse6());
+    /// let decoded = Graph::from_sparse6(&c4.to_sparse6()).unwrap();
+    /// assert_eq!(c4.to_graph6(), decoded.to_graph6());
+    /// ```
+    pub fn to_sparse6(&self) -> String {
+        let n = self.num_of_vertices;
+        let k = sparse6_width(n);
+        let mut bytes = vec![b':'];
+        encode_size(n, &mut bytes);
+
+        let mut edges: Vec<(usize, usize)> = 
+            self.vertices()
+            .flat_map(|v| self.neighbours[v].iter().filter(move |u| **u <= v).map(move |u| (v, *u)))
+            .collect();
+        edges.sort_unstable();
+
+        let mut bits = vec![];
+        let encode = |x: usize, bits: &mut Vec<bool>| bits.extend((0..k).rev().map(|i| (x >> i) & 1 == 1));
+        let mut curr = 0;
+        for (v, u) in edges {
+            if v == curr {
+                bits.push(false);
+            } else if v == curr + 1 {
+                curr += 1;
+                bits.push(true);
+            } else {
+                curr = v;
+                bits.push(true);
+                encode(v, &mut bits);
+                bits.push(false);
+            }
+            encode(u, &mut bits);
+        }
+        // padding must not be read as an edge to vertex n - 1
+        let padding = (6 - bits.len() % 6) % 6;
+        if k < 6 && n == (1 << k) && padding >= k && curr < n - 1 {
+            bits.push(false);
+        }
+        pack_bits(&bits, true, &mut bytes);
+        String::from_utf8(bytes).unwrap()
+    }
+
+    /// Decodes a graph from the sparse6 format of nauty, with an optional ">>sparse6<<" header.
+    /// Vertices are named "vertex_i", loops and multiple edges are ignored.
+    /// More than 258047 vertices are accepted only with at least one byte of edges per 64 vertices,
+    /// so that a short line cannot make it allocate a huge graph.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let g = Graph::from_sparse6(":Fa@x^").unwrap();
+    /// assert_eq!(7, g.get_num_of_vertices());
+    /// assert_eq!(4, g.get_num_of_edges());
+    /// assert!(Graph::from_sparse6("Bw").is_err());
+    /// assert!(Graph::from_sparse6(":~~~~~~~~").is_err());
+    /// ```
+    pub fn from_sparse6(line: &str) -> Result<Graph, &'static str> {
+        let line = line.trim_end();
+        let line = line.strip_prefix(">>sparse6<<").unwrap_or(line);
+        let line = line.strip_prefix(':').ok_or("The sparse6 encoding has to start with a colon.")?;
+        let (n, rest) = decode_size(line.as_bytes())?;
+        if n > MAX_SHORT_SIZE && n / 64 > rest.len() {
+            return Err("The number of vertices is too large for the length of the encoding.")
+        }
+        let bits = unpack_bits(rest)?;
+        let k = sparse6_width(n);
+
+        let mut graph = Graph::with_numbered_vertices(n);
+        let read = |from: usize| (from..from + k).fold(0, |acc, i| (acc << 1) | bits[i] as usize);
+        let (mut pos, mut v) = (0, 0);
+        while pos + k < bits.len() {
+            if bits[pos] {
+                v += 1;
+            }
+            let x = read(pos + 1);
+            pos += k + 1;
+            if x >= n || v >= n {
+                break
+            } else if x > v {
+                v = x;
+            } else {
+                graph.add_edge_idx(x, v);
+            }
+        }
+        Ok(graph)
+    }
+
+    /// Decodes a graph from a line in either graph6 or sparse6 format, recognized by the leading colon of the latter.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// assert_eq!(3, Graph::from_nauty("Bw").unwrap().get_num_of_edges());
+    /// assert_eq!(3, Graph::from_nauty(":Cdv").unwrap().get_num_of_edges());
+    /// assert!(Graph::from_nauty("!").is_err());
+    /// assert!(Graph::from_nauty(":!").is_err());
+    /// assert!(Graph::from_nauty("~!AAA").is_err());
+    /// ```
+    pub fn from_nauty(line: &str) -> Result<Graph, &'static str> {
+        if line.starts_with(':') || line.starts_with(">>sparse6<<") {
+            Graph::from_sparse6(line)
+        } else {
+            Graph::from_graph6(line)
+        }
+    }
+
+    /// Lazily decodes graphs from a reader with one graph6 or sparse6 encoding per line, such as the output of geng.
+    /// Empty lines are skipped.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let input = "Bw\n\n:Cdv\nC~\n";
+    /// let edges: Vec<usize> = 
+    ///     Graph::read_nauty(input.as_bytes())
+    ///     .map(|g| g.unwrap().get_num_of_edges())
+    ///     .collect();
+    /// assert_eq!(vec![3, 3, 6], edges);
+    /// ```
+    pub fn read_nauty<R: BufRead>(reader: R) -> impl Iterator<Item = Result<Graph, &'static str>> {
+        reader
+        .lines()
+        .filter(|line| !matches!(line, Ok(line) if line.trim().is_empty()))
+        .map(|line| line.map_err(|_| "Unable to read the line.").and_then(|line| Graph::from_nauty(&line)))
+    }
+}
@@ -0,0 +1,100 @@
+/// The mapping of vertex indices before an operation renumbering or removing vertices to the indices after it,
+/// returned e.g. by remove_vertex_relabeled, so that data indexed by vertices computed earlier (like results of a solver)
+/// can be aligned with the changed graph.
+/// # Examples
+/// ```
+/// use labisu::graphs::Graph;
+/// let mut p4 = Graph::path(4);
+/// let colors = vec!["red", "green", "blue", "red"];
+/// let relabeling = p4.remove_vertex_relabeled("vertex_1").unwrap();
+/// assert_eq!(None, relabeling.new_index(1));
+/// assert_eq!(Some(1), relabeling.new_index(2));
+/// assert_eq!(3, relabeling.old_index(2));
+/// assert_eq!(vec!["red", "blue", "red"], relabeling.apply(&colors));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Relabeling {
+    new_indices: Vec<Option<usize>>, // by old index
+    old_indices: Vec<usize>, // by new index
+}
+
+impl Relabeling {
+    /// Creates the relabeling keeping all num_of_vertices vertices at their indices.
+    pub fn identity(num_of_vertices: usize) -> Relabeling {
+        Relabeling::from_order(num_of_vertices, (0..num_of_vertices).collect())
+    }
+
+    /// Creates the relabeling of a graph with num_of_vertices vertices where the vertex order[i] gets index i
+    /// and the vertices missing in order are removed, e.g. from the permutation returned by reorder_by_degree.
+    /// Panics if an index in order is out of range or repeated.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::{Graph, Relabeling};
+    /// let (reordered, permutation) = Graph::path(3).reorder_by_degree();
+    /// let relabeling = Relabeling::from_order(3, permutation);
+    /// assert_eq!(Some(0), relabeling.new_index(1));
+    /// assert_eq!(Some("vertex_1".to_string()), reordered.idx_to_name(0));
+    /// ```
+    pub fn from_order(num_of_vertices: usize, order: Vec<usize>) -> Relabeling {
+        let mut new_indices = vec![None; num_of_vertices];
+        for (new, old) in order.iter().enumerate() {
+            assert!(new_indices[*old].is_none(), "The index {} is repeated.", old);
+            new_indices[*old] = Some(new);
+        }
+        Relabeling { new_indices, old_indices: order }
+    }
+
+    /// Returns the number of vertices before the operation.
+    pub fn old_len(&self) -> usize {
+        self.new_indices.len()
+    }
+
+    /// Returns the number of vertices after the operation.
+    pub fn new_len(&self) -> usize {
+        self.old_indices.len()
+    }
+
+    /// Returns true if no vertex was removed or moved.
+    pub fn is_identity(&self) -> bool {
+        self.old_len() == self.new_len() && self.old_indices.iter().enumerate().all(|(new, old)| new == *old)
+    }
+
+    /// Returns the new index of the vertex with given old index, or None if it was removed or the index is out of range.
+    pub fn new_index(&self, old: usize) -> Option<usize> {
+        self.new_indices.get(old).copied().flatten()
+    }
+
+    /// Returns the old index of the vertex with given new index. Panics if the index is out of range.
+    pub fn old_index(&self, new: usize) -> usize {
+        self.old_indices[new]
+    }
+
+    /// Returns the old indices of the vertices in the order of their new indices.
+    pub fn old_indices(&self) -> &[usize] {
+        &self.old_indices
+    }
+
+    /// Realigns data indexed by old indices to the new indices, dropping the values of removed vertices.
+    /// Panics if there are fewer values than vertices before the operation.
+    pub fn apply<T: Clone>(&self, data: &[T]) -> Vec<T> {
+        assert!(data.len() >= self.old_len(), "There has to be a value for each vertex.");
+        self.old_indices.iter().map(|old| data[*old].clone()).collect()
+    }
+
+    /// Returns the relabeling of this operation followed by the next one, which is applied to the graph after this one.
+    /// Panics if next does not start with the number of vertices this one ends with.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let mut p4 = Graph::path(4);
+    /// let first = p4.remove_vertex_relabeled("vertex_0").unwrap();
+    /// let second = p4.remove_vertex_relabeled("vertex_2").unwrap();
+    /// let both = first.then(&second);
+    /// assert_eq!(vec![1, 3], both.old_indices());
+    /// assert_eq!(Some(1), both.new_index(3));
+    /// ```
+    pub fn then(&self, next: &Relabeling) -> Relabeling {
+        assert_eq!(self.new_len(), next.old_len(), "The relabelings do not match.");
+        Relabeling::from_order(self.old_len(), next.old_indices.iter().map(|middle| self.old_indices[*middle]).collect())
+    }
+}
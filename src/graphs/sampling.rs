@@ -0,0 +1,75 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::collections::HashSet;
+
+use super::{Graph, GraphOps};
+use crate::combinatorics::WeightedSampler;
+
+/// Samples k vertices independently, with replacement, each one with probability proportional to its degree,
+/// i.e. the endpoints of k uniformly random edges. Isolated vertices are never sampled.
+/// The sample is built with the alias method (see combinatorics::WeightedSampler) in time linear in the number of vertices and k, and depends only on the seed.
+/// If the graph has no edges it returns an Err value.
+/// # Examples
+/// ```
+/// use labisu::graphs::{sample_by_degree, Graph};
+/// // the center of a star has half of the total degree
+/// let mut star = Graph::complete_multipartite(&[1, 10]);
+/// star.add_vertex("isolated");
+/// let sample = sample_by_degree(&star, 10000, 7).unwrap();
+/// assert_eq!(10000, sample.len());
+/// let center = sample.iter().filter(|v| **v == 0).count();
+/// assert!(4500 < center && center < 5500);
+/// assert!(!sample.contains(&11));
+/// assert_eq!(sample, sample_by_degree(&star, 10000, 7).unwrap());
+/// assert!(sample_by_degree(&Graph::from_names(vec!["a".to_string()]), 1, 7).is_err());
+/// ```
+pub fn sample_by_degree<G: GraphOps>(graph: &G, k: usize, seed: u64) -> Result<Vec<usize>, &'static str> {
+    if graph.get_num_of_edges() == 0 {
+        return Err("The graph has no edges.")
+    }
+    let degrees = graph.vertices().map(|v| graph.degree(v) as f64).collect::<Vec<f64>>();
+    let sampler = WeightedSampler::new(&degrees)?;
+    let mut rng = StdRng::seed_from_u64(seed);
+    Ok((0..k).map(|_| sampler.sample(&mut rng)).collect())
+}
+
+impl Graph {
+    /// Returns a small subgraph representing the structure of the graph, e.g. to be drawn with write_to_svg or write_to_dot,
+    /// with at most max_vertices vertices, which keep their names. It is induced by the core, i.e. the max_vertices / 2 (rounded up)
+    /// vertices of the highest degree, and the periphery, i.e. vertices sampled uniformly from the neighbours of the core,
+    /// so that the hubs are shown together with parts of their neighbourhoods. The periphery is smaller if the core has fewer neighbours.
+    /// The vertices of the core come first, and the sample depends only on the graph.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::{Graph, GraphOps};
+    /// let mut g = Graph::random(1000, 0.005);
+    /// g.add_vertex("hub");
+    /// (0..200).for_each(|v| { g.add_edge_idx(1000, v); });
+    /// let preview = g.preview(40);
+    /// assert_eq!(40, preview.get_num_of_vertices());
+    /// assert_eq!(Some(0), preview.name_to_idx("hub"));
+    /// assert!(preview.vertices().skip(20).all(|v| preview.degree(v) > 0));
+    /// assert_eq!(5, Graph::cycle(5).preview(10).get_num_of_vertices());
+    /// ```
+    pub fn preview(&self, max_vertices: usize) -> Graph {
+        let core_size = max_vertices.div_ceil(2).min(self.num_of_vertices);
+        let mut vertices = self.highest_degree_vertices(core_size);
+        let core = vertices.iter().copied().collect::<HashSet<usize>>();
+
+        let mut periphery = 
+            vertices
+            .iter()
+            .flat_map(|v| self.neighbours[*v].iter().copied())
+            .filter(|u| !core.contains(u))
+            .collect::<HashSet<usize>>()
+            .into_iter()
+            .collect::<Vec<usize>>();
+        periphery.sort_unstable();
+        let periphery_size = (max_vertices - core_size).min(periphery.len());
+        let (sampled, _) = periphery.partial_shuffle(&mut StdRng::seed_from_u64(0), periphery_size);
+        vertices.extend_from_slice(sampled);
+
+        self.induced_subgraph(&vertices).unwrap()
+    }
+}
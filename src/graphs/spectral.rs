@@ -0,0 +1,161 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use super::GraphOps;
+
+/// Runs the power iteration of a symmetric matrix with non-negative eigenvalues, given by apply which writes the product with x into y.
+/// The start vector is made orthogonal to the unit vector orthogonal_to, if given, after every step, so that the eigenvalue
+/// of the largest eigenvector orthogonal to it is found. The iteration stops when the Rayleigh quotient changes by less than tolerance.
+/// Returns the eigenvalue together with its unit eigenvector, or an Err value if it did not converge within max_iterations steps.
+pub(crate) fn power_iteration<F>(start: Vec<f64>, apply: F, orthogonal_to: Option<&[f64]>, tolerance: f64, max_iterations: usize) -> Result<(f64, Vec<f64>), &'static str>
+where F: Fn(&[f64], &mut [f64]) {
+    let project = |x: &mut [f64]| {
+        if let Some(u) = orthogonal_to {
+            let dot = x.iter().zip(u).map(|(a, b)| a * b).sum::<f64>();
+            x.iter_mut().zip(u).for_each(|(a, b)| *a -= dot * b);
+        }
+        let norm = x.iter().map(|a| a * a).sum::<f64>().sqrt();
+        if norm > 0.0 {
+            x.iter_mut().for_each(|a| *a /= norm);
+        }
+        norm
+    };
+    let mut x = start;
+    if project(&mut x) == 0.0 {
+        return Err("The start vector is orthogonal to the eigenvectors.")
+    }
+    let mut y = vec![0.0; x.len()];
+    let mut previous = f64::NAN;
+    for _ in 0..max_iterations {
+        apply(&x, &mut y);
+        let rayleigh = x.iter().zip(&y).map(|(a, b)| a * b).sum::<f64>();
+        if project(&mut y) == 0.0 || (rayleigh - previous).abs() < tolerance {
+            return Ok((rayleigh, x))
+        }
+        previous = rayleigh;
+        std::mem::swap(&mut x, &mut y);
+    }
+    Err("The power iteration did not converge.")
+}
+
+/// Returns a random start vector of the power iteration with a fixed seed, so that results are reproducible.
+/// Unlike a vector given by a formula it is not orthogonal to eigenvectors of graphs with symmetries, like the alternating one of a bipartite graph.
+fn random_start(n: usize) -> Vec<f64> {
+    let mut rng = StdRng::seed_from_u64(0);
+    (0..n).map(|_| rng.gen_range(-1.0..1.0)).collect()
+}
+
+/// Writes the product of the adjacency matrix of the graph plus shift times the identity matrix with x into y.
+pub(crate) fn shifted_adjacency<G: GraphOps>(graph: &G, shift: f64, x: &[f64], y: &mut [f64]) {
+    for v in graph.vertices() {
+        let mut sum = shift * x[v];
+        graph.for_each_neighbour(v, &mut |u| sum += x[u]);
+        y[v] = sum;
+    }
+}
+
+/// Returns the spectral radius of the graph, i.e. the largest eigenvalue of its adjacency matrix, with the power iteration.
+/// It is between the average and the largest degree and equals the degree of a regular graph.
+/// The iteration runs on the adjacency matrix plus the identity, so that it converges for bipartite graphs too,
+/// and stops when consecutive estimates differ by less than tolerance.
+/// If the graph has no vertices or the iteration does not converge within max_iterations steps, then it returns an Err value.
+/// # Examples
+/// ```
+/// use labisu::graphs::Graph;
+/// use labisu::graphs::spectral::spectral_radius;
+/// assert!((spectral_radius(&Graph::complete(5), 1e-12, 1000).unwrap() - 4.0).abs() < 1e-6);
+/// assert!((spectral_radius(&Graph::cycle(6), 1e-12, 1000).unwrap() - 2.0).abs() < 1e-6);
+/// // the star K_{1,4} has spectral radius 2
+/// assert!((spectral_radius(&Graph::complete_multipartite(&[1, 4]), 1e-12, 1000).unwrap() - 2.0).abs() < 1e-6);
+/// assert!(spectral_radius(&Graph::empty(), 1e-12, 1000).is_err());
+/// assert!(spectral_radius(&Graph::path(100), 1e-12, 10).is_err());
+/// ```
+pub fn spectral_radius<G: GraphOps>(graph: &G, tolerance: f64, max_iterations: usize) -> Result<f64, &'static str> {
+    let n = graph.get_num_of_vertices();
+    if n == 0 {
+        return Err("The graph has no vertices.")
+    }
+    // a positive vector is not orthogonal to the non-negative eigenvector of the spectral radius
+    let apply = |x: &[f64], y: &mut [f64]| shifted_adjacency(graph, 1.0, x, y);
+    let (value, _) = power_iteration(vec![1.0; n], apply, None, tolerance, max_iterations)?;
+    Ok(value - 1.0)
+}
+
+/// Returns the spectral gap of the graph, i.e. the second smallest eigenvalue of its Laplacian matrix, also called the algebraic connectivity.
+/// It is positive if and only if the graph is connected, and the larger it is, the better the graph expands.
+/// The power iteration runs on 2 Δ I - L, where Δ is the largest degree, orthogonally to the constant vector, the eigenvector of 0,
+/// and stops when consecutive estimates differ by less than tolerance.
+/// If the graph has fewer than two vertices or the iteration does not converge within max_iterations steps, then it returns an Err value.
+/// # Examples
+/// ```
+/// use labisu::graphs::Graph;
+/// use labisu::graphs::spectral::spectral_gap;
+/// assert!((spectral_gap(&Graph::complete(5), 1e-12, 1000).unwrap() - 5.0).abs() < 1e-6);
+/// assert!((spectral_gap(&Graph::cycle(6), 1e-12, 1000).unwrap() - 1.0).abs() < 1e-6);
+/// assert!((spectral_gap(&Graph::petersen(), 1e-12, 1000).unwrap() - 2.0).abs() < 1e-6);
+/// let mut two_edges = Graph::from_names((0..4).map(|i| i.to_string()).collect());
+/// two_edges.add_edge_idx(0, 1);
+/// two_edges.add_edge_idx(2, 3);
+/// assert!(spectral_gap(&two_edges, 1e-12, 1000).unwrap().abs() < 1e-6);
+/// assert!(spectral_gap(&Graph::complete(1), 1e-12, 1000).is_err());
+/// ```
+pub fn spectral_gap<G: GraphOps>(graph: &G, tolerance: f64, max_iterations: usize) -> Result<f64, &'static str> {
+    let n = graph.get_num_of_vertices();
+    if n < 2 {
+        return Err("The graph has fewer than two vertices.")
+    }
+    let shift = 2.0 * graph.vertices().map(|v| graph.degree(v)).max().unwrap_or(0) as f64;
+    let apply = |x: &[f64], y: &mut [f64]| {
+        shifted_adjacency(graph, 0.0, x, y);
+        graph.vertices().for_each(|v| y[v] += (shift - graph.degree(v) as f64) * x[v]);
+    };
+    let constant = vec![1.0 / (n as f64).sqrt(); n];
+    let (value, _) = power_iteration(random_start(n), apply, Some(&constant), tolerance, max_iterations)?;
+    Ok(shift - value)
+}
+
+/// Returns the largest t for which the expander mixing lemma does not exclude a complete bipartite subgraph K_{t,t} of the graph.
+/// The lemma for the normalized adjacency matrix D^(-1/2) A D^(-1/2) states that for disjoint sets of vertices S and T
+/// |e(S, T) - vol(S) vol(T) / vol(V)| <= σ sqrt(vol(S) vol(T)), where vol is the sum of the degrees and σ is the second largest absolute eigenvalue.
+/// The sides of K_{t,t} have t^2 edges between them and volumes at most t Δ, where Δ is the largest degree,
+/// so t <= σ Δ / (1 - Δ^2 / vol(V)). For a d-regular graph on n vertices it is the classic bound t <= λ / (1 - d / n).
+/// The result is at most half the number of vertices, which is also the result when the lemma gives no bound, e.g. for disconnected or bipartite graphs.
+/// σ is computed with the power iteration of the squared matrix, which stops when consecutive estimates differ by less than tolerance.
+/// If the graph has no edges or the iteration does not converge within max_iterations steps, then it returns an Err value.
+/// # Examples
+/// ```
+/// use labisu::graphs::Graph;
+/// use labisu::graphs::spectral::expander_mixing_bound;
+/// // the eigenvalues of the Petersen graph are 3, 1 and -2, so t <= 2 / (1 - 3 / 10)
+/// assert_eq!(Ok(2), expander_mixing_bound(&Graph::petersen(), 1e-12, 10000));
+/// assert_eq!(Ok(5), expander_mixing_bound(&Graph::complete(10), 1e-12, 10000));
+/// assert_eq!(Ok(4), expander_mixing_bound(&Graph::hypercube(3), 1e-12, 10000));
+/// assert!(expander_mixing_bound(&Graph::from_names(vec!["a".to_string()]), 1e-12, 10000).is_err());
+/// ```
+pub fn expander_mixing_bound<G: GraphOps>(graph: &G, tolerance: f64, max_iterations: usize) -> Result<usize, &'static str> {
+    let n = graph.get_num_of_vertices();
+    if graph.get_num_of_edges() == 0 {
+        return Err("The graph has no edges.")
+    }
+    let degrees = graph.vertices().map(|v| graph.degree(v) as f64).collect::<Vec<f64>>();
+    let volume = degrees.iter().sum::<f64>();
+    let max_degree = degrees.iter().copied().fold(0.0, f64::max);
+    let scaling = degrees.iter().map(|d| if *d > 0.0 { 1.0 / d.sqrt() } else { 0.0 }).collect::<Vec<f64>>();
+    let normalized = |x: &[f64], y: &mut [f64]| {
+        let scaled = x.iter().zip(&scaling).map(|(a, s)| a * s).collect::<Vec<f64>>();
+        shifted_adjacency(graph, 0.0, &scaled, y);
+        y.iter_mut().zip(&scaling).for_each(|(a, s)| *a *= s);
+    };
+    let squared = |x: &[f64], y: &mut [f64]| {
+        let mut z = vec![0.0; x.len()];
+        normalized(x, &mut z);
+        normalized(&z, y);
+    };
+    // the eigenvector of the eigenvalue 1 is the vector of square roots of the degrees
+    let top = degrees.iter().map(|d| (d / volume).sqrt()).collect::<Vec<f64>>();
+    let (value, _) = power_iteration(random_start(n), squared, Some(&top), tolerance, max_iterations)?;
+    let sigma = value.max(0.0).sqrt();
+    let denominator = 1.0 - max_degree * max_degree / volume;
+    let bound = if denominator > 0.0 { sigma * max_degree / denominator } else { f64::INFINITY };
+    Ok((bound.floor().min((n / 2) as f64)) as usize)
+}
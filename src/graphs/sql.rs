@@ -0,0 +1,86 @@
+use std::io::{Error, ErrorKind, Result, Write};
+use std::path::Path;
+
+use crate::atomic::{self, AtomicFile};
+use super::csv::csv_field;
+use super::Graph;
+
+/// The statements creating the tables and loading them from the csv files written next to them.
+/// The COPY statements work both in DuckDB and in Postgres (with \copy in psql for files on the client side).
+const SCHEMA: &str = "\
+CREATE TABLE vertices (idx BIGINT PRIMARY KEY, name TEXT NOT NULL, degree BIGINT NOT NULL);
+CREATE TABLE edges (source BIGINT NOT NULL, target BIGINT NOT NULL);
+CREATE TABLE attributes (vertex BIGINT NOT NULL, key TEXT NOT NULL, value TEXT NOT NULL);
+COPY vertices FROM 'vertices.csv' (FORMAT csv, HEADER true);
+COPY edges FROM 'edges.csv' (FORMAT csv, HEADER true);
+COPY attributes FROM 'attributes.csv' (FORMAT csv, HEADER true);
+";
+
+impl Graph {
+    /// Writes the graph to given directory as csv files vertices.csv (idx, name, degree), edges.csv (source, target with source < target)
+    /// and an empty attributes.csv (vertex, key, value), together with schema.sql creating and loading the tables,
+    /// e.g. with `duckdb graph.db < schema.sql` run in the directory. The directory is created if it does not exist.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let k3 = Graph::complete(3);
+    /// k3.write_sql("k3_sql").unwrap();
+    /// let edges = std::fs::read_to_string("k3_sql/edges.csv").unwrap();
+    /// assert_eq!("source,target\n0,1\n0,2\n1,2\n", edges);
+    /// assert!(std::fs::read_to_string("k3_sql/schema.sql").unwrap().starts_with("CREATE TABLE vertices"));
+    /// ```
+    pub fn write_sql(&self, directory: &str) -> Result<()> {
+        self.write_sql_with_attributes(directory, &[])
+    }
+
+    /// Writes the graph like write_sql, with the attributes of vertices in attributes.csv in long format,
+    /// i.e. one row (vertex, key, value) for each vertex and each pair of a key and the values of all vertices.
+    /// Returns an error of kind InvalidInput if the number of values of an attribute differs from the number of vertices.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// let k2 = Graph::complete(2);
+    /// let hosts = vec!["a.com".to_owned(), "b, c".to_owned()];
+    /// k2.write_sql_with_attributes("k2_sql", &[("host", &hosts)]).unwrap();
+    /// let attributes = std::fs::read_to_string("k2_sql/attributes.csv").unwrap();
+    /// assert_eq!("vertex,key,value\n0,host,a.com\n1,host,\"b, c\"\n", attributes);
+    /// assert!(k2.write_sql_with_attributes("k2_sql", &[("host", &hosts[..1])]).is_err());
+    /// ```
+    pub fn write_sql_with_attributes(&self, directory: &str, attributes: &[(&str, &[String])]) -> Result<()> {
+        if attributes.iter().any(|(_, values)| values.len() != self.num_of_vertices) {
+            return Err(Error::new(ErrorKind::InvalidInput, "Every attribute has to have a value for each vertex."))
+        }
+        let directory = Path::new(directory);
+        std::fs::create_dir_all(directory)?;
+
+        let mut vertices = AtomicFile::create(directory.join("vertices.csv"))?;
+        writeln!(vertices, "idx,name,degree")?;
+        for v in self.vertices() {
+            writeln!(vertices, "{},{},{}", v, csv_field(&self.idx_to_name_map[v]), self.neighbours[v].len())?;
+        }
+        vertices.commit()?;
+
+        let mut edges = AtomicFile::create(directory.join("edges.csv"))?;
+        writeln!(edges, "source,target")?;
+        for from in self.vertices() {
+            let mut neighbours = self.neighbours[from].iter().filter(|to| from < **to).collect::<Vec<&usize>>();
+            neighbours.sort_unstable();
+            for to in neighbours {
+                writeln!(edges, "{},{}", from, to)?;
+            }
+        }
+        edges.commit()?;
+
+        let mut attribute_file = AtomicFile::create(directory.join("attributes.csv"))?;
+        writeln!(attribute_file, "vertex,key,value")?;
+        for (key, values) in attributes {
+            let key = csv_field(key);
+            for (v, value) in values.iter().enumerate() {
+                writeln!(attribute_file, "{},{},{}", v, key, csv_field(value))?;
+            }
+        }
+        attribute_file.commit()?;
+
+        atomic::write(directory.join("schema.sql"), SCHEMA)
+    }
+}
@@ -0,0 +1,274 @@
+use std::io::{self, Write};
+
+use crate::atomic::AtomicFile;
+use super::GraphOps;
+
+/// Returns the degree assortativity of a graph, i.e. the Pearson correlation coefficient of the degrees at both ends of the edges.
+/// It is positive if high degree vertices tend to be adjacent to high degree vertices.
+/// Returns None if the graph has no edges or all edges join vertices of the same degree, e.g. in a regular graph.
+/// # Examples
+/// ```
+/// use labisu::graphs::Graph;
+/// use labisu::graphs::stats::assortativity;
+/// let mut star = Graph::from_names((0..5).map(|i| format!("v_{}", i)).collect());
+/// (1..5).for_each(|i| { star.add_edge_idx(0, i); });
+/// assert!((assortativity(&star).unwrap() + 1.0).abs() < 1e-9);
+/// assert_eq!(None, assortativity(&Graph::cycle(5)));
+/// ```
+pub fn assortativity<G: GraphOps>(graph: &G) -> Option<f64> {
+    let m = graph.get_num_of_edges();
+    if m == 0 {
+        return None
+    }
+    let (mut product, mut sum, mut square_sum) = (0.0, 0.0, 0.0);
+    for v in graph.vertices() {
+        let j = graph.degree(v) as f64;
+        graph.for_each_neighbour(v, &mut |u| {
+            if v < u {
+                let k = graph.degree(u) as f64;
+                product += j * k;
+                sum += (j + k) / 2.0;
+                square_sum += (j * j + k * k) / 2.0;
+            }
+        });
+    }
+    let m = m as f64;
+    let mean = sum / m;
+    let variance = square_sum / m - mean * mean;
+    if variance.abs() < 1e-12 {
+        return None
+    }
+    Some((product / m - mean * mean) / variance)
+}
+
+/// Returns the rich-club coefficients of a graph, where the value at index k is the density of the subgraph 
+/// induced by the vertices of degree greater than k, i.e. 2 E_k / (N_k (N_k - 1)).
+/// The coefficients are given for all k for which there are at least two such vertices.
+/// # Examples
+/// ```
+/// use labisu::graphs::Graph;
+/// use labisu::graphs::stats::rich_club_coefficients;
+/// let mut g = Graph::complete(4);
+/// g.add_vertex("leaf");
+/// g.add_edge("vertex_0", "leaf");
+/// let coefficients = rich_club_coefficients(&g);
+/// assert_eq!(3, coefficients.len());
+/// assert!((coefficients[0] - 0.7).abs() < 1e-9);
+/// assert!((coefficients[2] - 1.0).abs() < 1e-9);
+/// ```
+pub fn rich_club_coefficients<G: GraphOps>(graph: &G) -> Vec<f64> {
+    let degrees = graph.vertices().map(|v| graph.degree(v)).collect::<Vec<usize>>();
+    let max_degree = degrees.iter().copied().max().unwrap_or(0);
+
+    // vertices_with[d] and edges_with[d] count vertices of degree d and edges whose lower end degree is d
+    let mut vertices_with = vec![0; max_degree + 1];
+    let mut edges_with = vec![0; max_degree + 1];
+    for v in graph.vertices() {
+        vertices_with[degrees[v]] += 1;
+        graph.for_each_neighbour(v, &mut |u| {
+            if v < u {
+                edges_with[degrees[v].min(degrees[u])] += 1;
+            }
+        });
+    }
+
+    let mut coefficients = vec![];
+    let (mut num_of_vertices, mut num_of_edges) = (graph.get_num_of_vertices(), graph.get_num_of_edges());
+    for k in 0..=max_degree {
+        num_of_vertices -= vertices_with[k];
+        num_of_edges -= edges_with[k];
+        if num_of_vertices < 2 {
+            break
+        }
+        coefficients.push(2.0 * num_of_edges as f64 / (num_of_vertices * (num_of_vertices - 1)) as f64);
+    }
+    coefficients
+}
+
+/// Returns the rich-club coefficient of a graph for degree k, i.e. the density of the subgraph induced by the vertices of degree greater than k.
+/// Returns None if there are fewer than two such vertices.
+/// # Examples
+/// ```
+/// use labisu::graphs::Graph;
+/// use labisu::graphs::stats::rich_club_coefficient;
+/// let petersen = Graph::petersen();
+/// assert_eq!(Some(1.0 / 3.0), rich_club_coefficient(&petersen, 2));
+/// assert_eq!(None, rich_club_coefficient(&petersen, 3));
+/// ```
+pub fn rich_club_coefficient<G: GraphOps>(graph: &G, k: usize) -> Option<f64> {
+    rich_club_coefficients(graph).get(k).copied()
+}
+
+/// A measure of the similarity of the neighbourhoods of two vertices.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Similarity {
+    /// The number of common neighbours divided by the size of the union of the neighbourhoods.
+    Jaccard,
+    /// The sum of 1 / ln(degree) over the common neighbours, so that common neighbours of low degree count more.
+    AdamicAdar,
+}
+
+impl Similarity {
+    /// Returns the similarity of the neighbourhoods of two vertices.
+    /// Panics if one of the indices does not exist in the graph.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// use labisu::graphs::stats::Similarity;
+    /// let c4 = Graph::cycle(4);
+    /// assert_eq!(1.0, Similarity::Jaccard.score(&c4, 0, 2));
+    /// assert_eq!(0.0, Similarity::Jaccard.score(&c4, 0, 1));
+    /// assert!((Similarity::AdamicAdar.score(&c4, 0, 2) - 2.0 / 2f64.ln()).abs() < 1e-9);
+    /// ```
+    pub fn score<G: GraphOps>(&self, graph: &G, u: usize, v: usize) -> f64 {
+        let u_neighbours = graph.neighbours_idx(u).unwrap();
+        let v_neighbours = graph.neighbours_idx(v).unwrap();
+        match self {
+            Similarity::Jaccard => {
+                let union = u_neighbours.union(&v_neighbours).count();
+                match union {
+                    0 => 0.0,
+                    union => u_neighbours.intersection(&v_neighbours).count() as f64 / union as f64,
+                }
+            },
+            Similarity::AdamicAdar => 
+                u_neighbours
+                .intersection(&v_neighbours)
+                .map(|w| graph.degree(*w))
+                .filter(|degree| *degree > 1)
+                .map(|degree| 1.0 / (degree as f64).ln())
+                .sum(),
+        }
+    }
+}
+
+/// Scores the candidate pairs of vertices by the similarity of their neighbourhoods and returns the k best ones 
+/// with their scores, in non-increasing order of scores (ties broken by the order of candidates).
+/// Pairs with high overlap of neighbourhoods are natural seeds of complete bipartite subgraphs.
+/// Panics if one of the indices does not exist in the graph.
+/// # Examples
+/// ```
+/// use labisu::graphs::Graph;
+/// use labisu::graphs::stats::{top_similar_pairs, Similarity};
+/// let k33 = Graph::complete_multipartite(&[3, 3]);
+/// let candidates = [(0, 1), (0, 3), (3, 4)];
+/// let top = top_similar_pairs(&k33, &candidates, Similarity::Jaccard, 2);
+/// assert_eq!(vec![(0, 1, 1.0), (3, 4, 1.0)], top);
+/// ```
+pub fn top_similar_pairs<G: GraphOps>(graph: &G, candidates: &[(usize, usize)], similarity: Similarity, k: usize) -> Vec<(usize, usize, f64)> {
+    let mut scored = 
+        candidates
+        .iter()
+        .map(|(u, v)| (*u, *v, similarity.score(graph, *u, *v)))
+        .collect::<Vec<(usize, usize, f64)>>();
+    scored.sort_by(|a, b| b.2.total_cmp(&a.2));
+    scored.truncate(k);
+    scored
+}
+
+/// A histogram of the degrees of the vertices of a graph, with bins of equal width starting at degree 0.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DegreeHistogram {
+    /// The number of consecutive degrees counted in one bin.
+    pub bin_width: usize,
+    /// The numbers of vertices in the bins, the bin at index i counts the degrees from i * bin_width to (i + 1) * bin_width - 1.
+    pub counts: Vec<usize>,
+}
+
+impl DegreeHistogram {
+    /// Returns the smallest and the largest degree counted in the bin at given index.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// use labisu::graphs::stats::degree_histogram;
+    /// let histogram = degree_histogram(&Graph::complete(10), 3).unwrap();
+    /// assert_eq!((8, 11), histogram.bin_range(2));
+    /// ```
+    pub fn bin_range(&self, idx: usize) -> (usize, usize) {
+        (idx * self.bin_width, (idx + 1) * self.bin_width - 1)
+    }
+
+    /// Writes the histogram to a csv file with given filename, with the columns min_degree, max_degree and count.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// use labisu::graphs::stats::degree_histogram;
+    /// let histogram = degree_histogram(&Graph::cycle(5), 2).unwrap();
+    /// histogram.write_to_csv("histogram.csv").unwrap();
+    /// let contents = std::fs::read_to_string("histogram.csv").unwrap();
+    /// assert_eq!("min_degree,max_degree,count\n0,1,0\n2,3,5\n", contents);
+    /// ```
+    pub fn write_to_csv(&self, filename: &str) -> io::Result<()> {
+        let mut writer = AtomicFile::create(filename)?;
+        writeln!(writer, "min_degree,max_degree,count")?;
+        for (idx, count) in self.counts.iter().enumerate() {
+            let (min, max) = self.bin_range(idx);
+            writeln!(writer, "{},{},{}", min, max, count)?;
+        }
+        writer.commit()
+    }
+
+    /// Plots the histogram to a png file with given filename and title, using gnuplot, which has to be installed.
+    /// Returns an Err value if gnuplot cannot be run or fails.
+    /// # Examples
+    /// ```no_run
+    /// use labisu::graphs::Graph;
+    /// use labisu::graphs::stats::degree_histogram;
+    /// let histogram = degree_histogram(&Graph::random(1000, 0.01), 20).unwrap();
+    /// histogram.plot_png("degrees.png", "Degree distribution of G(1000, 0.01)").unwrap();
+    /// ```
+    #[cfg(feature = "plots")]
+    pub fn plot_png(&self, filename: &str, title: &str) -> io::Result<()> {
+        let mut script = String::new();
+        script.push_str("$degrees << EOD\n");
+        for (idx, count) in self.counts.iter().enumerate() {
+            let (min, max) = self.bin_range(idx);
+            script.push_str(&format!("{} {}\n", (min + max) as f64 / 2.0, count));
+        }
+        script.push_str("EOD\n");
+        script.push_str("set terminal pngcairo size 800,600\n");
+        script.push_str(&format!("set output '{}'\n", filename.replace('\'', "''")));
+        script.push_str(&format!("set title '{}'\n", title.replace('\'', "''")));
+        script.push_str("set xlabel 'degree'\nset ylabel 'number of vertices'\n");
+        script.push_str(&format!("set boxwidth {}\nset style fill solid\n", self.bin_width));
+        script.push_str("plot $degrees using 1:2 with boxes notitle\n");
+
+        let mut gnuplot = 
+            std::process::Command::new("gnuplot")
+            .stdin(std::process::Stdio::piped())
+            .spawn()?;
+        gnuplot.stdin.take().expect("stdin of gnuplot is piped").write_all(script.as_bytes())?;
+        let status = gnuplot.wait()?;
+        if !status.success() {
+            return Err(io::Error::other(format!("gnuplot failed with {}", status)))
+        }
+        Ok(())
+    }
+}
+
+/// Returns the histogram of the degrees of a graph with given number of bins of equal width, covering the degrees from 0 to the maximum degree.
+/// The width is the smallest one for which the bins cover the maximum degree, so trailing bins may be empty.
+/// Returns an Err value if the number of bins is 0.
+/// # Examples
+/// ```
+/// use labisu::graphs::Graph;
+/// use labisu::graphs::stats::degree_histogram;
+/// let mut star = Graph::from_names((0..6).map(|i| format!("v_{}", i)).collect());
+/// (1..6).for_each(|i| { star.add_edge_idx(0, i); });
+/// let histogram = degree_histogram(&star, 3).unwrap();
+/// assert_eq!(2, histogram.bin_width);
+/// assert_eq!(vec![5, 0, 1], histogram.counts);
+/// assert!(degree_histogram(&star, 0).is_err());
+/// ```
+pub fn degree_histogram<G: GraphOps>(graph: &G, bins: usize) -> Result<DegreeHistogram, &'static str> {
+    if bins == 0 {
+        return Err("the number of bins must be positive")
+    }
+    let max_degree = graph.vertices().map(|v| graph.degree(v)).max().unwrap_or(0);
+    let bin_width = (max_degree + 1).div_ceil(bins);
+    let mut counts = vec![0; bins];
+    for v in graph.vertices() {
+        counts[graph.degree(v) / bin_width] += 1;
+    }
+    Ok(DegreeHistogram { bin_width, counts })
+}
@@ -0,0 +1,78 @@
+/// The k vertices of the highest degrees of a growing graph, maintained under increments of degrees.
+/// Vertices are kept sorted by non-increasing degree, so the current hubs are available without sorting,
+/// and an increment costs O(1) unless the vertex overtakes others in the top list.
+/// # Examples
+/// ```
+/// use labisu::graphs::TopDegrees;
+/// let mut top = TopDegrees::new(2);
+/// top.increment(0);
+/// top.increment(1);
+/// top.increment(1);
+/// top.increment(2);
+/// top.increment(2);
+/// top.increment(2);
+/// assert_eq!(&[2, 1], top.top());
+/// assert_eq!(1, top.degree(0));
+/// ```
+#[derive(Clone, Debug)]
+pub struct TopDegrees {
+    k: usize,
+    degrees: Vec<usize>,
+    top: Vec<usize>,
+    position: Vec<Option<usize>>, // position of the vertex in top
+}
+
+impl TopDegrees {
+    /// Creates an empty structure maintaining k vertices of the highest degrees.
+    pub fn new(k: usize) -> TopDegrees {
+        TopDegrees { k, degrees: vec![], top: Vec::with_capacity(k), position: vec![] }
+    }
+
+    /// Returns up to k vertices of the highest degrees, sorted by non-increasing degree.
+    /// Vertices of degree 0 are never included.
+    pub fn top(&self) -> &[usize] {
+        &self.top
+    }
+
+    /// Returns the degree of the vertex seen so far.
+    pub fn degree(&self, idx: usize) -> usize {
+        self.degrees.get(idx).copied().unwrap_or(0)
+    }
+
+    /// Increases the degree of the vertex by one.
+    pub fn increment(&mut self, idx: usize) {
+        if idx >= self.degrees.len() {
+            self.degrees.resize(idx + 1, 0);
+            self.position.resize(idx + 1, None);
+        }
+        self.degrees[idx] += 1;
+
+        let pos = match self.position[idx] {
+            Some(pos) => pos,
+            None if self.top.len() < self.k => {
+                self.top.push(idx);
+                self.top.len() - 1
+            },
+            None => match self.top.last() {
+                Some(last) if self.degrees[*last] < self.degrees[idx] => {
+                    self.position[*last] = None;
+                    self.top[self.k - 1] = idx;
+                    self.k - 1
+                },
+                _ => return,
+            },
+        };
+        self.position[idx] = Some(pos);
+        self.bubble_up(pos);
+    }
+
+    /// Moves the vertex at given position towards the front until the order is restored.
+    fn bubble_up(&mut self, mut pos: usize) {
+        while pos > 0 && self.degrees[self.top[pos - 1]] < self.degrees[self.top[pos]] {
+            self.top.swap(pos - 1, pos);
+            self.position[self.top[pos]] = Some(pos);
+            self.position[self.top[pos - 1]] = Some(pos - 1);
+            pos -= 1;
+        }
+    }
+}
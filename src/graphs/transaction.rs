@@ -0,0 +1,115 @@
+use std::collections::{HashMap, HashSet};
+
+use super::{EdgeError, Graph};
+
+/// A batch of insertions into a graph, validated against the graph and the earlier insertions of the batch
+/// as they are made, and applied to the graph only when the whole batch succeeds (see Graph::apply).
+pub struct Transaction<'a> {
+    graph: &'a Graph,
+    names: Vec<String>,
+    name_to_idx_map: HashMap<String, usize>,
+    edges: HashSet<(usize, usize)>, // the smaller index first
+}
+
+impl<'a> Transaction<'a> {
+    /// Creates an empty transaction on the graph.
+    fn new(graph: &'a Graph) -> Transaction<'a> {
+        Transaction { graph, names: vec![], name_to_idx_map: HashMap::new(), edges: HashSet::new() }
+    }
+
+    /// Returns the number of vertices of the graph after the transaction.
+    pub fn get_num_of_vertices(&self) -> usize {
+        self.graph.get_num_of_vertices() + self.names.len()
+    }
+
+    /// Returns the number of edges of the graph after the transaction.
+    pub fn get_num_of_edges(&self) -> usize {
+        self.graph.get_num_of_edges() + self.edges.len()
+    }
+
+    /// Returns the index the vertex with given name has or will have after the transaction, if it exists.
+    pub fn name_to_idx(&self, name: &str) -> Option<usize> {
+        self.graph.name_to_idx(name).or_else(|| self.name_to_idx_map.get(name).copied())
+    }
+
+    /// Adds a new vertex with given name, unless the name already exists, and returns its index.
+    pub fn add_vertex(&mut self, name: &str) -> usize {
+        if let Some(idx) = self.name_to_idx(name) {
+            return idx
+        }
+        let idx = self.get_num_of_vertices();
+        self.names.push(name.to_owned());
+        self.name_to_idx_map.insert(name.to_owned(), idx);
+        idx
+    }
+
+    /// Adds an edge between two vertices based on their indices, which may be indices of vertices added by the transaction.
+    /// Returns an Err value with the reason if the edge cannot be added.
+    pub fn add_edge_idx(&mut self, from: usize, to: usize) -> Result<(), EdgeError> {
+        if let Some(idx) = [from, to].into_iter().find(|idx| *idx >= self.get_num_of_vertices()) {
+            return Err(EdgeError::IndexOutOfRange(idx))
+        }
+        if from == to {
+            return Err(EdgeError::SelfLoop)
+        }
+        let edge = (from.min(to), from.max(to));
+        if self.graph.contains_edge_idx(from, to) || !self.edges.insert(edge) {
+            return Err(EdgeError::AlreadyExists)
+        }
+        Ok(())
+    }
+
+    /// Adds an edge between two vertices based on their names, which may be names of vertices added by the transaction.
+    /// Returns an Err value with the reason if the edge cannot be added.
+    pub fn add_edge(&mut self, from: &str, to: &str) -> Result<(), EdgeError> {
+        let from = self.name_to_idx(from).ok_or_else(|| EdgeError::UnknownVertex(from.to_owned()))?;
+        let to = self.name_to_idx(to).ok_or_else(|| EdgeError::UnknownVertex(to.to_owned()))?;
+        self.add_edge_idx(from, to)
+    }
+}
+
+impl Graph {
+    /// Runs the closure on a transaction on the graph and applies all insertions made in it if the closure returns an Ok value.
+    /// If the closure returns an Err value or panics, the graph is left unchanged, so a batch of insertions failing in the middle
+    /// never leaves the graph, including its numbers of vertices and edges, partially updated.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::{EdgeError, Graph};
+    /// let mut g = Graph::path(3);
+    /// let result = g.apply(|tx| {
+    ///     tx.add_vertex("hub");
+    ///     tx.add_edge("hub", "vertex_0")?;
+    ///     tx.add_edge("hub", "vertex_1")?;
+    ///     tx.add_edge("vertex_0", "vertex_1") // already in the graph
+    /// });
+    /// assert_eq!(Err(EdgeError::AlreadyExists), result);
+    /// assert_eq!(3, g.get_num_of_vertices());
+    /// assert_eq!(2, g.get_num_of_edges());
+    ///
+    /// let added = g.apply(|tx| {
+    ///     let hub = tx.add_vertex("hub");
+    ///     for v in 0..3 {
+    ///         tx.add_edge_idx(hub, v)?;
+    ///     }
+    ///     Ok::<usize, EdgeError>(tx.get_num_of_edges())
+    /// });
+    /// assert_eq!(Ok(5), added);
+    /// assert_eq!(5, g.get_num_of_edges());
+    /// assert!(g.contains_edge("hub", "vertex_2"));
+    /// ```
+    pub fn apply<T, E, F>(&mut self, f: F) -> Result<T, E>
+    where
+        F: FnOnce(&mut Transaction) -> Result<T, E>,
+    {
+        let mut transaction = Transaction::new(self);
+        let result = f(&mut transaction)?;
+        let Transaction { names, edges, .. } = transaction;
+        for name in &names {
+            self.add_vertex(name);
+        }
+        for (from, to) in edges {
+            self.add_edge_idx(from, to);
+        }
+        Ok(result)
+    }
+}
@@ -3,24 +3,134 @@
 //! `labisu` is a library that implements an algorithm for finding large bipartite subgraphs in a graph.
 //! It also contains a web crawler that can be used to crawl over the Internet.
 //! A structure is provided to represent a graph with undirected edges. 
+//! 
+//! All items are imported from the crate `labisu` by the paths of their modules, e.g. `labisu::bipartite::find_bipartite`,
+//! and the most common ones at once with `use labisu::prelude::*;`.
+//! 
+//! The graphs and the algorithms are behind the feature `graph`, and need no heavy dependencies. The rest is behind features, all enabled by default:
+//! `crawl` (the scraper, the crawler and the datasets, with the HTTP stack), `viz` (drawing graphs in DOT and SVG)
+//! and `formats` (graph6, sparse6, csv and SQL). To use only the algorithms, depend on the crate with `default-features = false`
+//! and `features = ["graph"]`. Without any features only the module `combinatorics` is compiled, and it needs no standard library, only `alloc`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
 /// # combinatorics
 /// 
 /// Module implementing various combinatorial iterators and functions.
 pub mod combinatorics;
+/// # atomic
+/// 
+/// Module writing files through temporary files which atomically replace the targets, so that interrupted writes never corrupt them.
+#[cfg(feature = "std")]
+pub mod atomic;
 /// # graphs
 /// 
 /// Module used to represent a graph with undirected edges.
+#[cfg(feature = "graph")]
 pub mod graphs;
 /// # scraper
 /// 
 /// Module used to scrape a website for links to other pages.
+#[cfg(feature = "crawl")]
 pub mod scraper;
+/// # filter
+/// 
+/// Module used to compose the policies deciding which links are followed by the scraper and the crawler.
+#[cfg(feature = "crawl")]
+pub mod filter;
+/// # normalize
+/// 
+/// Module used to compose the policies turning the links found by the scraper into the names of vertices.
+#[cfg(feature = "crawl")]
+pub mod normalize;
 /// # crawler
 /// 
 /// Module used to crawl over a net of websites.
+#[cfg(feature = "crawl")]
 pub mod crawler;
 /// # bipartite
 /// 
 /// Module implementing algorithms finding large bipartite subgraphs.
-pub mod bipartite;
\ No newline at end of file
+#[cfg(feature = "graph")]
+pub mod bipartite;
+/// # evolving
+/// 
+/// Module used to represent a graph whose vertices and edges are stamped with their insertion times.
+#[cfg(feature = "graph")]
+pub mod evolving;
+/// # traversal
+/// 
+/// Module implementing graph traversals, sequential and (with the parallel feature) parallel ones.
+#[cfg(feature = "graph")]
+pub mod traversal;
+/// # flow
+/// 
+/// Module computing maximum flows and minimum cuts in networks with integer capacities.
+#[cfg(feature = "graph")]
+pub mod flow;
+/// # extremal
+/// 
+/// Module constructing extremal graphs, which contain no large complete bipartite subgraphs.
+#[cfg(feature = "graph")]
+pub mod extremal;
+/// # url_graph
+/// 
+/// Module used to represent a graph of web pages, whose vertex names are valid urls.
+#[cfg(feature = "crawl")]
+pub mod url_graph;
+/// # warc
+/// 
+/// Module used to archive fetched pages in the WARC format.
+#[cfg(feature = "crawl")]
+pub mod warc;
+/// # dns
+/// 
+/// Module implementing a cache of host name resolutions shared by the scrapers.
+#[cfg(feature = "crawl")]
+pub mod dns;
+/// # robots
+/// 
+/// Module used to obey the rules of robots.txt of the crawled hosts.
+#[cfg(feature = "crawl")]
+pub mod robots;
+/// # pipeline
+/// 
+/// Module used to clean up a crawled graph with a sequence of post-processing steps.
+#[cfg(feature = "crawl")]
+pub mod pipeline;
+/// # algorithms
+/// 
+/// Module used to register algorithms by name and run them on graphs given as trait objects.
+#[cfg(feature = "graph")]
+pub mod algorithms;
+/// # experiments
+/// 
+/// Module running the experiments measuring how the algorithms behave on random graphs.
+#[cfg(feature = "graph")]
+pub mod experiments;
+/// # benchmark
+/// 
+/// Module packaging anonymized instances extracted from crawls into benchmark bundles that can be published and read back.
+#[cfg(feature = "graph")]
+pub mod benchmark;
+/// # prelude
+/// 
+/// Module re-exporting the most commonly used types, traits and functions, to be imported at once with `use labisu::prelude::*;`.
+/// ```
+/// use labisu::prelude::*;
+/// let k300 = Graph::complete(300);
+/// let (left, right) = find_bipartite(&k300, 10, 3);
+/// assert!(is_complete_bipartite(&k300, &left, &right));
+/// assert_eq!(299, k300.degree(0));
+/// #[cfg(feature = "crawl")]
+/// assert!(Contains::new("pwr").accepts("https://pwr.edu.pl/"));
+/// ```
+#[cfg(feature = "graph")]
+pub mod prelude;
+/// # datasets
+/// 
+/// Module used to load well-known public graphs, downloading and caching them on first use.
+#[cfg(feature = "crawl")]
+pub mod datasets;
\ No newline at end of file
@@ -0,0 +1,223 @@
+use std::process::ExitCode;
+
+use labisu::benchmark::BenchmarkBundle;
+#[cfg(feature = "crawl")]
+use labisu::crawler::{Crawler, Frontier, Politeness};
+use labisu::graphs::{diff, Graph};
+
+const USAGE: &str = "usage:
+    labisu diff <old.json> <new.json> [--report <diff.json>]
+        compares two graphs written with Graph::write_to_json, e.g. two crawls of the same website,
+        prints the added (+) and removed (-) pages and links with their counts
+        and optionally writes them to a json report
+    labisu benchmark <graph.json> <bundle_dir> [--name <name>] [--source <source>] [--date <date>]
+                     [--param <key>=<value>]... [--biclique <highest_degree_size> <max_bipartite_size>]
+        extracts the anonymized densest component of a graph, e.g. a crawl,
+        and writes it with its metadata to a benchmark bundle directory
+    labisu crawl <root> [--depth <max_depth>] [--contain <word>]... [--stop <word>]... [--robots] [--urls <urls.txt>]
+                 [--politeness <aggressive|default|respectful>] [--output <graph.json>] [--dry-run [--cached <graph.json>] [--head] [--report <estimate.json>]]
+        crawls a website from the root, or from the urls listed one per line, and writes the graph to a json file,
+        hitting the hosts as hard as the politeness preset allows (aggressive without --politeness);
+        with --dry-run only prints how many pages would be fetched per depth and per host, following the links
+        of a cached graph of an earlier crawl and sending HEAD requests with --head, without downloading any pages
+        (available with the crawl feature)";
+
+/// Reads a graph from a json file, or returns a message describing why it cannot be read.
+fn read_graph(filename: &str) -> Result<Graph, String> {
+    if !std::path::Path::new(filename).is_file() {
+        return Err(format!("{} is not a file", filename))
+    }
+    Graph::try_read_from_json(filename).map_err(|e| format!("cannot read {}: {}", filename, e))
+}
+
+/// Runs the diff command with given arguments following the name of the command.
+fn run_diff(args: &[String]) -> Result<(), String> {
+    let mut files = vec![];
+    let mut report = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--report" => report = Some(args.next().ok_or("--report needs a filename")?),
+            _ => files.push(arg),
+        }
+    }
+    let [old, new] = files[..] else {
+        return Err("diff needs exactly two graph files".to_owned())
+    };
+
+    let changes = diff(&read_graph(old)?, &read_graph(new)?);
+    for page in &changes.added_vertices {
+        println!("+ {}", page);
+    }
+    for page in &changes.removed_vertices {
+        println!("- {}", page);
+    }
+    for (from, to) in &changes.added_edges {
+        println!("+ {} -- {}", from, to);
+    }
+    for (from, to) in &changes.removed_edges {
+        println!("- {} -- {}", from, to);
+    }
+    println!(
+        "pages: {} added, {} removed; links: {} added, {} removed",
+        changes.added_vertices.len(),
+        changes.removed_vertices.len(),
+        changes.added_edges.len(),
+        changes.removed_edges.len()
+    );
+
+    if let Some(filename) = report {
+        changes.write_to_json(filename).map_err(|e| format!("cannot write {}: {}", filename, e))?;
+    }
+    Ok(())
+}
+
+/// Parses a number given as the value of an option.
+fn parse_number(value: Option<&String>, option: &str) -> Result<usize, String> {
+    value
+    .and_then(|value| value.parse().ok())
+    .ok_or(format!("{} needs a non-negative integer", option))
+}
+
+/// Runs the benchmark command with given arguments following the name of the command.
+fn run_benchmark(args: &[String]) -> Result<(), String> {
+    let mut files = vec![];
+    let (mut name, mut source, mut date, mut biclique) = (None, None, None, None);
+    let mut parameters = vec![];
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--name" => name = Some(args.next().ok_or("--name needs a value")?),
+            "--source" => source = Some(args.next().ok_or("--source needs a value")?),
+            "--date" => date = Some(args.next().ok_or("--date needs a value")?),
+            "--param" => {
+                let parameter = args.next().ok_or("--param needs a value")?;
+                parameters.push(parameter.split_once('=').ok_or("--param needs a value of the form key=value")?);
+            },
+            "--biclique" => {
+                let highest_degree_size = parse_number(args.next(), "--biclique")?;
+                biclique = Some((highest_degree_size, parse_number(args.next(), "--biclique")?));
+            },
+            _ => files.push(arg),
+        }
+    }
+    let [graph, dir] = files[..] else {
+        return Err("benchmark needs a graph file and a bundle directory".to_owned())
+    };
+
+    let mut bundle = BenchmarkBundle::from_graph(&read_graph(graph)?, name.unwrap_or(dir));
+    if let Some(source) = source {
+        bundle = bundle.with_source(source);
+    }
+    if let Some(date) = date {
+        bundle = bundle.with_source_date(date);
+    }
+    for (key, value) in parameters {
+        bundle = bundle.with_parameter(key, value);
+    }
+    if let Some((highest_degree_size, max_bipartite_size)) = biclique {
+        bundle = bundle.with_known_biclique(highest_degree_size, max_bipartite_size);
+    }
+    bundle.write_to_dir(dir).map_err(|e| format!("cannot write {}: {}", dir, e))?;
+
+    let metadata = &bundle.metadata;
+    println!(
+        "{}: {} vertices and {} edges extracted from {} vertices and {} edges",
+        metadata.name, metadata.num_of_vertices, metadata.num_of_edges, metadata.source_num_of_vertices, metadata.source_num_of_edges
+    );
+    if let Some(known) = &metadata.known_biclique {
+        println!("known biclique: K_{{{},{}}}", known.left.len(), known.right.len());
+    }
+    Ok(())
+}
+
+/// Runs the crawl command with given arguments following the name of the command.
+#[cfg(feature = "crawl")]
+fn run_crawl(args: &[String]) -> Result<(), String> {
+    let mut roots = vec![];
+    let (mut max_depth, mut must_contain, mut stop_words) = (1, vec![], vec![]);
+    let (mut robots, mut dry_run, mut head) = (false, false, false);
+    let (mut urls, mut output, mut cached, mut report) = (None, None, None, None);
+    let mut politeness = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--depth" => max_depth = parse_number(args.next(), "--depth")?,
+            "--contain" => must_contain.push(args.next().ok_or("--contain needs a word")?.clone()),
+            "--stop" => stop_words.push(args.next().ok_or("--stop needs a word")?.clone()),
+            "--robots" => robots = true,
+            "--urls" => urls = Some(args.next().ok_or("--urls needs a filename")?),
+            "--politeness" => politeness = Some(args.next().ok_or("--politeness needs a preset")?.parse::<Politeness>()?),
+            "--output" => output = Some(args.next().ok_or("--output needs a filename")?),
+            "--dry-run" => dry_run = true,
+            "--cached" => cached = Some(args.next().ok_or("--cached needs a filename")?),
+            "--head" => head = true,
+            "--report" => report = Some(args.next().ok_or("--report needs a filename")?),
+            _ => roots.push(arg),
+        }
+    }
+    let [root] = roots[..] else {
+        return Err("crawl needs exactly one root url".to_owned())
+    };
+    if !dry_run && (cached.is_some() || head || report.is_some()) {
+        return Err("--cached, --head and --report need --dry-run".to_owned())
+    }
+
+    let mut crawler = Crawler::new(root.clone(), max_depth, must_contain, stop_words);
+    if let Some(politeness) = politeness {
+        crawler = crawler.with_politeness(politeness);
+    }
+    // --robots makes even the aggressive preset obey robots.txt
+    if robots {
+        crawler = crawler.with_robots(true);
+    }
+    if let Some(filename) = urls {
+        let list = std::fs::read_to_string(filename).map_err(|e| format!("cannot read {}: {}", filename, e))?;
+        let urls = list.lines().map(str::trim).filter(|url| !url.is_empty()).map(str::to_owned).collect::<Vec<String>>();
+        crawler = crawler.with_frontier(Frontier::from_urls(&urls));
+    }
+
+    if dry_run {
+        let cached = cached.map(|filename| read_graph(filename)).transpose()?;
+        let estimate = crawler.dry_run(cached.as_ref(), head);
+        for (depth, pages) in estimate.pages_per_depth.iter().enumerate() {
+            println!("depth {}: {} pages", depth, pages);
+        }
+        for (host, pages) in &estimate.pages_per_host {
+            println!("{}: {} pages", host, pages);
+        }
+        println!("{} pages would be fetched, {} disallowed by robots.txt, {} links filtered", estimate.total(), estimate.disallowed, estimate.filtered);
+        if head {
+            println!("{} pages unreachable, {} bytes stated", estimate.unreachable, estimate.known_bytes);
+        }
+        if let Some(filename) = report {
+            estimate.write_to_json(filename).map_err(|e| format!("cannot write {}: {}", filename, e))?;
+        }
+        return Ok(())
+    }
+
+    let graph = crawler.crawl();
+    println!("{} pages and {} links crawled", graph.get_num_of_vertices(), graph.get_num_of_edges());
+    if let Some(filename) = output {
+        graph.write_to_json(filename).map_err(|e| format!("cannot write {}: {}", filename, e))?;
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("diff") => run_diff(&args[1..]),
+        Some("benchmark") => run_benchmark(&args[1..]),
+        #[cfg(feature = "crawl")]
+        Some("crawl") => run_crawl(&args[1..]),
+        _ => Err(USAGE.to_owned()),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::FAILURE
+        },
+    }
+}
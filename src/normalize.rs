@@ -0,0 +1,88 @@
+use url::Url;
+
+/// A policy turning the links found in pages into the names of the vertices of the crawled graph, shared by a crawler and its scrapers,
+/// so that different urls of the same page become the same vertex. Normalizers are chained with Chain (or the method then),
+/// and any closure taking a link and returning an Option is a normalizer too. A link normalized to None is dropped as invalid.
+/// Links are normalized after they pass the link filter.
+/// # Examples
+/// ```
+/// use labisu::normalize::{ForceHttps, Standard, StripParams, UrlNormalizer};
+/// let normalizer = Standard.then(StripParams::new(&["page", "utm_source"])).then(ForceHttps);
+/// assert_eq!(Some("https://pwr.edu.pl/news?id=3".to_owned()), normalizer.normalize("http://pwr.edu.pl/news?page=2&id=3&utm_source=x"));
+/// let lowercase = |link: &str| Some(link.to_lowercase());
+/// assert_eq!(Some("https://pwr.edu.pl/a".to_owned()), lowercase.normalize("https://PWR.edu.pl/A"));
+/// ```
+pub trait UrlNormalizer: Send + Sync {
+    /// Returns the normalized link, or None if the link is invalid.
+    fn normalize(&self, link: &str) -> Option<String>;
+
+    /// Returns a normalizer applying this normalizer and then the other one.
+    fn then<N: UrlNormalizer + 'static>(self, other: N) -> Chain where Self: Sized + 'static {
+        Chain(vec![Box::new(self), Box::new(other)])
+    }
+}
+
+impl<F: Fn(&str) -> Option<String> + Send + Sync> UrlNormalizer for F {
+    fn normalize(&self, link: &str) -> Option<String> {
+        self(link)
+    }
+}
+
+/// Applies the normalizers one after another, dropping the link if any of them does.
+pub struct Chain(pub Vec<Box<dyn UrlNormalizer>>);
+
+impl UrlNormalizer for Chain {
+    fn normalize(&self, link: &str) -> Option<String> {
+        self.0.iter().try_fold(link.to_owned(), |link, normalizer| normalizer.normalize(&link))
+    }
+}
+
+/// Normalizes links with the normalize-url crate, e.g. removing default ports, fragments and trailing slashes.
+pub struct Standard;
+
+impl UrlNormalizer for Standard {
+    fn normalize(&self, link: &str) -> Option<String> {
+        normalize_url::normalizer::UrlNormalizer::new(link).ok()?.normalize(None).ok()
+    }
+}
+
+/// Changes the scheme of http links to https, so that both versions of a page become the same vertex.
+pub struct ForceHttps;
+
+impl UrlNormalizer for ForceHttps {
+    fn normalize(&self, link: &str) -> Option<String> {
+        Some(link.strip_prefix("http://").map_or_else(|| link.to_owned(), |rest| format!("https://{}", rest)))
+    }
+}
+
+/// Removes given parameters from the queries of links, e.g. pagination or tracking parameters, which make copies of the same page.
+/// Links which cannot be parsed are left unchanged.
+pub struct StripParams(Vec<String>);
+
+impl StripParams {
+    /// Creates a normalizer removing the parameters with given names.
+    pub fn new(names: &[&str]) -> StripParams {
+        StripParams(names.iter().map(|name| name.to_string()).collect())
+    }
+}
+
+impl UrlNormalizer for StripParams {
+    fn normalize(&self, link: &str) -> Option<String> {
+        let Ok(mut url) = Url::parse(link) else { return Some(link.to_owned()) };
+        if url.query().is_none() {
+            return Some(link.to_owned())
+        }
+        let kept = url.query_pairs().into_owned().filter(|(name, _)| !self.0.contains(name)).collect::<Vec<(String, String)>>();
+        if kept.is_empty() {
+            url.set_query(None);
+        } else {
+            url.query_pairs_mut().clear().extend_pairs(kept);
+        }
+        Some(url.into())
+    }
+}
+
+/// Returns the normalizer used by default: Standard followed by ForceHttps.
+pub fn default_normalizer() -> Chain {
+    Standard.then(ForceHttps)
+}
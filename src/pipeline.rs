@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use url::Url;
+
+use super::graphs::{Graph, GraphOps};
+
+/// A single cleanup step of a pipeline.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Step {
+    DedupeByContent,
+    CollapseHosts,
+    DropLeaves,
+    MinDegree(usize),
+}
+
+/// A sequence of cleanup steps applied to a crawled graph, in the order they were added.
+/// # Examples
+/// ```
+/// use labisu::graphs::Graph;
+/// use labisu::pipeline::CrawlPipeline;
+/// let names = vec!["https://a.pl/1", "https://a.pl/2", "https://b.pl/", "https://c.pl/", "https://d.pl/"];
+/// let mut g = Graph::from_names(names.into_iter().map(String::from).collect());
+/// g.add_edge_idx(0, 2);
+/// g.add_edge_idx(1, 3);
+/// g.add_edge_idx(2, 3);
+/// g.add_edge_idx(3, 4);
+/// let cleaned = CrawlPipeline::new().collapse_hosts().drop_leaves().apply(&g);
+/// assert_eq!(3, cleaned.get_num_of_vertices());
+/// assert_eq!(3, cleaned.get_num_of_edges());
+/// assert!(cleaned.contains_vertex("a.pl"));
+/// assert!(!cleaned.contains_vertex("d.pl"));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CrawlPipeline {
+    steps: Vec<Step>,
+}
+
+impl CrawlPipeline {
+    /// Creates an empty pipeline, which returns the graph unchanged.
+    pub fn new() -> CrawlPipeline {
+        CrawlPipeline { steps: vec![] }
+    }
+
+    /// Merges pages with identical bodies into one vertex, named after the first of them.
+    /// Pages whose content is unknown are never merged.
+    pub fn dedupe_by_content(mut self) -> CrawlPipeline {
+        self.steps.push(Step::DedupeByContent);
+        self
+    }
+
+    /// Merges pages with the same host into one vertex named by the host, dropping the edges inside a host.
+    /// Vertices whose names are not urls with a host keep their names.
+    pub fn collapse_hosts(mut self) -> CrawlPipeline {
+        self.steps.push(Step::CollapseHosts);
+        self
+    }
+
+    /// Removes the vertices of degree one (once, i.e. the vertices which become leaves are kept).
+    pub fn drop_leaves(mut self) -> CrawlPipeline {
+        self.steps.push(Step::DropLeaves);
+        self
+    }
+
+    /// Repeatedly removes the vertices of degree lower than min_degree, leaving the min_degree-core of the graph.
+    pub fn min_degree(mut self, min_degree: usize) -> CrawlPipeline {
+        self.steps.push(Step::MinDegree(min_degree));
+        self
+    }
+
+    /// Returns the number of steps of the pipeline.
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Checks if the pipeline has no steps.
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Applies the steps to the graph, where the content of all pages is unknown (so dedupe_by_content changes nothing).
+    pub fn apply(&self, graph: &Graph) -> Graph {
+        self.apply_with_hashes(graph, &vec![None; graph.get_num_of_vertices()])
+    }
+
+    /// Applies the steps to the graph, given the hashes of the bodies of the pages, indexed by vertices.
+    /// Panics if there are fewer hashes than vertices.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// use labisu::pipeline::CrawlPipeline;
+    /// let mut g = Graph::from_names(vec!["https://a.pl/".to_string(), "https://a.pl/index.html".to_string(), "https://b.pl/".to_string()]);
+    /// g.add_edge_idx(0, 2);
+    /// g.add_edge_idx(1, 2);
+    /// let cleaned = CrawlPipeline::new().dedupe_by_content().apply_with_hashes(&g, &[Some(7), Some(7), None]);
+    /// assert_eq!(2, cleaned.get_num_of_vertices());
+    /// assert_eq!(Some(0), cleaned.name_to_idx("https://a.pl/"));
+    /// assert_eq!(1, cleaned.get_num_of_edges());
+    /// ```
+    pub fn apply_with_hashes(&self, graph: &Graph, content_hashes: &[Option<u64>]) -> Graph {
+        let mut graph = graph.clone();
+        let mut hashes = content_hashes[..graph.get_num_of_vertices()].to_vec();
+        for step in &self.steps {
+            (graph, hashes) = match *step {
+                Step::DedupeByContent => {
+                    let keys = graph.vertices().map(|v| (hashes[v], graph.idx_to_name(v).unwrap())).collect();
+                    merge(&graph, &hashes, keys)
+                },
+                Step::CollapseHosts => {
+                    let keys =
+                        graph.vertices()
+                        .map(|v| {
+                            let name = graph.idx_to_name(v).unwrap();
+                            let host = Url::parse(&name).ok().and_then(|url| url.host_str().map(str::to_owned)).unwrap_or(name);
+                            (Some(host.clone()), host)
+                        })
+                        .collect();
+                    merge(&graph, &hashes, keys)
+                },
+                Step::DropLeaves => {
+                    let keep = graph.vertices().map(|v| graph.degree(v) != 1).collect::<Vec<bool>>();
+                    retain(graph, &hashes, &keep)
+                },
+                Step::MinDegree(min_degree) => {
+                    let keep = graph.core(min_degree);
+                    retain(graph, &hashes, &keep)
+                },
+            };
+        }
+        graph
+    }
+}
+
+/// Merges the vertices with equal keys into one vertex, named by the name given to the first of them.
+/// Vertices without a key are kept apart. Returns the merged graph with the hashes of the first vertices of the classes.
+fn merge<K: Hash + Eq>(graph: &Graph, hashes: &[Option<u64>], keys: Vec<(Option<K>, String)>) -> (Graph, Vec<Option<u64>>) {
+    let mut classes = HashMap::new();
+    let mut class_of = Vec::with_capacity(keys.len());
+    let mut names = vec![];
+    let mut class_hashes = vec![];
+    for (v, (key, name)) in keys.into_iter().enumerate() {
+        let class = match key {
+            Some(key) => *classes.entry(key).or_insert(names.len()),
+            None => names.len(),
+        };
+        if class == names.len() {
+            names.push(name);
+            class_hashes.push(hashes[v]);
+        }
+        class_of.push(class);
+    }
+
+    let mut merged = Graph::from_names(names);
+    for v in graph.vertices() {
+        graph.for_each_neighbour(v, &mut |u| {
+            if v < u && class_of[v] != class_of[u] {
+                merged.add_edge_idx(class_of[v], class_of[u]);
+            }
+        });
+    }
+    (merged, class_hashes)
+}
+
+/// Keeps only the vertices marked in keep, together with their hashes.
+fn retain(mut graph: Graph, hashes: &[Option<u64>], keep: &[bool]) -> (Graph, Vec<Option<u64>>) {
+    let kept = graph.retain_vertices(keep);
+    (graph, kept.iter().map(|v| hashes[*v]).collect())
+}
@@ -0,0 +1,12 @@
+pub use crate::bipartite::{expand_from_seed, find_bipartite, is_complete_bipartite, qr_parameters, BipartiteReport};
+#[cfg(feature = "crawl")]
+pub use crate::crawler::{CrawlReport, Crawler, EdgeProvenance, Politeness, Sink};
+#[cfg(feature = "crawl")]
+pub use crate::filter::{Contains, Domain, Excludes, Extension, LinkFilter, Matches};
+pub use crate::graphs::{EdgeError, Graph, GraphOps, UnknownVertex};
+#[cfg(feature = "crawl")]
+pub use crate::normalize::UrlNormalizer;
+#[cfg(feature = "crawl")]
+pub use crate::scraper::{DropReason, FetchError, Scraper};
+#[cfg(feature = "crawl")]
+pub use crate::url_graph::{InvalidUrl, UrlGraph};
@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use super::scraper::Scraper;
+use url::Url;
+
+/// The name under which the crawler looks for its group of rules in robots.txt, besides the group for all agents.
+pub const USER_AGENT: &str = "labisu";
+
+/// The longest delay between requests obeyed by the crawler, so that a host asking for a longer one does not hold a scraper for hours.
+pub const MAX_CRAWL_DELAY: Duration = Duration::from_secs(30);
+
+/// The rules of a robots.txt file applying to the crawler.
+/// # Examples
+/// ```
+/// use labisu::robots::Robots;
+/// use std::time::Duration;
+/// let robots = Robots::parse("User-agent: *\nDisallow: /private\nAllow: /private/public\nDisallow: /*.pdf$\nCrawl-delay: 2\n");
+/// assert!(robots.is_allowed("/"));
+/// assert!(!robots.is_allowed("/private/data"));
+/// assert!(robots.is_allowed("/private/public/data"));
+/// assert!(!robots.is_allowed("/docs/thesis.pdf"));
+/// assert!(robots.is_allowed("/docs/thesis.pdf?page=2"));
+/// assert_eq!(Some(Duration::from_secs(2)), robots.crawl_delay());
+/// assert!(!robots.allows(&"https://pwr.edu.pl/private/".parse().unwrap()));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Robots {
+    rules: Vec<(String, bool)>, // (pattern, allowed)
+    crawl_delay: Option<Duration>,
+}
+
+/// A group of rules of robots.txt, given for the user agents listed before them.
+#[derive(Default)]
+struct Group {
+    agents: Vec<String>,
+    robots: Robots,
+}
+
+impl Robots {
+    /// Returns the rules allowing everything, used when a host has no robots.txt.
+    pub fn allow_all() -> Robots {
+        Robots::default()
+    }
+
+    /// Returns the rules disallowing everything, used when the robots.txt of a host returns a server error.
+    /// # Examples
+    /// ```
+    /// use labisu::robots::Robots;
+    /// assert!(!Robots::disallow_all().is_allowed("/"));
+    /// assert!(!Robots::disallow_all().is_allowed("/index.html?page=2"));
+    /// ```
+    pub fn disallow_all() -> Robots {
+        Robots { rules: vec![("/".to_owned(), false)], crawl_delay: None }
+    }
+
+    /// Parses the contents of a robots.txt file, keeping the group of rules for USER_AGENT, or else the one for all agents.
+    /// Unknown lines are ignored, like crawlers usually do. A crawl delay longer than MAX_CRAWL_DELAY is cut to it.
+    /// # Examples
+    /// ```
+    /// use labisu::robots::{Robots, MAX_CRAWL_DELAY};
+    /// let robots = Robots::parse("User-agent: other\nDisallow: /\n\nUser-agent: labisu\nUser-agent: *\nDisallow: /tmp\n");
+    /// assert!(robots.is_allowed("/index.html"));
+    /// assert!(!robots.is_allowed("/tmp/index.html"));
+    /// assert!(Robots::parse("User-agent: other\nDisallow: /\n").is_allowed("/"));
+    /// assert_eq!(Some(MAX_CRAWL_DELAY), Robots::parse("User-agent: *\nCrawl-delay: 86400\n").crawl_delay());
+    /// ```
+    pub fn parse(contents: &str) -> Robots {
+        let mut groups: Vec<Group> = vec![];
+        let mut in_agents = false;
+        for line in contents.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let Some((key, value)) = line.split_once(':') else { continue };
+            let (key, value) = (key.trim().to_lowercase(), value.trim());
+            match key.as_str() {
+                "user-agent" => {
+                    if !in_agents {
+                        groups.push(Group::default());
+                    }
+                    groups.last_mut().unwrap().agents.push(value.to_lowercase());
+                    in_agents = true;
+                    continue
+                },
+                "allow" | "disallow" if !value.is_empty() => {
+                    if let Some(group) = groups.last_mut() {
+                        group.robots.rules.push((value.to_owned(), key == "allow"));
+                    }
+                },
+                "crawl-delay" => {
+                    if let (Some(group), Ok(seconds)) = (groups.last_mut(), value.parse::<f64>()) {
+                        group.robots.crawl_delay = Duration::try_from_secs_f64(seconds).ok().map(|delay| delay.min(MAX_CRAWL_DELAY));
+                    }
+                },
+                _ => {},
+            }
+            in_agents = false;
+        }
+        let find = |agent: &str| groups.iter().position(|group| group.agents.iter().any(|a| a == agent));
+        match find(USER_AGENT).or_else(|| find("*")) {
+            Some(i) => groups.swap_remove(i).robots,
+            None => Robots::allow_all(),
+        }
+    }
+
+    /// Checks if the crawler may fetch given path (with the query). The longest matching rule decides, an Allow rule winning ties,
+    /// and paths matching no rule are allowed. Patterns may contain * matching any sequence and end with $ anchoring the end of the path.
+    pub fn is_allowed(&self, path: &str) -> bool {
+        self.rules
+        .iter()
+        .filter(|(pattern, _)| matches(pattern, path))
+        .max_by_key(|(pattern, allowed)| (pattern.len(), *allowed))
+        .is_none_or(|(_, allowed)| *allowed)
+    }
+
+    /// Checks if the crawler may fetch the page with given url, by its path and query.
+    pub fn allows(&self, url: &Url) -> bool {
+        match url.query() {
+            Some(query) => self.is_allowed(&format!("{}?{}", url.path(), query)),
+            None => self.is_allowed(url.path()),
+        }
+    }
+
+    /// Returns the delay between requests requested by the host, if any.
+    pub fn crawl_delay(&self) -> Option<Duration> {
+        self.crawl_delay
+    }
+}
+
+/// Checks if a pattern of robots.txt matches the beginning of the path, or the whole path if it ends with $.
+fn matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(pattern) => (pattern, true),
+        None => (pattern, false),
+    };
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = path.strip_prefix(first) else { return false };
+    let parts = parts.collect::<Vec<&str>>();
+    for (i, part) in parts.iter().enumerate() {
+        // the last part of an anchored pattern has to end the path, the others match as early as possible
+        let found = if anchored && i + 1 == parts.len() { rest.ends_with(part).then(|| rest.len() - part.len()) } else { rest.find(part) };
+        match found {
+            Some(position) => rest = &rest[position + part.len()..],
+            None => return false,
+        }
+    }
+    !anchored || rest.is_empty()
+}
+
+/// The rules of robots.txt of the hosts met during a crawl, every one fetched once with the first scraper asking for it.
+#[derive(Default)]
+pub struct RobotsCache {
+    entries: Mutex<HashMap<String, Arc<Robots>>>,
+}
+
+impl RobotsCache {
+    /// Creates an empty cache.
+    pub fn new() -> RobotsCache {
+        RobotsCache::default()
+    }
+
+    /// Returns the rules of the host of given url, fetching its robots.txt with the scraper if it is not cached.
+    /// robots.txt is fetched with a plain request (see Scraper::fetch_plain), even if the scraper renders pages with a browser.
+    /// A host whose robots.txt returns a client error allows everything, and a host whose robots.txt is unreachable,
+    /// i.e. returns a server error or cannot be fetched because of a network error or a timeout, disallows everything, as RFC 9309 requires.
+    /// The lock is not held during the request, so a host may be fetched twice by concurrent scrapers.
+    /// # Examples
+    /// ```
+    /// use labisu::robots::RobotsCache;
+    /// use labisu::scraper::Scraper;
+    /// let cache = RobotsCache::new();
+    /// let url = "http://127.0.0.1:9/page".parse().unwrap();
+    /// assert!(!cache.get(&Scraper::new(vec![], vec![]), &url).allows(&url));
+    /// ```
+    pub fn get(&self, scraper: &Scraper, url: &Url) -> Arc<Robots> {
+        let origin = url.origin().ascii_serialization();
+        if let Some(robots) = self.entries.lock().unwrap().get(&origin) {
+            return Arc::clone(robots)
+        }
+        let robots = match scraper.fetch_plain(&format!("{}/robots.txt", origin)) {
+            Ok(page) if page.status < 400 => Robots::parse(&String::from_utf8_lossy(&page.body)),
+            Ok(page) if page.status < 500 => Robots::allow_all(),
+            _ => Robots::disallow_all(),
+        };
+        let robots = Arc::new(robots);
+        self.entries.lock().unwrap().insert(origin, Arc::clone(&robots));
+        robots
+    }
+}
@@ -1,16 +1,186 @@
 extern crate reqwest;
 extern crate scraper;
 
-use normalize_url::normalizer;
-use scraper::{Html, Selector};
-use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use scraper::{ElementRef, Html, Selector};
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use url::Url;
+
+use super::dns::{DnsCache, ResolutionError};
+use super::filter::{self, LinkFilter};
+use super::normalize::{default_normalizer, UrlNormalizer};
+
+/// A fetched page together with the parts of the response needed to archive it.
+#[derive(Clone, Debug)]
+pub struct Page {
+    /// The url the page was fetched from.
+    pub url: String,
+    /// The HTTP status code of the response.
+    pub status: u16,
+    /// The headers of the response.
+    pub headers: Vec<(String, String)>,
+    /// The body of the response.
+    pub body: Vec<u8>,
+}
+
+impl Page {
+    /// Returns the body decoded as UTF-8, with invalid sequences replaced.
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+}
+
+/// The part of a page a link was found in, based on the elements containing it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LinkClass {
+    /// A link in a navigation menu or a header of the page.
+    Nav,
+    /// A link in a footer of the page.
+    Footer,
+    /// Any other link, i.e. a link in the content of the page.
+    Content,
+}
+
+impl LinkClass {
+    /// Classifies a link by its closest ancestor which is a nav, header or footer element (or has the corresponding ARIA role).
+    fn of(link: &ElementRef) -> LinkClass {
+        for element in link.ancestors().filter_map(|node| node.value().as_element()) {
+            match (element.name(), element.attr("role")) {
+                ("nav", _) | ("header", _) | (_, Some("navigation")) | (_, Some("banner")) => return LinkClass::Nav,
+                ("footer", _) | (_, Some("contentinfo")) => return LinkClass::Footer,
+                _ => (),
+            }
+        }
+        LinkClass::Content
+    }
+}
+
+/// The reason an href found in a page was not turned into a link.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DropReason {
+    /// The href only points to an anchor in the same page.
+    FragmentOnly,
+    /// The href is not an absolute http or https url (e.g. a relative or mailto link).
+    NonHttp,
+    /// The href does not contain one of the required words.
+    MissingWord,
+    /// The href contains a stop word.
+    StopWord,
+    /// The href is rejected by another link filter.
+    Filtered,
+    /// The href could not be normalized.
+    Invalid,
+}
+
+/// The numbers of hrefs dropped for each reason.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DropStats {
+    /// The number of hrefs pointing only to an anchor in the same page.
+    pub fragment_only: usize,
+    /// The number of hrefs which are not absolute http or https urls.
+    pub non_http: usize,
+    /// The number of hrefs without one of the required words.
+    pub missing_word: usize,
+    /// The number of hrefs containing a stop word.
+    pub stop_word: usize,
+    /// The number of hrefs rejected by other link filters.
+    pub filtered: usize,
+    /// The number of hrefs which could not be normalized.
+    pub invalid: usize,
+    /// The number of links already found before, in the same page or (during a crawl) as an existing edge.
+    pub duplicate: usize,
+}
+
+impl DropStats {
+    /// Counts an href dropped for the given reason.
+    pub fn record(&mut self, reason: DropReason) {
+        match reason {
+            DropReason::FragmentOnly => self.fragment_only += 1,
+            DropReason::NonHttp => self.non_http += 1,
+            DropReason::MissingWord => self.missing_word += 1,
+            DropReason::StopWord => self.stop_word += 1,
+            DropReason::Filtered => self.filtered += 1,
+            DropReason::Invalid => self.invalid += 1,
+        }
+    }
+
+    /// Adds the tallies of other to the tallies of self.
+    pub fn merge(&mut self, other: &DropStats) {
+        self.fragment_only += other.fragment_only;
+        self.non_http += other.non_http;
+        self.missing_word += other.missing_word;
+        self.stop_word += other.stop_word;
+        self.filtered += other.filtered;
+        self.invalid += other.invalid;
+        self.duplicate += other.duplicate;
+    }
+
+    /// Returns the total number of dropped hrefs.
+    pub fn total(&self) -> usize {
+        self.fragment_only + self.non_http + self.missing_word + self.stop_word + self.filtered + self.invalid + self.duplicate
+    }
+}
+
+/// The default limit of the number of bytes of a body read by the scraper.
+pub const DEFAULT_MAX_BODY_SIZE: usize = 16 * 1024 * 1024;
+
+/// The default time after which the headless browser rendering a page is killed.
+#[cfg(feature = "headless")]
+pub const DEFAULT_BROWSER_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// The kind of a failure of fetching a page.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FetchError {
+    /// The host name could not be resolved.
+    Resolution,
+    /// The connection to the host could not be established.
+    Connection,
+    /// The request timed out.
+    Timeout,
+    /// Any other failure, e.g. of reading the body.
+    Other,
+}
+
+impl FetchError {
+    /// Classifies an error of the HTTP client, looking for a resolution error of the DNS cache among its sources.
+    fn classify(error: &reqwest::Error) -> FetchError {
+        let mut source: Option<&(dyn std::error::Error + 'static)> = Some(error);
+        while let Some(e) = source {
+            if e.downcast_ref::<ResolutionError>().is_some() {
+                return FetchError::Resolution
+            }
+            source = e.source();
+        }
+        if error.is_timeout() {
+            FetchError::Timeout
+        } else if error.is_connect() {
+            FetchError::Connection
+        } else {
+            FetchError::Other
+        }
+    }
+}
 
 /// A scraper for HTML documents finding links to other pages.
-/// It contains a set of stop words and words that must be contained in the link.
+/// It contains a link filter, by default made of a set of stop words and words that must be contained in the link.
 pub struct Scraper {
     client: reqwest::blocking::Client,
-    must_contain: Vec<String>,
-    stop_words: Vec<String>,
+    filter: Arc<dyn LinkFilter>,
+    normalizer: Arc<dyn UrlNormalizer>,
+    dns_cache: Option<Arc<DnsCache>>,
+    host_overrides: HashMap<String, Vec<SocketAddr>>,
+    accept_invalid_certs: bool,
+    root_certificates: Vec<reqwest::Certificate>,
+    max_body_size: usize,
+    #[cfg(feature = "headless")]
+    browser: Option<String>,
+    #[cfg(feature = "headless")]
+    browser_timeout: std::time::Duration,
+    #[cfg(feature = "headless")]
+    browser_sandbox: bool,
 }
 
 impl Scraper {
@@ -25,9 +195,448 @@ impl Scraper {
     pub fn new(must_contain: Vec<String>, stop_words: Vec<String>) -> Scraper {
         Scraper {
             client: reqwest::blocking::Client::new(),
-            must_contain,
-            stop_words,
+            filter: Arc::new(filter::words(&must_contain, &stop_words)),
+            normalizer: Arc::new(default_normalizer()),
+            dns_cache: None,
+            host_overrides: HashMap::new(),
+            accept_invalid_certs: false,
+            root_certificates: vec![],
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+            #[cfg(feature = "headless")]
+            browser: None,
+            #[cfg(feature = "headless")]
+            browser_timeout: DEFAULT_BROWSER_TIMEOUT,
+            #[cfg(feature = "headless")]
+            browser_sandbox: true,
+        }
+    }
+
+    /// Replaces the filter of the words with given link filter, which can be shared with other scrapers.
+    /// # Examples
+    /// ```
+    /// use labisu::filter::{Domain, Extension, LinkFilter};
+    /// use labisu::scraper::Scraper;
+    /// use std::sync::Arc;
+    /// let scraper = Scraper::new(vec![], vec![]).with_filter(Arc::new(Domain::new("pwr.edu.pl").and(Extension::new(&["pdf"]).not())));
+    /// let links = scraper.links("<a href='https://pwr.edu.pl/a'>a</a><a href='https://pwr.edu.pl/b.pdf'>b</a><a href='https://example.com/'>c</a>");
+    /// assert_eq!(1, links.len());
+    /// ```
+    pub fn with_filter(mut self, filter: Arc<dyn LinkFilter>) -> Scraper {
+        self.filter = filter;
+        self
+    }
+
+    /// Replaces the default normalizer of links (see normalize::default_normalizer) with given one, which can be shared with other scrapers.
+    /// # Examples
+    /// ```
+    /// use labisu::normalize::{Standard, StripParams, UrlNormalizer};
+    /// use labisu::scraper::Scraper;
+    /// use std::sync::Arc;
+    /// let scraper = Scraper::new(vec![], vec![]).with_normalizer(Arc::new(Standard.then(StripParams::new(&["page"]))));
+    /// let links = scraper.links("<a href='http://pwr.edu.pl/news?page=1'>1</a><a href='http://pwr.edu.pl/news?page=2'>2</a>");
+    /// assert_eq!(1, links.len());
+    /// assert!(links.iter().all(|link| link.starts_with("http://")));
+    /// ```
+    pub fn with_normalizer(mut self, normalizer: Arc<dyn UrlNormalizer>) -> Scraper {
+        self.normalizer = normalizer;
+        self
+    }
+
+    /// Builds the HTTP client according to the settings of the scraper.
+    fn build_client(&self) -> reqwest::blocking::Client {
+        // the custom resolver can only be set on the builder of the asynchronous client, which the blocking one wraps
+        let mut builder = match &self.dns_cache {
+            Some(cache) => reqwest::blocking::ClientBuilder::from(reqwest::Client::builder().dns_resolver(Arc::clone(cache))),
+            None => reqwest::blocking::Client::builder(),
+        };
+        for (host, addrs) in &self.host_overrides {
+            builder = builder.resolve_to_addrs(host, addrs);
+        }
+        for certificate in &self.root_certificates {
+            builder = builder.add_root_certificate(certificate.clone());
+        }
+        builder = builder.danger_accept_invalid_certs(self.accept_invalid_certs);
+        builder.build().expect("Unable to create the HTTP client.")
+    }
+
+    /// Sets the limit of the number of bytes of a body read by the scraper, by default DEFAULT_MAX_BODY_SIZE.
+    /// Longer bodies are truncated, so only the links in their beginning are found.
+    /// # Examples
+    /// ```
+    /// use labisu::scraper::Scraper;
+    /// let scraper = Scraper::new(vec![], vec![]).with_max_body_size(1024 * 1024);
+    /// ```
+    pub fn with_max_body_size(mut self, max_body_size: usize) -> Scraper {
+        self.max_body_size = max_body_size;
+        self
+    }
+
+    /// Makes the scraper accept invalid TLS certificates, e.g. self-signed ones of intranet sites.
+    /// It disables the verification of the identity of the hosts, so it should be used only in trusted networks.
+    /// # Examples
+    /// ```
+    /// use labisu::scraper::Scraper;
+    /// let scraper = Scraper::new(vec![], vec![]).with_invalid_certs_accepted();
+    /// ```
+    pub fn with_invalid_certs_accepted(mut self) -> Scraper {
+        self.accept_invalid_certs = true;
+        self.client = self.build_client();
+        self
+    }
+
+    /// Makes the scraper trust the root certificates from a PEM file (which may contain a bundle of them)
+    /// in addition to the built-in ones, e.g. the certificate of an internal certificate authority.
+    /// Returns an Err value if the file cannot be read or does not contain valid certificates.
+    /// # Examples
+    /// ```
+    /// use labisu::scraper::Scraper;
+    /// std::fs::write("invalid.pem", "not a certificate").unwrap();
+    /// assert!(Scraper::new(vec![], vec![]).with_root_certificates("invalid.pem").is_err());
+    /// assert!(Scraper::new(vec![], vec![]).with_root_certificates("missing.pem").is_err());
+    /// ```
+    pub fn with_root_certificates(mut self, pem_filename: &str) -> Result<Scraper, &'static str> {
+        let pem = std::fs::read(pem_filename).map_err(|_| "Unable to read the certificates file.")?;
+        let certificates = reqwest::Certificate::from_pem_bundle(&pem).map_err(|_| "The certificates are not valid.")?;
+        if certificates.is_empty() {
+            return Err("The certificates are not valid.")
+        }
+        self.root_certificates.extend(certificates);
+        self.client = self.build_client();
+        Ok(self)
+    }
+
+    /// Makes the scraper connect to the given IPv4 or IPv6 addresses instead of resolving the host names,
+    /// e.g. to crawl a staging environment or a mirror in a closed network without public DNS.
+    /// A host may be given several addresses. The port is taken from the url as usual.
+    /// The overrides can be read from a hosts file with dns::read_hosts_file.
+    /// # Examples
+    /// ```
+    /// use labisu::scraper::Scraper;
+    /// let overrides = vec![("mirror.local".to_string(), "127.0.0.1".parse().unwrap())];
+    /// let scraper = Scraper::new(vec![], vec![]).with_host_overrides(&overrides);
+    /// assert!(scraper.fetch_checked("http://mirror.local:9/").is_err());
+    /// ```
+    pub fn with_host_overrides(mut self, overrides: &[(String, IpAddr)]) -> Scraper {
+        for (host, ip) in overrides {
+            self.host_overrides.entry(host.to_owned()).or_default().push(SocketAddr::new(*ip, 0));
+        }
+        self.client = self.build_client();
+        self
+    }
+
+    /// Makes the scraper resolve host names with the given cache, which may be shared with other scrapers.
+    /// Failed resolutions are then reported as FetchError::Resolution by fetch_checked.
+    /// # Examples
+    /// ```
+    /// use labisu::dns::DnsCache;
+    /// use labisu::scraper::{FetchError, Scraper};
+    /// use std::sync::Arc;
+    /// let cache = Arc::new(DnsCache::new());
+    /// let scraper = Scraper::new(vec![], vec![]).with_dns_cache(Arc::clone(&cache));
+    /// assert_eq!(Some(FetchError::Resolution), scraper.fetch_checked("https://no-such-host.invalid/").err());
+    /// assert_eq!(1, cache.get_num_of_lookups());
+    /// ```
+    pub fn with_dns_cache(mut self, cache: Arc<DnsCache>) -> Scraper {
+        self.dns_cache = Some(cache);
+        self.client = self.build_client();
+        self
+    }
+
+    /// Makes the scraper fetch pages with a headless Chromium (or Chrome) given by the path to its binary,
+    /// so that the links injected by JavaScript are found as well. The page is rendered with --dump-dom,
+    /// which prints the DOM after the scripts have run. Such pages are recorded with status 200 and no headers.
+    /// The rendered DOM is read up to the limit of the body size and the browser is killed after DEFAULT_BROWSER_TIMEOUT.
+    /// # Examples
+    /// ```
+    /// use labisu::scraper::Scraper;
+    /// let scraper = Scraper::new(vec![], vec![]).with_browser("chromium");
+    /// ```
+    #[cfg(feature = "headless")]
+    pub fn with_browser(mut self, binary: &str) -> Scraper {
+        self.browser = Some(binary.to_owned());
+        self
+    }
+
+    /// Sets the time after which the headless browser rendering a page is killed and the fetch fails with FetchError::Timeout.
+    /// # Examples
+    /// ```
+    /// use labisu::scraper::Scraper;
+    /// use std::time::Duration;
+    /// let scraper = Scraper::new(vec![], vec![]).with_browser("chromium").with_browser_timeout(Duration::from_secs(10));
+    /// ```
+    #[cfg(feature = "headless")]
+    pub fn with_browser_timeout(mut self, timeout: std::time::Duration) -> Scraper {
+        self.browser_timeout = timeout;
+        self
+    }
+
+    /// Sets whether the headless browser runs in its sandbox, which it does by default.
+    /// Disabling the sandbox (--no-sandbox) is only needed where the browser cannot create one, e.g. as root in a container,
+    /// and leaves the system exposed to exploits of the rendered pages.
+    /// # Examples
+    /// ```
+    /// use labisu::scraper::Scraper;
+    /// let scraper = Scraper::new(vec![], vec![]).with_browser("chromium").with_browser_sandbox(false);
+    /// ```
+    #[cfg(feature = "headless")]
+    pub fn with_browser_sandbox(mut self, sandbox: bool) -> Scraper {
+        self.browser_sandbox = sandbox;
+        self
+    }
+
+    /// Renders the page with the headless browser.
+    /// Returns an Err value if the browser cannot be run, fails or does not finish before the timeout.
+    #[cfg(feature = "headless")]
+    fn fetch_rendered(&self, binary: &str, url: &str) -> Result<Page, FetchError> {
+        use std::process::{Command, Stdio};
+        use std::time::{Duration, Instant};
+
+        let mut command = Command::new(binary);
+        command.args(["--headless", "--disable-gpu", "--dump-dom"]);
+        if !self.browser_sandbox {
+            command.arg("--no-sandbox");
         }
+        let mut child = 
+            command
+            .arg(url)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|_| FetchError::Other)?;
+
+        // the DOM is read up to the cap and the rest is discarded, so that the browser is never blocked on a full pipe
+        let mut stdout = child.stdout.take().unwrap();
+        let max_body_size = self.max_body_size as u64;
+        let reader = std::thread::spawn(move || {
+            let mut body = Vec::new();
+            let read = (&mut stdout).take(max_body_size).read_to_end(&mut body);
+            std::io::copy(&mut stdout, &mut std::io::sink()).and(read).map(|_| body)
+        });
+
+        let deadline = Instant::now() + self.browser_timeout;
+        let status = loop {
+            match child.try_wait() {
+                Ok(Some(status)) => break status,
+                Ok(None) if Instant::now() < deadline => std::thread::sleep(Duration::from_millis(10)),
+                Ok(None) => {
+                    // the reader is not joined, as the pipe may be held open by the processes spawned by the browser
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(FetchError::Timeout)
+                },
+                Err(_) => return Err(FetchError::Other),
+            }
+        };
+        let body = reader.join().map_err(|_| FetchError::Other)?.map_err(|_| FetchError::Other)?;
+        if !status.success() {
+            return Err(FetchError::Other)
+        }
+        Ok(Page { url: url.to_owned(), status: 200, headers: vec![], body })
+    }
+
+    /// Fetches the page with given url, with the headless browser if it was set.
+    /// Returns None if the connection to the url fails or the body cannot be read.
+    pub fn fetch(&self, url: &str) -> Option<Page> {
+        self.fetch_checked(url).ok()
+    }
+
+    /// Fetches the page with given url like fetch, but returns the kind of the failure as an Err value.
+    pub fn fetch_checked(&self, url: &str) -> Result<Page, FetchError> {
+        #[cfg(feature = "headless")]
+        if let Some(binary) = &self.browser {
+            return self.fetch_rendered(binary, url)
+        }
+        self.fetch_plain(url)
+    }
+
+    /// Fetches the page with given url with a plain GET request, never with the headless browser,
+    /// e.g. for files which are not rendered, like robots.txt. Returns the kind of the failure as an Err value.
+    pub fn fetch_plain(&self, url: &str) -> Result<Page, FetchError> {
+        let resp = self.client.get(url).send().map_err(|e| FetchError::classify(&e))?;
+        let status = resp.status().as_u16();
+        let headers = 
+            resp.headers()
+            .iter()
+            .filter(|(name, _)| *name != reqwest::header::TRANSFER_ENCODING) // the body is stored decoded
+            .map(|(name, value)| (name.to_string(), String::from_utf8_lossy(value.as_bytes()).into_owned()))
+            .collect();
+        // the body is read up to the cap, so that a few huge pages do not exhaust the memory of concurrent scrapers
+        let mut body = Vec::with_capacity(resp.content_length().map_or(0, |len| len.min(self.max_body_size as u64) as usize));
+        resp.take(self.max_body_size as u64).read_to_end(&mut body).map_err(|_| FetchError::Other)?;
+        Ok(Page { url: url.to_owned(), status, headers, body })
+    }
+
+    /// Sends a HEAD request to given url, so that the status and the headers of a page are known without downloading its body.
+    /// Returns a page with an empty body, or the kind of the failure as an Err value. The headless browser is never used.
+    pub fn fetch_head(&self, url: &str) -> Result<Page, FetchError> {
+        let resp = self.client.head(url).send().map_err(|e| FetchError::classify(&e))?;
+        let status = resp.status().as_u16();
+        let headers =
+            resp.headers()
+            .iter()
+            .map(|(name, value)| (name.to_string(), String::from_utf8_lossy(value.as_bytes()).into_owned()))
+            .collect();
+        Ok(Page { url: url.to_owned(), status, headers, body: vec![] })
+    }
+
+    /// Filters and normalizes a link, resolving it against the base url if it is relative.
+    /// Returns the reason as an Err value if it is not accepted.
+    fn check(&self, href: &str, base: Option<&Url>) -> Result<String, DropReason> {
+        if href.starts_with('#') {
+            return Err(DropReason::FragmentOnly)
+        }
+        let resolved;
+        let href = match base {
+            Some(base) if !href.is_empty() && !href.starts_with("http") => {
+                resolved = base.join(href).map_err(|_| DropReason::Invalid)?;
+                resolved.as_str()
+            },
+            _ => href,
+        };
+        if !href.starts_with("http") {
+            return Err(DropReason::NonHttp)
+        }
+        self.filter.check(href)?;
+        self.normalizer.normalize(href).ok_or(DropReason::Invalid)
+    }
+
+    /// Finds links to other pages in the given HTML document while normalizing their urls.
+    /// # Examples
+    /// ```
+    /// use labisu::scraper::Scraper;
+    /// let scraper = Scraper::new(vec![], vec!["stop".to_owned()]);
+    /// let links = scraper.links("<a href='https://pwr.edu.pl/a'>a</a><a href='https://pwr.edu.pl/stop'>b</a><a href='/c'>c</a>");
+    /// assert_eq!(1, links.len());
+    /// ```
+    pub fn links(&self, body: &str) -> HashSet<String> {
+        self.classified_links(body).into_keys().collect()
+    }
+
+    /// Finds links to other pages in the given HTML document like links, together with the part of the page they were found in.
+    /// A link found in several parts is classified as content if it appears in the content at all.
+    /// # Examples
+    /// ```
+    /// use labisu::scraper::{LinkClass, Scraper};
+    /// let scraper = Scraper::new(vec![], vec![]);
+    /// let links = scraper.classified_links("<nav><a href='https://pwr.edu.pl/a'>a</a></nav><main><a href='https://pwr.edu.pl/b'>b</a></main><footer><a href='https://pwr.edu.pl/c'>c</a></footer>");
+    /// assert_eq!(Some(&LinkClass::Nav), links.get("https://pwr.edu.pl/a"));
+    /// assert_eq!(Some(&LinkClass::Content), links.get("https://pwr.edu.pl/b"));
+    /// assert_eq!(Some(&LinkClass::Footer), links.get("https://pwr.edu.pl/c"));
+    /// ```
+    pub fn classified_links(&self, body: &str) -> HashMap<String, LinkClass> {
+        self.tallied_links(body).0
+    }
+
+    /// Finds links to other pages in the given HTML document like classified_links, together with the numbers of hrefs dropped for each reason.
+    /// # Examples
+    /// ```
+    /// use labisu::scraper::Scraper;
+    /// let scraper = Scraper::new(vec![], vec!["stop".to_owned()]);
+    /// let (links, drops) = scraper.tallied_links("<a href='#top'>a</a><a href='/b'>b</a><a href='https://pwr.edu.pl/stop'>c</a><a href='https://pwr.edu.pl/d'>d</a><a href='https://pwr.edu.pl/d'>d</a>");
+    /// assert_eq!(1, links.len());
+    /// assert_eq!((1, 1, 1, 1), (drops.fragment_only, drops.non_http, drops.stop_word, drops.duplicate));
+    /// assert_eq!(4, drops.total());
+    /// ```
+    pub fn tallied_links(&self, body: &str) -> (HashMap<String, LinkClass>, DropStats) {
+        self.tally(body, None)
+    }
+
+    /// Finds links to other pages in the given HTML document like links, but relative links are resolved against base_url
+    /// the same way as in fetched pages. Nothing is fetched, so the filters and the normalization can be tested on fixture documents.
+    /// If base_url is not a valid url, relative links are dropped like in links.
+    /// # Examples
+    /// ```
+    /// use labisu::scraper::Scraper;
+    /// use std::collections::HashSet;
+    /// let scraper = Scraper::new(vec!["pwr.edu".to_owned()], vec!["stop".to_owned()]);
+    /// let html = "<a href='/a'>a</a><a href='b/c'>b</a><a href='../stop'>c</a><a href='https://example.com/'>d</a><a href='mailto:x@pwr.edu.pl'>e</a>";
+    /// let links = scraper.extract_links(html, "https://pwr.edu.pl/x/y");
+    /// let expected = HashSet::from(["https://pwr.edu.pl/a".to_owned(), "https://pwr.edu.pl/x/b/c".to_owned()]);
+    /// assert_eq!(expected, links);
+    /// ```
+    pub fn extract_links(&self, html: &str, base_url: &str) -> HashSet<String> {
+        self.tally(html, Url::parse(base_url).ok().as_ref()).0.into_keys().collect()
+    }
+
+    /// Finds the links in an HTML document, resolving relative links against the base url if it is given.
+    fn tally(&self, body: &str, base: Option<&Url>) -> (HashMap<String, LinkClass>, DropStats) {
+        let document = Html::parse_document(body);
+        let mut links = HashMap::new();
+        let mut drops = DropStats::default();
+        if let Ok(selector) = Selector::parse("a") {
+            for link in document.select(&selector) {
+                match self.check(link.value().attr("href").unwrap_or_default(), base) {
+                    Ok(href) => {
+                        let class = LinkClass::of(&link);
+                        match links.get_mut(&href) {
+                            Some(entry) => {
+                                drops.duplicate += 1;
+                                if class == LinkClass::Content {
+                                    *entry = class;
+                                }
+                            },
+                            None => { links.insert(href, class); },
+                        }
+                    },
+                    Err(reason) => drops.record(reason),
+                }
+            }
+        }
+        (links, drops)
+    }
+
+    /// Finds links to other pages in the given PDF document, i.e. the targets of its URI actions, while normalizing their urls.
+    /// If the document cannot be parsed, then an empty HashSet is returned.
+    /// # Examples
+    /// ```
+    /// use labisu::scraper::Scraper;
+    /// use lopdf::{dictionary, Document, Object};
+    /// let mut document = Document::with_version("1.5");
+    /// let action = document.add_object(dictionary! { "S" => "URI", "URI" => Object::string_literal("https://pwr.edu.pl/a") });
+    /// let catalog = document.add_object(dictionary! { "Type" => "Catalog", "OpenAction" => action });
+    /// document.trailer.set("Root", catalog);
+    /// let mut body = vec![];
+    /// document.save_to(&mut body).unwrap();
+    /// let scraper = Scraper::new(vec![], vec![]);
+    /// assert_eq!(1, scraper.pdf_links(&body).len());
+    /// assert!(scraper.pdf_links(b"%PDF-broken").is_empty());
+    /// ```
+    #[cfg(feature = "pdf")]
+    pub fn pdf_links(&self, body: &[u8]) -> HashSet<String> {
+        let document = match lopdf::Document::load_mem(body) {
+            Ok(document) => document,
+            Err(_) => return HashSet::new(),
+        };
+        document.objects
+        .values()
+        .filter_map(|object| object.as_dict().ok())
+        .filter_map(|dict| dict.get(b"URI").ok())
+        .filter_map(|uri| uri.as_str().ok())
+        .filter_map(|uri| self.check(&String::from_utf8_lossy(uri), None).ok())
+        .collect()
+    }
+
+    /// Finds links to other pages in a fetched page, which is an HTML document or (with the pdf feature) a PDF document.
+    pub fn page_links(&self, page: &Page) -> HashSet<String> {
+        self.classified_page_links(page).into_keys().collect()
+    }
+
+    /// Finds links to other pages in a fetched page like page_links, together with the part of the page they were found in.
+    /// All links in a PDF document are classified as content.
+    pub fn classified_page_links(&self, page: &Page) -> HashMap<String, LinkClass> {
+        self.tallied_page_links(page).0
+    }
+
+    /// Finds links to other pages in a fetched page like classified_page_links, together with the numbers of hrefs dropped for each reason.
+    /// Relative links in an HTML document are resolved against the url of the page. The hrefs of a PDF document are not tallied.
+    pub fn tallied_page_links(&self, page: &Page) -> (HashMap<String, LinkClass>, DropStats) {
+        #[cfg(feature = "pdf")]
+        if page.body.starts_with(b"%PDF") {
+            let links = self.pdf_links(&page.body).into_iter().map(|link| (link, LinkClass::Content)).collect();
+            return (links, DropStats::default())
+        }
+        self.tally(&page.text(), Url::parse(&page.url).ok().as_ref())
     }
 
     /// Scrapes the given url for links to other pages while normalizing their urls.
@@ -43,31 +652,9 @@ impl Scraper {
     /// }
     /// ```
     pub fn scrape(&self, url: &str) -> HashSet<String> {
-        let mut result = HashSet::new();
-        let resp = self.client.get(url).send();
-        if let Ok(resp) = resp {
-            if let Ok(body) = resp.text() {
-                let document = Html::parse_document(&body);
-                if let Ok(selector) = Selector::parse("a") {
-                    for link in document.select(&selector) {
-                        let href = link.value().attr("href").unwrap_or_default();
-                        if href.starts_with("http") 
-                        && self.must_contain.iter().all(|word| href.contains(word)) // must contain
-                        && self.stop_words.iter().all(|word| !href.contains(word)) { // must not contain
-                            if let Ok(normalizer) = normalizer::UrlNormalizer::new(href) {
-                                if let Ok(normalized) = normalizer.normalize(None) {
-                                    let mut normalized = normalized.to_owned();
-                                    if normalized.chars().nth(4) != Some('s') {
-                                        normalized.insert(4, 's');
-                                    }
-                                    result.insert(normalized);
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+        match self.fetch(url) {
+            Some(page) => self.page_links(&page),
+            None => HashSet::new(),
         }
-        result
     }
 }
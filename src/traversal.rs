@@ -0,0 +1,161 @@
+use std::collections::VecDeque;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+#[cfg(feature = "parallel")]
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use super::graphs::GraphOps;
+
+/// Renames the labels so that components are numbered 0, 1, ... in order of their smallest vertex.
+#[cfg(feature = "parallel")]
+fn normalize_labels(labels: &[usize]) -> Vec<usize> {
+    let mut new_label = vec![usize::MAX; labels.len()];
+    let mut next = 0;
+    labels
+    .iter()
+    .map(|label| {
+        if new_label[*label] == usize::MAX {
+            new_label[*label] = next;
+            next += 1;
+        }
+        new_label[*label]
+    })
+    .collect()
+}
+
+/// Returns for each vertex the index of its connected component.
+/// Components are numbered 0, 1, ... in order of their smallest vertex.
+/// # Examples
+/// ```
+/// use labisu::graphs::Graph;
+/// use labisu::traversal::connected_components;
+/// let mut g = Graph::from_names((0..5).map(|i| format!("v_{}", i)).collect());
+/// g.add_edge_idx(0, 3);
+/// g.add_edge_idx(1, 4);
+/// assert_eq!(vec![0, 1, 2, 0, 1], connected_components(&g));
+/// ```
+pub fn connected_components<G: GraphOps>(graph: &G) -> Vec<usize> {
+    let n = graph.get_num_of_vertices();
+    let mut labels = vec![usize::MAX; n];
+    let mut stack = Vec::new();
+    let mut next = 0;
+    for start in 0..n {
+        if labels[start] == usize::MAX {
+            labels[start] = next;
+            stack.push(start);
+            while let Some(current) = stack.pop() {
+                graph.for_each_neighbour(current, &mut |neighbour| {
+                    if labels[neighbour] == usize::MAX {
+                        labels[neighbour] = next;
+                        stack.push(neighbour);
+                    }
+                });
+            }
+            next += 1;
+        }
+    }
+    labels
+}
+
+/// Runs a breadth-first search started at all given sources at once.
+/// Returns for each vertex its distance to the closest source or None if it is unreachable.
+/// # Examples
+/// ```
+/// use labisu::graphs::Graph;
+/// use labisu::traversal::bfs;
+/// let mut path = Graph::from_names((0..5).map(|i| format!("v_{}", i)).collect());
+/// (0..3).for_each(|i| { path.add_edge_idx(i, i + 1); });
+/// assert_eq!(vec![Some(0), Some(1), Some(1), Some(0), None], bfs(&path, &[0, 3]));
+/// ```
+pub fn bfs<G: GraphOps>(graph: &G, sources: &[usize]) -> Vec<Option<usize>> {
+    let mut distances = vec![None; graph.get_num_of_vertices()];
+    let mut queue = VecDeque::new();
+    for source in sources {
+        if distances[*source].is_none() {
+            distances[*source] = Some(0);
+            queue.push_back(*source);
+        }
+    }
+    while let Some(current) = queue.pop_front() {
+        let distance = distances[current].unwrap();
+        graph.for_each_neighbour(current, &mut |neighbour| {
+            if distances[neighbour].is_none() {
+                distances[neighbour] = Some(distance + 1);
+                queue.push_back(neighbour);
+            }
+        });
+    }
+    distances
+}
+
+/// Parallel version of connected_components based on minimum label propagation.
+/// Each round every vertex pushes its label to its neighbours in parallel until no label changes.
+/// The result is the same as the one of connected_components.
+/// # Examples
+/// ```
+/// use labisu::graphs::Graph;
+/// use labisu::traversal::{connected_components, par_connected_components};
+/// let g = Graph::random(200, 0.01);
+/// assert_eq!(connected_components(&g), par_connected_components(&g));
+/// ```
+#[cfg(feature = "parallel")]
+pub fn par_connected_components<G: GraphOps + Sync>(graph: &G) -> Vec<usize> {
+    let n = graph.get_num_of_vertices();
+    let labels: Vec<AtomicUsize> = (0..n).map(AtomicUsize::new).collect();
+    let changed = AtomicBool::new(true);
+    while changed.swap(false, Ordering::Relaxed) {
+        (0..n).into_par_iter().for_each(|v| {
+            let label = labels[v].load(Ordering::Relaxed);
+            graph.for_each_neighbour(v, &mut |neighbour| {
+                if labels[neighbour].fetch_min(label, Ordering::Relaxed) > label {
+                    changed.store(true, Ordering::Relaxed);
+                }
+            });
+        });
+    }
+    let labels: Vec<usize> = labels.into_iter().map(|label| label.into_inner()).collect();
+    normalize_labels(&labels)
+}
+
+/// Parallel version of bfs, which expands whole levels of the search in parallel.
+/// # Examples
+/// ```
+/// use labisu::graphs::Graph;
+/// use labisu::traversal::{bfs, par_bfs};
+/// let g = Graph::random(200, 0.02);
+/// assert_eq!(bfs(&g, &[0, 1]), par_bfs(&g, &[0, 1]));
+/// ```
+#[cfg(feature = "parallel")]
+pub fn par_bfs<G: GraphOps + Sync>(graph: &G, sources: &[usize]) -> Vec<Option<usize>> {
+    let n = graph.get_num_of_vertices();
+    let distances: Vec<AtomicUsize> = (0..n).map(|_| AtomicUsize::new(usize::MAX)).collect();
+    let mut frontier = Vec::new();
+    for source in sources {
+        if distances[*source].swap(0, Ordering::Relaxed) == usize::MAX {
+            frontier.push(*source);
+        }
+    }
+    let mut level = 0;
+    while !frontier.is_empty() {
+        level += 1;
+        frontier = 
+            frontier
+            .par_iter()
+            .flat_map_iter(|current| {
+                let mut found = Vec::new();
+                graph.for_each_neighbour(*current, &mut |neighbour| {
+                    // only the thread that sets the distance first adds the vertex to the next level
+                    if distances[neighbour].compare_exchange(usize::MAX, level, Ordering::Relaxed, Ordering::Relaxed).is_ok() {
+                        found.push(neighbour);
+                    }
+                });
+                found
+            })
+            .collect();
+    }
+    distances
+    .into_iter()
+    .map(|distance| Some(distance.into_inner()).filter(|d| *d != usize::MAX))
+    .collect()
+}
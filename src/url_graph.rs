@@ -0,0 +1,155 @@
+use std::collections::HashSet;
+use url::Url;
+
+use super::graphs::{Graph, GraphOps};
+
+/// An error of building a UrlGraph, pointing at the vertex whose name is not a valid url.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InvalidUrl {
+    /// The index of the vertex in the graph.
+    pub idx: usize,
+    /// The name of the vertex.
+    pub name: String,
+    /// The reason why the name could not be parsed.
+    pub error: url::ParseError,
+}
+
+/// A graph of web pages, where every vertex name is guaranteed to be a valid absolute url.
+/// The parsed urls are kept next to the graph, so that their parts are available without parsing again.
+#[derive(Clone)]
+pub struct UrlGraph {
+    graph: Graph,
+    urls: Vec<Url>,
+}
+
+impl UrlGraph {
+    /// Creates an empty url graph.
+    pub fn empty() -> UrlGraph {
+        UrlGraph { graph: Graph::empty(), urls: vec![] }
+    }
+
+    /// Validates all vertex names of a graph, e.g. one returned by the crawler.
+    /// Returns the first vertex whose name is not a valid url as an Err value.
+    /// # Examples
+    /// ```
+    /// use labisu::graphs::Graph;
+    /// use labisu::url_graph::UrlGraph;
+    /// let mut g = Graph::from_names(vec!["https://pwr.edu.pl/".to_string(), "https://pwr.edu.pl/a/b".to_string()]);
+    /// g.add_edge_idx(0, 1);
+    /// let urls = UrlGraph::try_from_graph(g).unwrap();
+    /// assert_eq!(1, urls.get_graph().get_num_of_edges());
+    /// let invalid = Graph::from_names(vec!["https://pwr.edu.pl/".to_string(), "pwr.edu.pl".to_string()]);
+    /// assert_eq!(1, UrlGraph::try_from_graph(invalid).err().unwrap().idx);
+    /// ```
+    pub fn try_from_graph(graph: Graph) -> Result<UrlGraph, InvalidUrl> {
+        let urls = 
+            graph.vertices()
+            .map(|idx| {
+                let name = graph.idx_to_name_ref(idx).unwrap();
+                Url::parse(name).map_err(|error| InvalidUrl { idx, name: name.to_string(), error })
+            })
+            .collect::<Result<Vec<Url>, InvalidUrl>>()?;
+        Ok(UrlGraph { graph, urls })
+    }
+
+    /// Returns the underlying graph.
+    pub fn get_graph(&self) -> &Graph {
+        &self.graph
+    }
+
+    /// Returns the underlying graph, consuming the url graph.
+    pub fn into_graph(self) -> Graph {
+        self.graph
+    }
+
+    /// Adds a new vertex with given url and returns its index.
+    /// If the url already exists then it is not added and the index of the existing vertex is returned.
+    /// Returns an Err value if the url is not valid.
+    /// # Examples
+    /// ```
+    /// use labisu::url_graph::UrlGraph;
+    /// let mut g = UrlGraph::empty();
+    /// assert_eq!(Ok(0), g.add_vertex("https://pwr.edu.pl/"));
+    /// assert_eq!(Ok(0), g.add_vertex("https://pwr.edu.pl/"));
+    /// assert!(g.add_vertex("not a url").is_err());
+    /// assert_eq!(1, g.get_graph().get_num_of_vertices());
+    /// ```
+    pub fn add_vertex(&mut self, url: &str) -> Result<usize, url::ParseError> {
+        if let Some(idx) = self.graph.name_to_idx(url) {
+            return Ok(idx)
+        }
+        let parsed = Url::parse(url)?;
+        self.graph.add_vertex(url);
+        self.urls.push(parsed);
+        Ok(self.urls.len() - 1)
+    }
+
+    /// Adds an edge between two vertices based on their indices.
+    /// Returns boolean value - if the adding was successful.
+    pub fn add_edge_idx(&mut self, from: usize, to: usize) -> bool {
+        self.graph.add_edge_idx(from, to)
+    }
+
+    /// Returns the parsed url of the vertex with given index.
+    pub fn url(&self, idx: usize) -> Option<&Url> {
+        self.urls.get(idx)
+    }
+
+    /// Returns the host of the url of the vertex with given index, if the url has one.
+    /// # Examples
+    /// ```
+    /// use labisu::url_graph::UrlGraph;
+    /// let mut g = UrlGraph::empty();
+    /// g.add_vertex("https://pwr.edu.pl/studenci/").unwrap();
+    /// assert_eq!(Some("pwr.edu.pl"), g.host_of(0));
+    /// assert_eq!(None, g.host_of(1));
+    /// ```
+    pub fn host_of(&self, idx: usize) -> Option<&str> {
+        self.url(idx).and_then(|url| url.host_str())
+    }
+
+    /// Returns the number of non-empty segments of the path of the url of the vertex with given index.
+    /// # Examples
+    /// ```
+    /// use labisu::url_graph::UrlGraph;
+    /// let mut g = UrlGraph::empty();
+    /// g.add_vertex("https://pwr.edu.pl/").unwrap();
+    /// g.add_vertex("https://pwr.edu.pl/a/b/index.html").unwrap();
+    /// g.add_vertex("https://pwr.edu.pl/a/").unwrap();
+    /// assert_eq!(Some(0), g.path_depth(0));
+    /// assert_eq!(Some(3), g.path_depth(1));
+    /// assert_eq!(Some(1), g.path_depth(2));
+    /// ```
+    pub fn path_depth(&self, idx: usize) -> Option<usize> {
+        self.url(idx).map(|url| 
+            url.path_segments()
+            .map_or(0, |segments| segments.filter(|segment| !segment.is_empty()).count())
+        )
+    }
+}
+
+impl GraphOps for UrlGraph {
+    fn get_num_of_vertices(&self) -> usize {
+        self.graph.get_num_of_vertices()
+    }
+
+    fn get_num_of_edges(&self) -> usize {
+        self.graph.get_num_of_edges()
+    }
+
+    fn neighbours_idx(&self, idx: usize) -> Result<HashSet<usize>, &str> {
+        self.graph.neighbours_idx(idx)
+    }
+
+    fn degree(&self, idx: usize) -> usize {
+        GraphOps::degree(&self.graph, idx)
+    }
+
+    fn for_each_neighbour(&self, idx: usize, f: &mut dyn FnMut(usize)) {
+        self.graph.for_each_neighbour(idx, f)
+    }
+
+    fn highest_degree_vertices(&self, s: usize) -> Vec<usize> {
+        self.graph.highest_degree_vertices(s)
+    }
+}
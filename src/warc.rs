@@ -0,0 +1,175 @@
+use rand::Rng;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::scraper::Page;
+
+/// Formats a time as an ISO 8601 UTC timestamp, as required by the WARC-Date field.
+fn warc_date(time: SystemTime) -> String {
+    let secs = time.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let (days, rem) = (secs / 86400, secs % 86400);
+    // conversion of days since the epoch to a civil date by Howard Hinnant
+    let z = days as i64 + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + (month <= 2) as i64;
+    format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z", year, month, day, rem / 3600, rem % 3600 / 60, rem % 60)
+}
+
+/// Returns a random record identifier in the form of an urn:uuid (version 4).
+fn record_id() -> String {
+    let mut bytes: [u8; 16] = rand::thread_rng().gen();
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    format!("<urn:uuid:{}-{}-{}-{}-{}>", &hex[0..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..32])
+}
+
+/// A writer of fetched pages to a WARC 1.0 archive, one response record per page.
+pub struct WarcWriter {
+    writer: BufWriter<File>,
+}
+
+impl WarcWriter {
+    /// Creates an archive with given filename, starting with a warcinfo record.
+    pub fn create(filename: &str) -> std::io::Result<WarcWriter> {
+        let mut warc = WarcWriter { writer: BufWriter::new(File::create(filename)?) };
+        let info = format!("software: labisu/{}\r\nformat: WARC File Format 1.0\r\n", env!("CARGO_PKG_VERSION"));
+        warc.write_record("warcinfo", None, "application/warc-fields", info.as_bytes())?;
+        Ok(warc)
+    }
+
+    /// Writes a single record with given type, target uri, content type and block.
+    fn write_record(&mut self, warc_type: &str, target: Option<&str>, content_type: &str, block: &[u8]) -> std::io::Result<()> {
+        write!(self.writer, "WARC/1.0\r\n")?;
+        write!(self.writer, "WARC-Type: {}\r\n", warc_type)?;
+        if let Some(target) = target {
+            write!(self.writer, "WARC-Target-URI: {}\r\n", target)?;
+        }
+        write!(self.writer, "WARC-Date: {}\r\n", warc_date(SystemTime::now()))?;
+        write!(self.writer, "WARC-Record-ID: {}\r\n", record_id())?;
+        write!(self.writer, "Content-Type: {}\r\n", content_type)?;
+        write!(self.writer, "Content-Length: {}\r\n\r\n", block.len())?;
+        self.writer.write_all(block)?;
+        write!(self.writer, "\r\n\r\n")
+    }
+
+    /// Writes the page as a response record, whose block is the HTTP response.
+    /// A Content-Length header is rewritten to the length of the body, which is shorter than the original one
+    /// if the scraper truncated the body (see Scraper::with_max_body_size).
+    /// # Examples
+    /// ```
+    /// use labisu::scraper::Page;
+    /// use labisu::warc::{read_warc, WarcWriter};
+    /// let page = Page {
+    ///     url: "https://pwr.edu.pl/".to_owned(),
+    ///     status: 200,
+    ///     headers: vec![("content-length".to_owned(), "1000000".to_owned())],
+    ///     body: b"<a href='https://pwr.edu.pl/a'>a</a>".to_vec(),
+    /// };
+    /// let mut warc = WarcWriter::create("truncated.warc").unwrap();
+    /// warc.write_page(&page).unwrap();
+    /// warc.flush().unwrap();
+    /// let pages = read_warc("truncated.warc").unwrap();
+    /// assert_eq!(vec![("content-length".to_owned(), page.body.len().to_string())], pages[0].headers);
+    /// ```
+    pub fn write_page(&mut self, page: &Page) -> std::io::Result<()> {
+        let mut block = format!("HTTP/1.1 {}\r\n", page.status).into_bytes();
+        for (name, value) in &page.headers {
+            let value = if name.eq_ignore_ascii_case("content-length") { page.body.len().to_string() } else { value.clone() };
+            block.extend(format!("{}: {}\r\n", name, value).into_bytes());
+        }
+        block.extend(b"\r\n");
+        block.extend(&page.body);
+        self.write_record("response", Some(&page.url), "application/http; msgtype=response", &block)
+    }
+
+    /// Flushes the buffered records to the file.
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Parses the block of a response record, i.e. an HTTP response, into a page.
+fn parse_response(url: String, block: &[u8]) -> Option<Page> {
+    let split = block.windows(4).position(|w| w == b"\r\n\r\n")?;
+    let head = String::from_utf8_lossy(&block[..split]);
+    let mut lines = head.split("\r\n");
+    let status = lines.next()?.split_whitespace().nth(1)?.parse().ok()?;
+    let headers = 
+        lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_owned(), value.trim().to_owned()))
+        .collect();
+    Some(Page { url, status, headers, body: block[split + 4..].to_vec() })
+}
+
+/// Reads all response records of a WARC archive with given filename as pages, e.g. to scrape them again without fetching.
+/// Records of other types and responses which are not HTTP responses are skipped.
+/// # Examples
+/// ```
+/// use labisu::scraper::Page;
+/// use labisu::warc::{read_warc, WarcWriter};
+/// let page = Page {
+///     url: "https://pwr.edu.pl/".to_owned(),
+///     status: 200,
+///     headers: vec![("content-type".to_owned(), "text/html".to_owned())],
+///     body: b"<a href='https://pwr.edu.pl/a'>a</a>".to_vec(),
+/// };
+/// let mut warc = WarcWriter::create("pages.warc").unwrap();
+/// warc.write_page(&page).unwrap();
+/// warc.flush().unwrap();
+/// let pages = read_warc("pages.warc").unwrap();
+/// assert_eq!(1, pages.len());
+/// assert_eq!(page.url, pages[0].url);
+/// assert_eq!(page.headers, pages[0].headers);
+/// assert_eq!(page.body, pages[0].body);
+/// ```
+pub fn read_warc(filename: &str) -> std::io::Result<Vec<Page>> {
+    let mut reader = BufReader::new(File::open(filename)?);
+    let mut pages = vec![];
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(pages)
+        }
+        if !line.starts_with("WARC/") {
+            continue // the empty lines separating records
+        }
+
+        let (mut warc_type, mut target, mut length) = (String::new(), String::new(), 0);
+        loop {
+            line.clear();
+            reader.read_line(&mut line)?;
+            let header = line.trim_end();
+            if header.is_empty() {
+                break
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                let value = value.trim().to_owned();
+                match name.to_ascii_lowercase().as_str() {
+                    "warc-type" => warc_type = value,
+                    "warc-target-uri" => target = value,
+                    "content-length" => length = value.parse().map_err(|_| {
+                        std::io::Error::new(std::io::ErrorKind::InvalidData, "Invalid Content-Length of a record.")
+                    })?,
+                    _ => (),
+                }
+            }
+        }
+
+        let mut block = vec![0; length];
+        reader.read_exact(&mut block)?;
+        if warc_type == "response" {
+            if let Some(page) = parse_response(target, &block) {
+                pages.push(page);
+            }
+        }
+    }
+}